@@ -16,9 +16,11 @@
     FakeReadCause, LocalDecl, LocalInfo, LocalKind, Location, Operand, Place, PlaceRef,
     ProjectionElem, Rvalue, Statement, StatementKind, Terminator, TerminatorKind, VarBindingForm,
 };
-use rustc_middle::ty::{self, subst::Subst, suggest_constraining_type_params, PredicateKind, Ty};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::subst::Subst;
+use rustc_middle::ty::{self, suggest_constraining_type_params, PredicateKind, Ty, TyCtxt};
 use rustc_mir_dataflow::move_paths::{InitKind, MoveOutIndex, MovePathIndex};
-use rustc_span::def_id::LocalDefId;
+use rustc_span::def_id::{DefId, LocalDefId};
 use rustc_span::hygiene::DesugaringKind;
 use rustc_span::symbol::sym;
 use rustc_span::{BytePos, Span, Symbol};
@@ -40,6 +42,21 @@
     DescribePlaceOpt, RegionName, RegionNameSource, UseSpans,
 };
 
+/// Diagnostic items of functions running their argument on another thread or task, which may
+/// outlive the caller and so require the argument to be `'static`. Diagnostic items are internal
+/// to the compiler and the standard library, so other crates' spawn functions can't be listed.
+const SPAWN_FUNCTIONS: &[Symbol] = &[sym::thread_spawn, sym::thread_builder_spawn];
+
+/// Explains why a closure or async block passed to `spawn_fn` must be `'static`.
+fn spawned_work_note(tcx: TyCtxt<'_>, spawn_fn: DefId, kind: &str) -> String {
+    format!(
+        "the {} passed to `{}` may run after the current function has returned, so it must be \
+         `'static` and cannot borrow anything the function owns",
+        kind,
+        with_no_trimmed_paths!(tcx.def_path_str(spawn_fn))
+    )
+}
+
 #[derive(Debug)]
 struct MoveSite {
     /// Index of the "move out" that we found. The `MoveData` can
@@ -1371,6 +1388,10 @@ fn report_local_value_does_not_live_long_enough(
                 None,
                 None,
             );
+
+            if let BorrowExplanation::MustBeValidFor { category, span, .. } = explanation {
+                self.explain_spawned_closure_borrow(&mut err, category, span);
+            }
         }
 
         err
@@ -1749,6 +1770,9 @@ fn report_escaping_closure_capture(
                     let msg = format!("function requires argument type to outlive `{}`", fr_name);
                     err.span_note(constraint_span, &msg);
                 }
+                if let Some(spawn_fn) = self.spawn_fn(category) {
+                    err.note(&spawned_work_note(tcx, spawn_fn, kind));
+                }
             }
             _ => bug!(
                 "report_escaping_closure_capture called with unexpected constraint \
@@ -1760,6 +1784,136 @@ fn report_escaping_closure_capture(
         err
     }
 
+    /// Returns the function called if `category` is an argument of one of `SPAWN_FUNCTIONS`.
+    fn spawn_fn(&self, category: ConstraintCategory<'tcx>) -> Option<DefId> {
+        let ConstraintCategory::CallArgument(Some(func_ty)) = category else { return None };
+        let ty::FnDef(def_id, _) = *func_ty.kind() else { return None };
+        let name = self.infcx.tcx.get_diagnostic_name(def_id)?;
+        SPAWN_FUNCTIONS.contains(&name).then_some(def_id)
+    }
+
+    /// Explains why a borrow must be `'static` if it flows into a closure handed to a spawn
+    /// function. A `move` closure can still borrow from the current function through the
+    /// references it captures, so for those, suggests giving it its own copy of the data.
+    fn explain_spawned_closure_borrow(
+        &self,
+        err: &mut Diagnostic,
+        category: ConstraintCategory<'tcx>,
+        constraint_span: Span,
+    ) {
+        let tcx = self.infcx.tcx;
+        let Some(spawn_fn) = self.spawn_fn(category) else { return };
+
+        // Find the closure among the arguments of the call the constraint comes from.
+        let closure_def_id = self.body.basic_blocks().iter().find_map(|block| {
+            let terminator = block.terminator();
+            let TerminatorKind::Call { func, args, .. } = &terminator.kind else { return None };
+            if terminator.source_info.span != constraint_span {
+                return None;
+            }
+            match func.ty(self.body, tcx).kind() {
+                ty::FnDef(def_id, _) if *def_id == spawn_fn => {}
+                _ => return None,
+            }
+            args.iter().find_map(|arg| match *arg.ty(self.body, tcx).kind() {
+                ty::Closure(def_id, _) | ty::Generator(def_id, _, _) => def_id.as_local(),
+                _ => None,
+            })
+        });
+        let Some(closure_def_id) = closure_def_id else { return };
+        let closure_hir_id = tcx.hir().local_def_id_to_hir_id(closure_def_id);
+        let hir::ExprKind::Closure(&hir::Closure { capture_clause, .. }) =
+            tcx.hir().expect_expr(closure_hir_id).kind
+        else {
+            return;
+        };
+        let kind = match tcx.generator_kind(closure_def_id) {
+            Some(GeneratorKind::Async(_)) => "async block",
+            Some(GeneratorKind::Gen) => "generator",
+            None => "closure",
+        };
+        err.note(&spawned_work_note(tcx, spawn_fn, kind));
+
+        if capture_clause != hir::CaptureBy::Value {
+            // Without `move` the closure borrows its captures itself, and that error suggests
+            // adding `move`.
+            return;
+        }
+
+        // The operands the closure is built from, one for each of its captures.
+        let operands = self.body.basic_blocks().iter().find_map(|block| {
+            block.statements.iter().find_map(|stmt| match &stmt.kind {
+                StatementKind::Assign(box (_, Rvalue::Aggregate(kind, operands))) => match **kind {
+                    AggregateKind::Closure(def_id, _) | AggregateKind::Generator(def_id, _, _)
+                        if def_id == closure_def_id.to_def_id() =>
+                    {
+                        Some(operands)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+        });
+        let Some(operands) = operands else { return };
+
+        // The captures holding a borrow of data the current function owns. The types of the
+        // captures have their regions erased, so a borrow is found by what the captured local
+        // was assigned. References to statics and literals aren't borrows borrowck tracks, so
+        // `&'static` captures aren't counted.
+        let mut borrowing_captures = tcx
+            .typeck(closure_def_id)
+            .closure_min_captures_flattened(closure_def_id)
+            .zip(operands)
+            .filter(|(capture, _)| capture.place.projections.is_empty())
+            .filter(|(_, operand)| {
+                let Some(place) = operand.place() else { return false };
+                self.borrow_set.location_map.values().any(|borrow| borrow.assigned_place == place)
+            })
+            .map(|(capture, _)| capture);
+        let (Some(capture), None) = (borrowing_captures.next(), borrowing_captures.next()) else {
+            return;
+        };
+        let name = capture.to_string(tcx);
+        // Cloning the reference itself would still borrow, so only suggest it when what it
+        // points to can be cloned.
+        let pointee_is_clone = match *capture.place.ty().kind() {
+            ty::Ref(_, pointee, _) => tcx.lang_items().clone_trait().map_or(false, |clone_trait| {
+                self.infcx
+                    .type_implements_trait(clone_trait, pointee, ty::List::empty(), self.param_env)
+                    .must_apply_modulo_regions()
+            }),
+            _ => false,
+        };
+        if !pointee_is_clone {
+            err.help(&format!(
+                "the {} still borrows through `{}`; to move the data into it instead, share it \
+                 through an `Arc`",
+                kind, name
+            ));
+            return;
+        }
+        let Some(hir::Node::Stmt(stmt)) = tcx
+            .hir()
+            .parent_iter(closure_hir_id)
+            .map(|(_, node)| node)
+            .find(|node| matches!(node, hir::Node::Stmt(_)))
+        else {
+            return;
+        };
+        let sm = tcx.sess.source_map();
+        let indent = sm.indentation_before(stmt.span).unwrap_or_default();
+        err.span_suggestion_verbose(
+            stmt.span.shrink_to_lo(),
+            &format!(
+                "the {} still borrows through `{}`; to move a copy of the data into it instead, \
+                 clone it before the call, or share it through an `Arc`",
+                kind, name
+            ),
+            format!("let {name} = {name}.clone();\n{indent}"),
+            Applicability::MaybeIncorrect,
+        );
+    }
+
     fn report_escaping_data(
         &mut self,
         borrow_span: Span,
@@ -3,7 +3,7 @@
 use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::{Applicability, Diagnostic, DiagnosticBuilder, ErrorGuaranteed, MultiSpan};
 use rustc_hir::def_id::DefId;
-use rustc_hir::intravisit::Visitor;
+use rustc_hir::intravisit::{walk_expr, Visitor};
 use rustc_hir::{self as hir, Item, ItemKind, Node};
 use rustc_infer::infer::{
     error_reporting::nice_region_error::{
@@ -21,6 +21,7 @@
 use rustc_middle::ty::{self, RegionVid, Ty};
 use rustc_span::symbol::{kw, sym, Ident};
 use rustc_span::Span;
+use rustc_trait_selection::infer::InferCtxtExt;
 
 use crate::borrowck_errors;
 use crate::session_diagnostics::GenericDoesNotLiveLongEnough;
@@ -649,6 +650,8 @@ fn report_escaping_data_error(
             _ => {}
         }
 
+        self.suggest_copied_or_cloned_iterator(&mut diag, *span);
+
         diag
     }
 
@@ -718,6 +721,7 @@ fn report_general_error(
         self.add_static_impl_trait_suggestion(&mut diag, *fr, fr_name, *outlived_fr);
         self.suggest_adding_lifetime_params(&mut diag, *fr, *outlived_fr);
         self.suggest_move_on_borrowing_closure(&mut diag);
+        self.suggest_copied_or_cloned_iterator(&mut diag, *span);
 
         diag
     }
@@ -778,7 +782,6 @@ fn add_static_impl_trait_suggestion(
                 lifetime.to_string(),
                 Some(arg),
                 captures,
-                Some((param.param_ty_span, param.param_ty.to_string())),
             );
         }
     }
@@ -901,7 +904,7 @@ fn suggest_adding_lifetime_params(
             return
         };
 
-        suggest_adding_lifetime_params(self.infcx.tcx, sub, ty_sup, ty_sub, diag);
+        suggest_adding_lifetime_params(self.infcx.tcx, sub, sup, ty_sup, ty_sub, diag);
     }
 
     fn suggest_move_on_borrowing_closure(&self, diag: &mut Diagnostic) {
@@ -938,4 +941,128 @@ fn suggest_move_on_borrowing_closure(&self, diag: &mut Diagnostic) {
             );
         }
     }
+
+    /// Suggests copying or cloning the items of an iterator over borrowed data when the error
+    /// at `span` is caused by the iterator chain there being collected or returned, as the
+    /// items are then no longer tied to the data they were borrowed from.
+    ///
+    /// ```text
+    /// help: consider copying the iterator's items instead of borrowing them
+    ///    |
+    /// LL |     store(v.iter().copied().collect());
+    ///    |                   +++++++++
+    /// ```
+    fn suggest_copied_or_cloned_iterator(&self, diag: &mut Diagnostic, span: Span) {
+        struct ExprFinder<'hir> {
+            span: Span,
+            expr: Option<&'hir hir::Expr<'hir>>,
+        }
+
+        impl<'hir> Visitor<'hir> for ExprFinder<'hir> {
+            fn visit_expr(&mut self, expr: &'hir hir::Expr<'hir>) {
+                if self.expr.is_none() && expr.span == self.span {
+                    self.expr = Some(expr);
+                }
+                walk_expr(self, expr);
+            }
+        }
+
+        let tcx = self.infcx.tcx;
+        let map = tcx.hir();
+        let mut finder = ExprFinder { span, expr: None };
+        finder.visit_body(map.body(map.body_owned_by(self.mir_def_id())));
+        let Some(mut expr) = finder.expr else { return };
+
+        // The error usually points at the call borrowing the data, so move up to the end of the
+        // method chain it is the start of.
+        while let Some(hir::Node::Expr(parent)) = map.find(map.get_parent_node(expr.hir_id))
+            && let hir::ExprKind::MethodCall(_, [receiver, ..], _) = parent.kind
+            && receiver.hir_id == expr.hir_id
+        {
+            expr = parent;
+        }
+        // The chain may also be passed to the call the error points at.
+        let args = match expr.kind {
+            hir::ExprKind::Call(_, args) | hir::ExprKind::MethodCall(_, [_, args @ ..], _) => args,
+            _ => &[],
+        };
+
+        let typeck_results = tcx.typeck(self.mir_def_id());
+        for mut iter in args.iter().chain(std::iter::once(expr)) {
+            if let hir::ExprKind::MethodCall(segment, [receiver, ..], _) = iter.kind
+                && segment.ident.name == sym::collect
+            {
+                iter = receiver;
+            }
+            if iter.span.from_expansion() || !Self::borrows_through_iter(iter) {
+                continue;
+            }
+            let Some(iter_ty) = typeck_results.node_type_opt(iter.hir_id) else { continue };
+            let Some(item_ty) = self.iterator_item_ty(iter_ty) else { continue };
+            let ty::Ref(_, pointee, hir::Mutability::Not) = *item_ty.kind() else { continue };
+            // Copies of items that borrow data themselves, like the `&str`s of a `&Vec<&str>`,
+            // may not live long enough either. The regions of `typeck` results are erased, so
+            // this also gives up on items that only borrow `'static` data.
+            if tcx.any_free_region_meets(&pointee, |r| !r.is_static()) {
+                return;
+            }
+            let implements = |trait_def_id: Option<DefId>| {
+                trait_def_id.map_or(false, |trait_def_id| {
+                    self.infcx
+                        .type_implements_trait(
+                            trait_def_id,
+                            pointee,
+                            ty::List::empty(),
+                            self.param_env,
+                        )
+                        .must_apply_modulo_regions()
+                })
+            };
+            let (verb, method) = if implements(tcx.lang_items().copy_trait()) {
+                ("copying", ".copied()")
+            } else if implements(tcx.lang_items().clone_trait()) {
+                ("cloning", ".cloned()")
+            } else {
+                return;
+            };
+            diag.span_suggestion_verbose(
+                iter.span.shrink_to_hi(),
+                format!("consider {verb} the iterator's items instead of borrowing them"),
+                method,
+                Applicability::MaybeIncorrect,
+            );
+            return;
+        }
+    }
+
+    /// Whether `expr` is a method chain starting with a call to `iter`.
+    fn borrows_through_iter(mut expr: &hir::Expr<'_>) -> bool {
+        while let hir::ExprKind::MethodCall(segment, [receiver, ..], _) = expr.kind {
+            if segment.ident.name == sym::iter {
+                return true;
+            }
+            expr = receiver;
+        }
+        false
+    }
+
+    /// Returns the `Item` type of `ty` if it is an iterator.
+    fn iterator_item_ty(&self, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+        let tcx = self.infcx.tcx;
+        let ty = tcx.erase_regions(ty);
+        let iterator = tcx.get_diagnostic_item(sym::Iterator)?;
+        if !self
+            .infcx
+            .type_implements_trait(iterator, ty, ty::List::empty(), self.param_env)
+            .must_apply_modulo_regions()
+        {
+            return None;
+        }
+        let item = tcx
+            .associated_items(iterator)
+            .in_definition_order()
+            .find(|item| item.kind == ty::AssocKind::Type)?;
+        let item_ty = tcx.mk_projection(item.def_id, tcx.mk_substs_trait(ty, &[]));
+        tcx.try_normalize_erasing_regions(self.param_env, item_ty).ok()
+    }
 }
@@ -4,6 +4,7 @@
 use rustc_errors::Diagnostic;
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
+use rustc_infer::infer::error_reporting::nice_region_error::find_anon_type;
 use rustc_middle::ty::print::RegionHighlightMode;
 use rustc_middle::ty::subst::{GenericArgKind, SubstsRef};
 use rustc_middle::ty::{self, DefIdTree, RegionVid, Ty};
@@ -251,7 +252,8 @@ pub(crate) fn give_region_a_name(&self, fr: RegionVid) -> Option<RegionName> {
             .or_else(|| self.give_name_if_anonymous_region_appears_in_upvars(fr))
             .or_else(|| self.give_name_if_anonymous_region_appears_in_output(fr))
             .or_else(|| self.give_name_if_anonymous_region_appears_in_yield_ty(fr))
-            .or_else(|| self.give_name_if_anonymous_region_appears_in_impl_signature(fr));
+            .or_else(|| self.give_name_if_anonymous_region_appears_in_impl_signature(fr))
+            .or_else(|| self.give_name_if_anonymous_region_appears_in_impl_trait_argument(fr));
 
         if let Some(ref value) = value {
             self.region_names.try_borrow_mut().unwrap().insert(fr, value.clone());
@@ -893,4 +895,44 @@ fn give_name_if_anonymous_region_appears_in_impl_signature(
             ),
         })
     }
+
+    /// Checks for the case where `fr` is a lifetime elided in the bounds of an argument-position
+    /// `impl Trait`, like the one in `impl Iterator<Item = &u8>`. Such lifetimes are early-bound
+    /// parameters of the function without a name, so we point at the type mentioning them:
+    ///
+    /// ```text
+    ///  | fn foo(items: impl Iterator<Item = &u8>) { .. }
+    ///  |                                    - let's call the lifetime of this reference `'1`
+    /// ```
+    fn give_name_if_anonymous_region_appears_in_impl_trait_argument(
+        &self,
+        fr: RegionVid,
+    ) -> Option<RegionName> {
+        let error_region = self.to_error_region(fr)?;
+        let ty::ReEarlyBound(region) = *error_region else {
+            return None;
+        };
+        if region.has_name() {
+            return None;
+        };
+
+        let tcx = self.infcx.tcx;
+        if tcx.parent(region.def_id) != self.mir_def_id().to_def_id() {
+            return None;
+        }
+
+        let bound_region = ty::BoundRegionKind::BrNamed(region.def_id, region.name);
+        let (hir_ty, _) = find_anon_type(tcx, error_region, &bound_region)?;
+        let highlight = match hir_ty.kind {
+            hir::TyKind::Rptr(..) => {
+                RegionNameHighlight::MatchedHirTy(tcx.sess.source_map().start_point(hir_ty.span))
+            }
+            _ => RegionNameHighlight::MatchedAdtAndSegment(hir_ty.span),
+        };
+
+        Some(RegionName {
+            name: self.synthesize_region_name(),
+            source: RegionNameSource::AnonRegionFromArgument(highlight),
+        })
+    }
 }
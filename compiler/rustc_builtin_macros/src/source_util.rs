@@ -6,7 +6,7 @@
 use rustc_expand::base::{self, *};
 use rustc_expand::module::DirOwnership;
 use rustc_parse::parser::{ForceCollect, Parser};
-use rustc_parse::{self, new_parser_from_file};
+use rustc_parse::{self, file_to_source_file, source_file_to_parser};
 use rustc_session::lint::builtin::INCOMPLETE_INCLUDE;
 use rustc_span::symbol::Symbol;
 use rustc_span::{self, Pos, Span};
@@ -97,6 +97,7 @@ pub fn expand_include<'cx>(
     sp: Span,
     tts: TokenStream,
 ) -> Box<dyn base::MacResult + 'cx> {
+    let call_site = sp;
     let sp = cx.with_def_site_ctxt(sp);
     let Some(file) = get_single_str_from_tts(cx, sp, tts, "include!") else {
         return DummyResult::any(sp);
@@ -109,7 +110,11 @@ pub fn expand_include<'cx>(
             return DummyResult::any(sp);
         }
     };
-    let p = new_parser_from_file(cx.parse_sess(), &file, Some(sp));
+    let included_file = file_to_source_file(cx.parse_sess(), &file, Some(sp));
+    // The code of the file has no expansion info pointing back to this invocation, so
+    // remember it for errors in that code.
+    cx.source_map().save_include_site(&included_file, call_site);
+    let p = source_file_to_parser(cx.parse_sess(), included_file);
 
     // If in the included file we have e.g., `mod bar;`,
     // then the path of `bar.rs` should be relative to the directory of `file`.
@@ -0,0 +1 @@
+errors_related_error_note = this error may be caused by this previous error
@@ -0,0 +1,218 @@
+infer_type_annotations_needed = type annotations needed{$source_kind ->
+        [closure] {" "}for the closure `{$source_name}`
+        [normal] {" "}for `{$source_name}`
+        *[other] {""}
+    }
+    .teach = Types are inferred from how values are used: the arguments a value is passed as, the fields and methods it is used with and the types it is compared or assigned to. When none of these uses determine a type, for example because the value is only used with generic functions, the compiler needs a type annotation to know which type was meant. Adding one where the value is created usually fixes this error and any errors caused by it.
+
+infer_type_annotations_needed_in_const = type annotations needed{$source_kind ->
+        [closure] {" "}for the closure `{$source_name}`
+        [normal] {" "}for `{$source_name}`
+        *[other] {""}
+    } in this constant expression
+    .teach = {infer_type_annotations_needed.teach}
+
+infer_inference_errors_summarized = {$count} more type annotation {$count ->
+        [one] error
+        *[other] errors
+    } with error code {$code} in this function {$count ->
+        [one] was
+        *[other] were
+    } not shown
+    .note = use `-Zinference-error-budget=0` to see all of them
+
+infer_cannot_infer_generic_args = cannot infer {$generic_param_descr} {$generic_param_names}{$generic_parent_descr ->
+        [none] {""}
+        *[other] {" "}declared on the {$generic_parent_descr} `{$generic_parent_name}`
+    }
+
+infer_included_here = the code containing this error was included here
+
+infer_where_x_is_specified = {$specified ->
+        [placeholders] , where the placeholders `_` are specified
+        [param] , where {$param_prefix} `{$param_name}` is specified
+        *[none] {""}
+    }
+
+# kind: suggestion
+infer_let_binding_suggestion = consider giving `{$pattern_name}` an explicit type{infer_where_x_is_specified}
+
+# kind: suggestion
+infer_pattern_type_suggestion = consider giving this pattern a type{infer_where_x_is_specified}
+
+# kind: label
+infer_macro_binding_call_label = this macro call requires the type to be inferable
+
+# kind: note
+infer_macro_binding_note = the binding is introduced by this macro; consider making the macro accept an explicit type
+
+# kind: note
+infer_external_macro_binding_note = the binding is introduced by the macro `{$macro_name}` from the `{$macro_crate}` crate; consider making the macro accept an explicit type
+
+# kind: note
+infer_unnameable_type_note = the type `{$unnameable_ty}` can't be written out here, as {$unnameable_item_is_anonymous ->
+        [true] it contains the type of a {$unnameable_item_descr}
+        *[false] the {$unnameable_item_descr} `{$unnameable_item_path}` isn't visible from here
+    }
+
+# kind: suggestion
+infer_closure_arg_suggestion = consider giving this closure parameter an explicit type{infer_where_x_is_specified}
+
+# kind: suggestion
+infer_combinator_closure_param_suggestion = consider giving this closure parameter an explicit type
+
+# kind: note
+infer_combinator_closure_param_note = consider annotating this closure parameter
+
+# kind: note
+infer_empty_collection_note = no {$unknown_types ->
+        [element] elements
+        *[other] entries
+    } are ever added to this `{$collection}`, so its {$unknown_types ->
+        [element] element type
+        [key] key type
+        [value] value type
+        *[key_and_value] key and value types
+    } cannot be inferred
+
+# kind: note
+infer_unconstraining_uses_note = `{$binding_name}` is used here, but none of its uses constrain its type
+# kind: label
+infer_unconstraining_use_label = the type is not constrained by this use
+
+# kind: suggestion
+infer_generic_arg_suggestion = consider specifying the generic {$count ->
+        [one] argument
+        *[other] arguments
+    }
+
+# kind: suggestion
+infer_collect_items_suggestion = consider specifying how to collect the items
+
+# kind: note
+infer_generic_return_method_note = the method `{$generic_method_name}` can return any type implementing `{$generic_method_bound}`; you need to specify which
+
+# kind: note
+infer_collect_result_items_note = `collect` can gather `Result` items into `Result<Vec<_>, _>`, which is the first error if there is one, or into `Vec<Result<_, _>>`, which keeps every result
+
+# kind: note
+infer_collect_option_items_note = `collect` can gather `Option` items into `Option<Vec<_>>`, which is `None` if any item is, or into `Vec<Option<_>>`, which keeps every item
+
+# kind: suggestion
+infer_fully_qualified_path_suggestion = try using a fully qualified path to specify the expected types
+
+# kind: suggestion
+infer_self_generic_arg_suggestion = consider specifying the generic {$count ->
+        [one] argument
+        *[other] arguments
+    } declared on the {$item_descr} `{$item_name}`
+
+# kind: suggestion
+infer_trait_generic_arg_suggestion = consider specifying the generic argument on the trait, not the method
+
+# kind: note
+infer_trait_generic_arg_help = the generic parameter `{$trait_param_name}` is declared on the trait `{$trait_name}`, so it can't be specified on `{$method_name}`
+
+# kind: suggestion
+infer_closure_return_suggestion = try giving this closure an explicit return type
+
+# kind: suggestion
+infer_let_introduction_suggestion = consider moving this argument into a binding with an explicit type
+
+infer_cannot_satisfy_predicate = cannot satisfy `{$predicate}`
+
+infer_lifetime_mismatch = lifetime mismatch
+    .teach = Each elided lifetime in the parameters of a function becomes a distinct lifetime parameter, so `fn foo(x: &u8, y: &u8)` is short for `fn foo<'a, 'b>(x: &'a u8, y: &'b u8)`. When data flows from one such reference into another, the compiler can't assume that the two lifetimes are related, which has to be stated by giving both references the same named lifetime.
+
+# kind: label
+infer_mirrored_data_flow_label = ...and data{$mirror_from ->
+        [none] {""}
+        *[other] {" "}from `{$mirror_from}`
+    } flows{$mirror_into ->
+        [none] {""}
+        *[other] {" "}into `{$mirror_into}`
+    } here
+
+# kind: label
+infer_lifetime_declared_here_label = lifetime declared here
+
+# kind: note
+infer_elided_lifetimes_distinct_note = each elided lifetime in input position becomes a distinct lifetime
+
+# kind: suggestion
+infer_lifetime_param_suggestion = consider {$is_reuse ->
+        [true] reusing the lifetime parameter
+        *[false] introducing a named lifetime parameter
+    } `{$param_name}`{$is_impl ->
+        [true] {" "}and update trait if needed
+        *[false] {""}
+    }
+
+# kind: suggestion
+infer_lifetime_param_provided_method_suggestion = consider {$reused ->
+        [trait] reusing the lifetime parameter `{$param_name}` of the trait
+        [method] reusing the lifetime parameter `{$param_name}`
+        *[none] introducing a named lifetime parameter `{$param_name}` on this provided method
+    }
+
+# kind: note
+infer_lifetimes_fixed_by_trait_note = the lifetimes of `{$method_name}` can't be made the same, as its signature is fixed by the trait `{$trait_name}`, which leaves them unrelated
+
+# kind: suggestion
+infer_lifetime_param_trait_suggestion = consider {$trait_is_reuse ->
+        [true] reusing the lifetime parameter
+        *[false] introducing a named lifetime parameter
+    } `{$trait_param_name}` in the trait instead, which changes the signature for every implementation
+
+# kind: note
+infer_provided_method_signature_note = changing the signature of a provided method changes it for every implementation of the trait
+
+# kind: note
+infer_provided_method_impls_note = these implementations of the trait may need to change as well
+# kind: label
+infer_provided_method_override_label = overrides the method
+
+# kind: suggestion
+infer_lifetime_param_apit_suggestion = consider {$is_reuse ->
+        [true] reusing the lifetime parameter
+        *[false] introducing a named lifetime parameter
+    } `{$param_name}` and a named type parameter in place of `impl Trait`
+
+# kind: note
+infer_lifetime_param_apit_note = unlike with `impl Trait` arguments, callers can specify the new type parameter with turbofish syntax, so this changes the function's API
+
+# kind: note
+infer_object_lifetime_default_note = the lifetime of this trait object defaults to `{$object_lifetime}` rather than to the lifetime of the reference it is behind
+
+# kind: note
+infer_higher_ranked_elided_lifetimes_note = the elided lifetimes in this {$sugar_is_fn_ptr ->
+        [true] function pointer type
+        *[false] `Fn` trait bound
+    } are higher-ranked, so they can't be tied to a lifetime of the enclosing function
+
+# kind: suggestion
+infer_higher_ranked_lifetime_suggestion = consider introducing a higher-ranked lifetime on the {$sugar_is_fn_ptr ->
+        [true] function pointer type
+        *[false] `Fn` trait bound
+    }
+
+# kind: suggestion
+infer_static_return_suggestion = this method only returns `'static` data, so it can return a `'static` reference as the `trait` requires
+
+infer_requirement = {$requirement ->
+        [method_compat] method type matches the trait declaration
+        [type_compat] associated type matches the trait declaration
+        [const_compat] const matches the trait declaration
+        [expr_assignable] expression is assignable
+        [if_else_different] `if` and `else` have compatible types
+        [no_else] `if` missing an `else` returns `()`
+        [fn_main_correct_type] `main` function has the correct type
+        [fn_start_correct_type] `#[start]` function has the correct type
+        [intrinsic_correct_type] intrinsic has the correct type
+        [method_correct_type] method receiver has the correct type
+        [return_type] returned value matches the return type of the function
+        *[other] types are compatible
+    }
+
+infer_subtype = ...so that the {infer_requirement}
+infer_subtype_2 = ...so that {infer_requirement}
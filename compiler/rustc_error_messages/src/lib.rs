@@ -35,7 +35,10 @@
     borrowck => "../locales/en-US/borrowck.ftl",
     builtin_macros => "../locales/en-US/builtin_macros.ftl",
     const_eval => "../locales/en-US/const_eval.ftl",
+    errors => "../locales/en-US/errors.ftl",
     expand => "../locales/en-US/expand.ftl",
+    #[code_free_suggestions]
+    infer => "../locales/en-US/infer.ftl",
     interface => "../locales/en-US/interface.ftl",
     lint => "../locales/en-US/lint.ftl",
     parser => "../locales/en-US/parser.ftl",
@@ -46,6 +49,12 @@
 
 pub use fluent_generated::{self as fluent, DEFAULT_LOCALE_RESOURCES};
 
+/// Returns `true` if the Fluent message `id` has a `.teach` attribute, which is emitted
+/// as an extra note with `-Zteach`.
+pub fn has_teach_attr(id: &str) -> bool {
+    fluent_generated::TEACHABLE_MESSAGES.contains(&id)
+}
+
 pub type FluentBundle = fluent_bundle::bundle::FluentBundle<FluentResource, IntlLangMemoizer>;
 
 #[cfg(parallel_compiler)]
@@ -278,7 +287,7 @@ fn from(s: S) -> Self {
 /// diagnostic messages.
 ///
 /// Intended to be removed once diagnostics are entirely translatable.
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Encodable, Decodable)]
 #[rustc_diagnostic_item = "DiagnosticMessage"]
 pub enum DiagnosticMessage {
     /// Non-translatable diagnostic message.
@@ -328,6 +337,18 @@ pub fn expect_str(&self) -> &str {
             _ => panic!("expected non-translatable diagnostic message"),
         }
     }
+
+    /// Returns the Fluent identifier and attribute of this message, or `None` if it is of the
+    /// legacy, non-translatable variety.
+    ///
+    /// Only intended for asserting on the structure of diagnostics in tests.
+    #[doc(hidden)]
+    pub fn fluent_identifier(&self) -> Option<(&str, Option<&str>)> {
+        match self {
+            DiagnosticMessage::Str(_) => None,
+            DiagnosticMessage::FluentIdentifier(id, attr) => Some((id, attr.as_deref())),
+        }
+    }
 }
 
 /// `From` impl that enables existing diagnostic calls to functions which now take
@@ -338,6 +359,76 @@ fn from(s: S) -> Self {
     }
 }
 
+/// Defines a type for the Fluent messages which are only meant for one kind of subdiagnostic,
+/// and the trait for the messages taken by the methods of `Diagnostic` adding that kind: the
+/// messages of the type, and all untyped messages. `fluent_messages!` generates messages of these
+/// types for the messages declaring their kind with a `# kind: ...` comment, so that a message of
+/// one kind can't be used as another kind. `as_message` gets the message out where it is needed
+/// anyway.
+macro_rules! subdiagnostic_kind_message {
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident;
+        $(#[$trait_attr:meta])*
+        pub trait $trait_name:ident { fn $method:ident }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub struct $name(DiagnosticMessage);
+
+        impl $name {
+            pub const fn new(message: DiagnosticMessage) -> Self {
+                $name(message)
+            }
+
+            pub fn as_message(self) -> DiagnosticMessage {
+                self.0
+            }
+        }
+
+        $(#[$trait_attr])*
+        pub trait $trait_name {
+            fn $method(self) -> SubdiagnosticMessage;
+        }
+
+        impl<T: Into<SubdiagnosticMessage>> $trait_name for T {
+            fn $method(self) -> SubdiagnosticMessage {
+                self.into()
+            }
+        }
+
+        impl $trait_name for $name {
+            fn $method(self) -> SubdiagnosticMessage {
+                self.0.into()
+            }
+        }
+    };
+}
+
+subdiagnostic_kind_message! {
+    /// A Fluent message which is only meant to be the message of a suggestion.
+    #[rustc_diagnostic_item = "SuggestionMessage"]
+    pub struct SuggestionMessage;
+    /// Messages taken by the suggestion methods of `Diagnostic`.
+    pub trait IntoSuggestionMessage { fn into_suggestion_message }
+}
+
+subdiagnostic_kind_message! {
+    /// A Fluent message which is only meant to be the message of a label.
+    #[rustc_diagnostic_item = "LabelMessage"]
+    pub struct LabelMessage;
+    /// Messages taken by `Diagnostic::span_label`.
+    pub trait IntoLabelMessage { fn into_label_message }
+}
+
+subdiagnostic_kind_message! {
+    /// A Fluent message which is only meant to be the message of a note, help or warning.
+    #[rustc_diagnostic_item = "NoteMessage"]
+    pub struct NoteMessage;
+    /// Messages taken by the methods of `Diagnostic` adding notes, helps and warnings.
+    pub trait IntoNoteMessage { fn into_note_message }
+}
+
 /// Translating *into* a subdiagnostic message from a diagnostic message is a little strange - but
 /// the subdiagnostic functions (e.g. `span_label`) take a `SubdiagnosticMessage` and the
 /// subdiagnostic derive refers to typed identifiers that are `DiagnosticMessage`s, so need to be
@@ -406,6 +497,12 @@ pub fn push_span_label(&mut self, span: Span, label: impl Into<DiagnosticMessage
         self.span_labels.push((span, label.into()));
     }
 
+    /// Sorts the labels by their spans, keeping the ones with the same span in the order they
+    /// were pushed.
+    pub fn sort_span_labels(&mut self) {
+        self.span_labels.sort_by_key(|&(span, _)| span);
+    }
+
     /// Selects the first primary span (if any).
     pub fn primary_span(&self) -> Option<Span> {
         self.primary_spans.first().cloned()
@@ -1,7 +1,8 @@
 use crate::snippet::Style;
 use crate::{
-    CodeSuggestion, DiagnosticMessage, EmissionGuarantee, Level, LintDiagnosticBuilder, MultiSpan,
-    SubdiagnosticMessage, Substitution, SubstitutionPart, SuggestionStyle,
+    CodeSuggestion, DiagnosticMessage, EmissionGuarantee, IntoLabelMessage, IntoNoteMessage,
+    IntoSuggestionMessage, Level, LintDiagnosticBuilder, MultiSpan, SubdiagnosticMessage,
+    Substitution, SubstitutionPart, SuggestionStyle,
 };
 use rustc_data_structures::fx::FxHashMap;
 use rustc_error_messages::FluentValue;
@@ -10,9 +11,11 @@
 use rustc_span::edition::LATEST_STABLE_EDITION;
 use rustc_span::symbol::{Ident, MacroRulesNormalizedIdent, Symbol};
 use rustc_span::{edition::Edition, Span, DUMMY_SP};
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::panic::Location;
 
 /// Error type for `Diagnostic`'s `suggestions` field, indicating that
 /// `.disable_suggestions()` was called on the `Diagnostic`.
@@ -24,14 +27,40 @@
 /// diagnostic emission.
 pub type DiagnosticArg<'source> = (Cow<'source, str>, DiagnosticArgValue<'source>);
 
-/// Simplified version of `FluentValue` that can implement `Encodable` and `Decodable`. Converted
-/// to a `FluentValue` by the emitter to be used in diagnostic translation.
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
+/// See `Diagnostic::struct_identity`.
+pub(crate) type StructIdentity<'a> = (
+    Level,
+    &'a Option<DiagnosticId>,
+    &'a DiagnosticMessage,
+    &'a [Span],
+    Vec<&'a DiagnosticArg<'static>>,
+);
+
+/// Simplified version of `FluentValue` that can implement `Encodable` and `Decodable`, and be
+/// serialized for `-Zdump-diagnostics`. Converted to a `FluentValue` by the emitter to be used in
+/// diagnostic translation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable, Serialize, Deserialize)]
 pub enum DiagnosticArgValue<'source> {
     Str(Cow<'source, str>),
     Number(usize),
 }
 
+impl DiagnosticArgValue<'_> {
+    /// Whether this is a string wrapped in backticks, like "`T`".
+    fn is_quoted(&self) -> bool {
+        match self {
+            DiagnosticArgValue::Str(s) => s.len() > 1 && s.starts_with('`') && s.ends_with('`'),
+            DiagnosticArgValue::Number(_) => false,
+        }
+    }
+}
+
+/// The prefixes of the Fluent identifiers of the messages whose arguments must not be quoted,
+/// as the messages quote them themselves. Arguments which quote parts of themselves are set
+/// with `Diagnostic::set_code_arg` instead. The messages of the other crates haven't been
+/// checked for this yet.
+const UNQUOTED_ARG_PREFIXES: &[&str] = &["infer_"];
+
 /// Converts a value of a type into a `DiagnosticArg` (typically a field of a `SessionDiagnostic`
 /// struct). Implemented as a custom trait rather than `From` so that it is implemented on the type
 /// being converted rather than on `DiagnosticArgValue`, which enables types from other `rustc_*`
@@ -153,10 +182,40 @@ fn into_diagnostic_arg(self) -> DiagnosticArgValue<'static> {
 /// `#[derive(SessionSubdiagnostic)]` -- see [rustc_macros::SessionSubdiagnostic].
 #[rustc_diagnostic_item = "AddSubdiagnostic"]
 pub trait AddSubdiagnostic {
+    /// Where the subdiagnostic goes relative to the other `#[subdiagnostic]` fields of a derived
+    /// diagnostic, which are added in order of priority and then in the order of the fields.
+    const PRIORITY: SubdiagnosticPriority = SubdiagnosticPriority::Note;
+
     /// Add a subdiagnostic to an existing diagnostic.
     fn add_to_diagnostic(self, diag: &mut Diagnostic);
 }
 
+/// The order in which the subdiagnostics of a derived diagnostic are added: labels first, then
+/// notes, then suggestions. As a `Diagnostic` keeps its labels, children and suggestions apart,
+/// this only decides the order of subdiagnostics of the same kind, like that of single-span and
+/// multipart suggestions. The labels of a derived diagnostic are sorted by their spans instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SubdiagnosticPriority {
+    /// `#[label]`s.
+    Label,
+    /// `#[note]`s, `#[help]`s and `#[warn_]`s.
+    Note,
+    /// Suggestions with a single span.
+    Suggestion,
+    /// Suggestions with multiple parts.
+    MultipartSuggestion,
+}
+
+impl SubdiagnosticPriority {
+    /// All priorities, from the first added to the last.
+    pub const ALL: [Self; 4] = [
+        SubdiagnosticPriority::Label,
+        SubdiagnosticPriority::Note,
+        SubdiagnosticPriority::Suggestion,
+        SubdiagnosticPriority::MultipartSuggestion,
+    ];
+}
+
 /// Trait implemented by lint types. This should not be implemented manually. Instead, use
 /// `#[derive(LintDiagnostic)]` -- see [rustc_macros::LintDiagnostic].
 #[rustc_diagnostic_item = "DecorateLint"]
@@ -187,9 +246,101 @@ pub struct Diagnostic {
     /// If diagnostic is from Lint, custom hash function ignores notes
     /// otherwise hash is based on the all the fields
     pub is_lint: bool,
+
+    /// If set, this diagnostic is emitted even if an identical one was emitted before, see
+    /// `Diagnostic::set_no_dedup`.
+    pub no_dedup: bool,
+
+    /// Earlier errors which may have caused this diagnostic, see `Diagnostic::related_to`.
+    pub related: Vec<EmittedErrorId>,
+
+    /// Information for tools, which is only part of the JSON output.
+    pub metadata: DiagnosticMetadata,
+
+    /// Messages added with `Diagnostic::label_or_note`, which become labels or notes when the
+    /// diagnostic is emitted, along with the number of children the diagnostic had at the time.
+    deferred_labels_or_notes: Vec<(Span, DiagnosticMessage, usize)>,
+
+    /// Where in the compiler the diagnostic was created, printed with `-Z track-diagnostics`.
+    pub created_at: DiagnosticLocation,
+    /// Where in the compiler the diagnostic was emitted, if that was through
+    /// `DiagnosticBuilder::emit`.
+    pub emitted_at: Option<DiagnosticLocation>,
+    /// The subdiagnostics added with `Diagnostic::track_subdiagnostic`, along with where in the
+    /// compiler they were added.
+    tracked_subdiagnostics: Vec<(Cow<'static, str>, DiagnosticLocation)>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
+/// A location in the source of the compiler, taken from the caller of a `#[track_caller]`
+/// function. The functions creating and emitting diagnostics are all `#[track_caller]`, so that
+/// the location is the one of the code reporting the diagnostic. The same goes for the
+/// `into_diagnostic` of derived `SessionDiagnostic`s, manual implementations can opt in by
+/// being `#[track_caller]` as well. Manual `AddSubdiagnostic` impls opt in with
+/// `Diagnostic::track_subdiagnostic`.
+#[derive(Clone, Debug, Encodable, Decodable)]
+pub struct DiagnosticLocation {
+    file: Cow<'static, str>,
+    line: u32,
+    col: u32,
+}
+
+impl DiagnosticLocation {
+    #[track_caller]
+    pub fn caller() -> Self {
+        let loc = Location::caller();
+        DiagnosticLocation { file: loc.file().into(), line: loc.line(), col: loc.column() }
+    }
+}
+
+impl fmt::Display for DiagnosticLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.col)
+    }
+}
+
+/// Machine-readable information about a diagnostic for tools, like the candidates behind an
+/// ambiguity. It is emitted as the `metadata` of the diagnostic in the JSON output, and doesn't
+/// affect the human-readable output.
+#[derive(Clone, Debug, Default, PartialEq, Hash, Encodable, Decodable, Serialize)]
+pub struct DiagnosticMetadata {
+    /// The impls which could have been used for an ambiguous obligation.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub impl_candidates: Vec<ImplCandidate>,
+    /// The number of errors a summary stands for, which weren't emitted themselves.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suppressed_errors: Option<usize>,
+}
+
+impl DiagnosticMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.impl_candidates.is_empty() && self.suppressed_errors.is_none()
+    }
+}
+
+/// An impl which could have been used for an ambiguous obligation.
+#[derive(Clone, Debug, PartialEq, Hash, Encodable, Decodable, Serialize)]
+pub struct ImplCandidate {
+    /// The name of the crate defining the impl.
+    #[serde(rename = "crate")]
+    pub krate: String,
+    pub self_ty: String,
+    /// The generic arguments of the trait, without the self type.
+    pub trait_args: Vec<String>,
+    /// Whether the self type of the impl is a type parameter, as in `impl<T> Trait for T`.
+    pub is_blanket: bool,
+}
+
+/// Identifies an error emitted by a `Handler`, by the order in which errors were emitted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Encodable, Decodable)]
+pub struct EmittedErrorId(pub(crate) usize);
+
+impl EmittedErrorId {
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Encodable, Decodable, Serialize, Deserialize)]
 pub enum DiagnosticId {
     Error(String),
     Lint { name: String, has_future_breakage: bool, is_force_warn: bool },
@@ -253,10 +404,12 @@ pub fn content(&self) -> &str {
 }
 
 impl Diagnostic {
+    #[track_caller]
     pub fn new<M: Into<DiagnosticMessage>>(level: Level, message: M) -> Self {
         Diagnostic::new_with_code(level, None, message)
     }
 
+    #[track_caller]
     pub fn new_with_code<M: Into<DiagnosticMessage>>(
         level: Level,
         code: Option<DiagnosticId>,
@@ -272,6 +425,13 @@ pub fn new_with_code<M: Into<DiagnosticMessage>>(
             args: vec![],
             sort_span: DUMMY_SP,
             is_lint: false,
+            no_dedup: false,
+            related: vec![],
+            metadata: DiagnosticMetadata::default(),
+            deferred_labels_or_notes: vec![],
+            created_at: DiagnosticLocation::caller(),
+            emitted_at: None,
+            tracked_subdiagnostics: vec![],
         }
     }
 
@@ -291,6 +451,7 @@ pub fn is_error(&self) -> bool {
             Level::Warning(_)
             | Level::Note
             | Level::OnceNote
+            | Level::ExplanatoryNote
             | Level::Help
             | Level::Allow
             | Level::Expect(_) => false,
@@ -366,11 +527,65 @@ pub fn downgrade_to_delayed_bug(&mut self) -> &mut Self {
     /// This span is *not* considered a ["primary span"][`MultiSpan`]; only
     /// the `Span` supplied when creating the diagnostic is primary.
     #[rustc_lint_diagnostics]
-    pub fn span_label(&mut self, span: Span, label: impl Into<SubdiagnosticMessage>) -> &mut Self {
-        self.span.push_span_label(span, self.subdiagnostic_message_to_diagnostic_message(label));
+    pub fn span_label(&mut self, span: Span, label: impl IntoLabelMessage) -> &mut Self {
+        let label = self.subdiagnostic_message_to_diagnostic_message(label.into_label_message());
+        self.span.push_span_label(span, label);
+        self
+    }
+
+    /// Adds `msg` as a label on `span` or as a note, whichever renders better once the
+    /// diagnostic is complete. The choice is made when the diagnostic is emitted, so unlike
+    /// when deciding based on the labels and children the diagnostic has so far, it doesn't
+    /// depend on the order in which things are added to it.
+    /// See [`Self::resolve_labels_or_notes()`] for how it is made.
+    #[rustc_lint_diagnostics]
+    pub fn label_or_note(&mut self, span: Span, msg: impl Into<SubdiagnosticMessage>) -> &mut Self {
+        let msg = self.subdiagnostic_message_to_diagnostic_message(msg);
+        self.deferred_labels_or_notes.push((span, msg, self.children.len()));
         self
     }
 
+    /// Turns the messages added with [`Self::label_or_note()`] into labels or notes: a message
+    /// becomes a label if `span` is the primary span, it isn't labeled yet and the diagnostic
+    /// has no notes or helps, nor will get any from the other messages, as those would be read in
+    /// between the primary span and the label.
+    /// Otherwise, it becomes a note in the place the message was added, which only points at
+    /// `span` if that isn't the primary span.
+    pub(crate) fn resolve_labels_or_notes(&mut self) {
+        if self.deferred_labels_or_notes.is_empty() {
+            return;
+        }
+        let deferred = std::mem::take(&mut self.deferred_labels_or_notes);
+        let primary_spans = self.span.primary_spans().to_vec();
+        let is_primary = |span: Span| primary_spans.iter().all(|&sp| sp == span);
+        // A message for another span always becomes a note, so it rules out labels as much as
+        // the notes already there do, whether it was added before or after them.
+        let has_children =
+            !self.children.is_empty() || deferred.iter().any(|&(span, ..)| !is_primary(span));
+        let mut inserted = 0;
+        for (span, msg, position) in deferred {
+            let span_is_primary = is_primary(span);
+            let is_labeled = self
+                .span
+                .span_labels()
+                .iter()
+                .any(|label| label.span == span && label.label.is_some());
+            if span_is_primary && !is_labeled && !has_children {
+                self.span.push_span_label(span, msg);
+            } else {
+                let span = if span_is_primary { MultiSpan::new() } else { span.into() };
+                let sub = SubDiagnostic {
+                    level: Level::Note,
+                    message: vec![(msg, Style::NoStyle)],
+                    span,
+                    render_span: None,
+                };
+                self.children.insert(position + inserted, sub);
+                inserted += 1;
+            }
+        }
+    }
+
     /// Labels all the given spans with the provided label.
     /// See [`Self::span_label()`] for more information.
     pub fn span_labels(
@@ -385,6 +600,40 @@ pub fn span_labels(
         self
     }
 
+    /// Adds all of the given labels, resolving how their spans overlap so that the result doesn't
+    /// depend on the order they are given in:
+    ///
+    /// - The messages of labels with the same span are drawn together under a single underline.
+    /// - A label whose span contains the span of another one becomes a note, so that only the
+    ///   innermost span is underlined in the primary snippet. The note points at its span
+    ///   unless that is the primary span.
+    /// - Labels with partially overlapping spans are kept as they are.
+    ///
+    /// Labels are added sorted by their spans, and then by their messages.
+    pub fn span_labels_from_iter(
+        &mut self,
+        labels: impl IntoIterator<Item = (Span, DiagnosticMessage)>,
+    ) -> &mut Self {
+        let mut labels: Vec<_> = labels.into_iter().collect();
+        labels.sort();
+        labels.dedup();
+        for (span, msg) in &labels {
+            let is_outer = labels.iter().any(|(other, _)| other != span && span.contains(*other));
+            if !is_outer {
+                self.span.push_span_label(*span, msg.clone());
+                continue;
+            }
+            let span_is_primary = self.span.primary_spans().iter().all(|sp| sp == span);
+            self.children.push(SubDiagnostic {
+                level: Level::Note,
+                message: vec![(msg.clone(), Style::NoStyle)],
+                span: if span_is_primary { MultiSpan::new() } else { (*span).into() },
+                render_span: None,
+            });
+        }
+        self
+    }
+
     pub fn replace_span_with(&mut self, after: Span) -> &mut Self {
         let before = self.span.clone();
         self.set_span(after);
@@ -484,8 +733,8 @@ pub fn note_trait_signature(&mut self, name: Symbol, signature: String) -> &mut
 
     /// Add a note attached to this diagnostic.
     #[rustc_lint_diagnostics]
-    pub fn note(&mut self, msg: impl Into<SubdiagnosticMessage>) -> &mut Self {
-        self.sub(Level::Note, msg, MultiSpan::new(), None);
+    pub fn note(&mut self, msg: impl IntoNoteMessage) -> &mut Self {
+        self.sub(Level::Note, msg.into_note_message(), MultiSpan::new(), None);
         self
     }
 
@@ -499,8 +748,16 @@ pub fn highlighted_note<M: Into<SubdiagnosticMessage>>(
 
     /// Prints the span with a note above it.
     /// This is like [`Diagnostic::note()`], but it gets its own span.
-    pub fn note_once(&mut self, msg: impl Into<SubdiagnosticMessage>) -> &mut Self {
-        self.sub(Level::OnceNote, msg, MultiSpan::new(), None);
+    pub fn note_once(&mut self, msg: impl IntoNoteMessage) -> &mut Self {
+        self.sub(Level::OnceNote, msg.into_note_message(), MultiSpan::new(), None);
+        self
+    }
+
+    /// Add a note explaining the general rule behind this diagnostic. Unlike other notes,
+    /// this is only emitted `-Z max-explanatory-notes` times per compilation.
+    #[rustc_lint_diagnostics]
+    pub fn explanatory_note(&mut self, msg: impl IntoNoteMessage) -> &mut Self {
+        self.sub(Level::ExplanatoryNote, msg.into_note_message(), MultiSpan::new(), None);
         self
     }
 
@@ -510,9 +767,9 @@ pub fn note_once(&mut self, msg: impl Into<SubdiagnosticMessage>) -> &mut Self {
     pub fn span_note<S: Into<MultiSpan>>(
         &mut self,
         sp: S,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoNoteMessage,
     ) -> &mut Self {
-        self.sub(Level::Note, msg, sp.into(), None);
+        self.sub(Level::Note, msg.into_note_message(), sp.into(), None);
         self
     }
 
@@ -521,16 +778,16 @@ pub fn span_note<S: Into<MultiSpan>>(
     pub fn span_note_once<S: Into<MultiSpan>>(
         &mut self,
         sp: S,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoNoteMessage,
     ) -> &mut Self {
-        self.sub(Level::OnceNote, msg, sp.into(), None);
+        self.sub(Level::OnceNote, msg.into_note_message(), sp.into(), None);
         self
     }
 
     /// Add a warning attached to this diagnostic.
     #[rustc_lint_diagnostics]
-    pub fn warn(&mut self, msg: impl Into<SubdiagnosticMessage>) -> &mut Self {
-        self.sub(Level::Warning(None), msg, MultiSpan::new(), None);
+    pub fn warn(&mut self, msg: impl IntoNoteMessage) -> &mut Self {
+        self.sub(Level::Warning(None), msg.into_note_message(), MultiSpan::new(), None);
         self
     }
 
@@ -540,16 +797,16 @@ pub fn warn(&mut self, msg: impl Into<SubdiagnosticMessage>) -> &mut Self {
     pub fn span_warn<S: Into<MultiSpan>>(
         &mut self,
         sp: S,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoNoteMessage,
     ) -> &mut Self {
-        self.sub(Level::Warning(None), msg, sp.into(), None);
+        self.sub(Level::Warning(None), msg.into_note_message(), sp.into(), None);
         self
     }
 
     /// Add a help message attached to this diagnostic.
     #[rustc_lint_diagnostics]
-    pub fn help(&mut self, msg: impl Into<SubdiagnosticMessage>) -> &mut Self {
-        self.sub(Level::Help, msg, MultiSpan::new(), None);
+    pub fn help(&mut self, msg: impl IntoNoteMessage) -> &mut Self {
+        self.sub(Level::Help, msg.into_note_message(), MultiSpan::new(), None);
         self
     }
 
@@ -565,9 +822,9 @@ pub fn highlighted_help(&mut self, msg: Vec<(String, Style)>) -> &mut Self {
     pub fn span_help<S: Into<MultiSpan>>(
         &mut self,
         sp: S,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoNoteMessage,
     ) -> &mut Self {
-        self.sub(Level::Help, msg, sp.into(), None);
+        self.sub(Level::Help, msg.into_note_message(), sp.into(), None);
         self
     }
 
@@ -606,11 +863,22 @@ fn push_suggestion(&mut self, suggestion: CodeSuggestion) {
         }
     }
 
+    /// Records which code produced the last suggestion added to this diagnostic, like
+    /// `"SourceKindMultiSuggestion::ClosureReturn"`. The tag is shown after the suggestion's
+    /// message when `-Zui-testing` and `-Zunstable-options` are both passed, so tests can tell
+    /// apart suggestions that render the same.
+    pub fn tag_last_suggestion(&mut self, tag: &'static str) -> &mut Self {
+        if let Some(suggestion) = self.suggestions.as_mut().ok().and_then(|s| s.last_mut()) {
+            suggestion.tag = Some(tag.into());
+        }
+        self
+    }
+
     /// Show a suggestion that has multiple parts to it.
     /// In other words, multiple changes need to be applied as part of this suggestion.
     pub fn multipart_suggestion(
         &mut self,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: Vec<(Span, String)>,
         applicability: Applicability,
     ) -> &mut Self {
@@ -626,7 +894,7 @@ pub fn multipart_suggestion(
     /// In other words, multiple changes need to be applied as part of this suggestion.
     pub fn multipart_suggestion_verbose(
         &mut self,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: Vec<(Span, String)>,
         applicability: Applicability,
     ) -> &mut Self {
@@ -640,7 +908,7 @@ pub fn multipart_suggestion_verbose(
     /// [`Diagnostic::multipart_suggestion()`] but you can set the [`SuggestionStyle`].
     pub fn multipart_suggestion_with_style(
         &mut self,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: Vec<(Span, String)>,
         applicability: Applicability,
         style: SuggestionStyle,
@@ -653,9 +921,10 @@ pub fn multipart_suggestion_with_style(
                     .map(|(span, snippet)| SubstitutionPart { snippet, span })
                     .collect(),
             }],
-            msg: self.subdiagnostic_message_to_diagnostic_message(msg),
+            msg: self.suggestion_message_to_diagnostic_message(msg),
             style,
             applicability,
+            tag: None,
         });
         self
     }
@@ -668,7 +937,7 @@ pub fn multipart_suggestion_with_style(
     /// improve understandability.
     pub fn tool_only_multipart_suggestion(
         &mut self,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: Vec<(Span, String)>,
         applicability: Applicability,
     ) -> &mut Self {
@@ -680,9 +949,10 @@ pub fn tool_only_multipart_suggestion(
                     .map(|(span, snippet)| SubstitutionPart { snippet, span })
                     .collect(),
             }],
-            msg: self.subdiagnostic_message_to_diagnostic_message(msg),
+            msg: self.suggestion_message_to_diagnostic_message(msg),
             style: SuggestionStyle::CompletelyHidden,
             applicability,
+            tag: None,
         });
         self
     }
@@ -707,7 +977,7 @@ pub fn tool_only_multipart_suggestion(
     pub fn span_suggestion(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: impl ToString,
         applicability: Applicability,
     ) -> &mut Self {
@@ -725,7 +995,7 @@ pub fn span_suggestion(
     pub fn span_suggestion_with_style(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: impl ToString,
         applicability: Applicability,
         style: SuggestionStyle,
@@ -734,9 +1004,10 @@ pub fn span_suggestion_with_style(
             substitutions: vec![Substitution {
                 parts: vec![SubstitutionPart { snippet: suggestion.to_string(), span: sp }],
             }],
-            msg: self.subdiagnostic_message_to_diagnostic_message(msg),
+            msg: self.suggestion_message_to_diagnostic_message(msg),
             style,
             applicability,
+            tag: None,
         });
         self
     }
@@ -745,7 +1016,7 @@ pub fn span_suggestion_with_style(
     pub fn span_suggestion_verbose(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: impl ToString,
         applicability: Applicability,
     ) -> &mut Self {
@@ -764,7 +1035,7 @@ pub fn span_suggestion_verbose(
     pub fn span_suggestions(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestions: impl Iterator<Item = String>,
         applicability: Applicability,
     ) -> &mut Self {
@@ -776,9 +1047,10 @@ pub fn span_suggestions(
             .collect();
         self.push_suggestion(CodeSuggestion {
             substitutions,
-            msg: self.subdiagnostic_message_to_diagnostic_message(msg),
+            msg: self.suggestion_message_to_diagnostic_message(msg),
             style: SuggestionStyle::ShowCode,
             applicability,
+            tag: None,
         });
         self
     }
@@ -787,7 +1059,7 @@ pub fn span_suggestions(
     /// See also [`Diagnostic::span_suggestion()`].
     pub fn multipart_suggestions(
         &mut self,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestions: impl Iterator<Item = Vec<(Span, String)>>,
         applicability: Applicability,
     ) -> &mut Self {
@@ -800,9 +1072,10 @@ pub fn multipart_suggestions(
                         .collect(),
                 })
                 .collect(),
-            msg: self.subdiagnostic_message_to_diagnostic_message(msg),
+            msg: self.suggestion_message_to_diagnostic_message(msg),
             style: SuggestionStyle::ShowCode,
             applicability,
+            tag: None,
         });
         self
     }
@@ -813,7 +1086,7 @@ pub fn multipart_suggestions(
     pub fn span_suggestion_short(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: impl ToString,
         applicability: Applicability,
     ) -> &mut Self {
@@ -836,7 +1109,7 @@ pub fn span_suggestion_short(
     pub fn span_suggestion_hidden(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: impl ToString,
         applicability: Applicability,
     ) -> &mut Self {
@@ -857,7 +1130,7 @@ pub fn span_suggestion_hidden(
     pub fn tool_only_span_suggestion(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: impl ToString,
         applicability: Applicability,
     ) -> &mut Self {
@@ -873,11 +1146,25 @@ pub fn tool_only_span_suggestion(
 
     /// Add a subdiagnostic from a type that implements `SessionSubdiagnostic` - see
     /// [rustc_macros::SessionSubdiagnostic].
+    #[track_caller]
     pub fn subdiagnostic(&mut self, subdiagnostic: impl AddSubdiagnostic) -> &mut Self {
         subdiagnostic.add_to_diagnostic(self);
         self
     }
 
+    /// Notes where the subdiagnostic `name` was added with `-Z track-diagnostics`. Manual
+    /// `AddSubdiagnostic` impls opt in by calling this from an `add_to_diagnostic` marked
+    /// `#[track_caller]`, which makes the location the caller of `Diagnostic::subdiagnostic`.
+    #[track_caller]
+    pub fn track_subdiagnostic(&mut self, name: &'static str) -> &mut Self {
+        self.tracked_subdiagnostics.push((Cow::Borrowed(name), DiagnosticLocation::caller()));
+        self
+    }
+
+    pub fn tracked_subdiagnostics(&self) -> &[(Cow<'static, str>, DiagnosticLocation)] {
+        &self.tracked_subdiagnostics
+    }
+
     pub fn set_span<S: Into<MultiSpan>>(&mut self, sp: S) -> &mut Self {
         self.span = sp.into();
         if let Some(span) = self.span.primary_span() {
@@ -891,6 +1178,23 @@ pub fn set_is_lint(&mut self) -> &mut Self {
         self
     }
 
+    /// Makes the handler emit this diagnostic even if it is identical to one emitted before, for
+    /// diagnostics whose meaning depends on where or when they are emitted rather than on their
+    /// text alone, like a summary of the errors left out of a function.
+    pub fn set_no_dedup(&mut self) -> &mut Self {
+        self.no_dedup = true;
+        self
+    }
+
+    /// Marks the earlier error `id` as a possible cause of this diagnostic. This adds a note
+    /// pointing to the line of that error and is recorded in the JSON output.
+    pub fn related_to(&mut self, id: EmittedErrorId) -> &mut Self {
+        if !self.related.contains(&id) {
+            self.related.push(id);
+        }
+        self
+    }
+
     pub fn code(&mut self, s: DiagnosticId) -> &mut Self {
         self.code = Some(s);
         self
@@ -918,15 +1222,87 @@ pub fn set_arg(
         &mut self,
         name: impl Into<Cow<'static, str>>,
         arg: impl IntoDiagnosticArg,
+    ) -> &mut Self {
+        let name = name.into();
+        let arg = arg.into_diagnostic_arg();
+        if cfg!(debug_assertions) && arg.is_quoted() && self.has_unquoted_args() {
+            panic!(
+                "the argument `{}` of `{:?}` is quoted, but its message should add the quotes",
+                name,
+                self.fluent_identifier(),
+            );
+        }
+        self.args.push((name, arg));
+        self
+    }
+
+    /// Whether the message of this diagnostic is one of those whose arguments are passed
+    /// unquoted, with the message adding backticks around them where needed.
+    fn has_unquoted_args(&self) -> bool {
+        self.fluent_identifier()
+            .map_or(false, |(id, _)| UNQUOTED_ARG_PREFIXES.iter().any(|p| id.starts_with(p)))
+    }
+
+    /// Like [`Diagnostic::set_arg`], but for arguments which quote the code in them themselves,
+    /// like lists of names (`` `T`, `U` ``), and so are allowed to start and end with a backtick.
+    pub fn set_code_arg(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        arg: impl IntoDiagnosticArg,
     ) -> &mut Self {
         self.args.push((name.into(), arg.into_diagnostic_arg()));
         self
     }
 
+    /// Replaces the arguments of this diagnostic, like when it is loaded from a dump of
+    /// `-Zdump-diagnostics`, see `dump::load_diagnostics`.
+    pub(crate) fn replace_args(&mut self, args: Vec<DiagnosticArg<'static>>) {
+        self.args = args;
+    }
+
     pub fn styled_message(&self) -> &[(DiagnosticMessage, Style)] {
         &self.message
     }
 
+    /// Returns the arguments of this diagnostic rendered to strings and sorted by name.
+    ///
+    /// Only intended for asserting on the structure of diagnostics in tests.
+    #[doc(hidden)]
+    pub fn rendered_args(&self) -> Vec<(&str, String)> {
+        let mut args: Vec<_> = self
+            .args
+            .iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    DiagnosticArgValue::Str(s) => s.to_string(),
+                    DiagnosticArgValue::Number(n) => n.to_string(),
+                };
+                (&**name, value)
+            })
+            .collect();
+        args.sort_by(|(a, _), (b, _)| a.cmp(b));
+        args
+    }
+
+    /// Returns a digest of the primary message of this diagnostic, consisting of its Fluent
+    /// identifier followed by the sorted names (not values) of its arguments, e.g.
+    /// `infer_type_annotations_needed(source_kind, source_name)`. Returns `None` for diagnostics
+    /// whose message is not translatable.
+    ///
+    /// Digests are used by compiletest to catch changes to the set of arguments passed to a
+    /// message, which may go unnoticed in the rendered output.
+    pub fn digest(&self) -> Option<String> {
+        let (id, attr) = self.message.first().and_then(|(msg, _)| msg.fluent_identifier())?;
+        let mut names: Vec<&str> = self.args.iter().map(|(name, _)| &**name).collect();
+        names.sort_unstable();
+        names.dedup();
+        let id = match attr {
+            Some(attr) => format!("{}.{}", id, attr),
+            None => id.to_string(),
+        };
+        Some(format!("{}({})", id, names.join(", ")))
+    }
+
     /// Helper function that takes a `SubdiagnosticMessage` and returns a `DiagnosticMessage` by
     /// combining it with the primary message of the diagnostic (if translatable, otherwise it just
     /// passes the user's string along).
@@ -939,6 +1315,15 @@ fn subdiagnostic_message_to_diagnostic_message(
         msg.with_subdiagnostic_message(attr.into())
     }
 
+    /// Helper function like `subdiagnostic_message_to_diagnostic_message`, for the messages of
+    /// suggestions, which can't be given a message of another subdiagnostic kind.
+    fn suggestion_message_to_diagnostic_message(
+        &self,
+        msg: impl IntoSuggestionMessage,
+    ) -> DiagnosticMessage {
+        self.subdiagnostic_message_to_diagnostic_message(msg.into_suggestion_message())
+    }
+
     /// Convenience function for internal use, clients should use one of the
     /// public methods above.
     ///
@@ -979,6 +1364,28 @@ fn sub_with_highlights<M: Into<SubdiagnosticMessage>>(
         self.children.push(sub);
     }
 
+    /// Returns `true` if the primary message is a Fluent message with a `.teach` attribute.
+    pub(crate) fn has_teach_attr(&self) -> bool {
+        match self.message.first() {
+            Some((DiagnosticMessage::FluentIdentifier(id, None), _)) => {
+                rustc_error_messages::has_teach_attr(id)
+            }
+            _ => false,
+        }
+    }
+
+    /// For diagnostics whose primary message is a Fluent slug (i.e. that were built from a
+    /// `SessionDiagnostic` struct), the level, code, slug, primary spans and (sorted) arguments.
+    /// These identify the diagnostic independently of any children attached to it.
+    pub(crate) fn struct_identity(&self) -> Option<StructIdentity<'_>> {
+        let (message @ DiagnosticMessage::FluentIdentifier(..), _) = self.message.first()? else {
+            return None;
+        };
+        let mut args: Vec<_> = self.args.iter().collect();
+        args.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Some((self.level, &self.code, message, self.span.primary_spans(), args))
+    }
+
     /// Fields used for Hash, and PartialEq trait
     fn keys(
         &self,
@@ -1001,6 +1408,21 @@ fn keys(
     }
 }
 
+impl SubDiagnostic {
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Returns the Fluent identifier and attribute of the first message of this subdiagnostic,
+    /// or `None` if it is of the legacy, non-translatable variety.
+    ///
+    /// Only intended for asserting on the structure of diagnostics in tests.
+    #[doc(hidden)]
+    pub fn fluent_identifier(&self) -> Option<(&str, Option<&str>)> {
+        self.message.first().and_then(|(msg, _)| msg.fluent_identifier())
+    }
+}
+
 impl Hash for Diagnostic {
     fn hash<H>(&self, state: &mut H)
     where
@@ -1015,3 +1437,6 @@ fn eq(&self, other: &Self) -> bool {
         self.keys() == other.keys()
     }
 }
+
+#[cfg(test)]
+mod tests;
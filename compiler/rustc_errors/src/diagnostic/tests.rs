@@ -0,0 +1,223 @@
+use super::*;
+use rustc_span::BytePos;
+
+fn fluent_diagnostic() -> Diagnostic {
+    Diagnostic::new(Level::Error, DiagnosticMessage::FluentIdentifier("test_slug".into(), None))
+}
+
+#[test]
+fn subdiagnostic_fluent_identifier() {
+    let mut diag = fluent_diagnostic();
+    diag.span_note(DUMMY_SP, SubdiagnosticMessage::FluentAttr("note".into()));
+    diag.help(SubdiagnosticMessage::FluentIdentifier("other_slug".into()));
+    diag.note("not translatable");
+
+    let children: Vec<_> =
+        diag.children.iter().map(|child| (child.level(), child.fluent_identifier())).collect();
+    assert_eq!(
+        children,
+        vec![
+            (Level::Note, Some(("test_slug", Some("note")))),
+            (Level::Help, Some(("other_slug", None))),
+            (Level::Note, None),
+        ]
+    );
+}
+
+#[test]
+fn primary_message_fluent_identifier() {
+    let diag = fluent_diagnostic();
+    assert_eq!(diag.styled_message()[0].0.fluent_identifier(), Some(("test_slug", None)));
+
+    let diag = Diagnostic::new(Level::Error, "not translatable");
+    assert_eq!(diag.styled_message()[0].0.fluent_identifier(), None);
+}
+
+#[test]
+fn rendered_args_are_sorted() {
+    let mut diag = fluent_diagnostic();
+    diag.set_arg("name", "`'a`");
+    diag.set_arg("count", 2u32);
+    diag.set_arg("is_mut", true);
+
+    assert_eq!(
+        diag.rendered_args(),
+        vec![
+            ("count", "2".to_string()),
+            ("is_mut", "true".to_string()),
+            ("name", "`'a`".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn digest_has_sorted_arg_names() {
+    let mut diag = fluent_diagnostic();
+    diag.set_arg("name", "`'a`");
+    diag.set_arg("count", 2u32);
+    assert_eq!(diag.digest().as_deref(), Some("test_slug(count, name)"));
+
+    let diag = Diagnostic::new(Level::Error, "not translatable");
+    assert_eq!(diag.digest(), None);
+}
+
+#[test]
+fn label_or_note_is_order_independent() {
+    let primary = Span::with_root_ctxt(BytePos(0), BytePos(4));
+    let other = Span::with_root_ctxt(BytePos(8), BytePos(12));
+    let build = |note_first: bool| {
+        let mut diag = fluent_diagnostic();
+        diag.set_span(primary);
+        if note_first {
+            diag.note("unrelated note");
+        }
+        diag.label_or_note(primary, "first");
+        diag.label_or_note(primary, "second");
+        diag.label_or_note(other, "elsewhere");
+        if !note_first {
+            diag.note("unrelated note");
+        }
+        diag.resolve_labels_or_notes();
+        diag
+    };
+
+    let (before, after) = (build(true), build(false));
+    assert_eq!(before.span, after.span);
+    let mut before_children = before.children.clone();
+    before_children.rotate_left(1);
+    assert_eq!(before_children, after.children);
+
+    assert!(after.span.span_labels().iter().all(|label| label.label.is_none()));
+    let notes: Vec<_> = after.children.iter().map(|child| child.span.primary_span()).collect();
+    assert_eq!(notes, vec![None, None, Some(other), None]);
+
+    let mut diag = fluent_diagnostic();
+    diag.set_span(primary);
+    diag.label_or_note(primary, "first");
+    diag.label_or_note(primary, "second");
+    diag.resolve_labels_or_notes();
+    let labels: Vec<_> = diag
+        .span
+        .span_labels()
+        .into_iter()
+        .filter_map(|label| Some((label.span, label.label?)))
+        .collect();
+    assert_eq!(labels, vec![(primary, DiagnosticMessage::Str("first".into()))]);
+    assert_eq!(diag.children.len(), 1);
+
+    let build = |elsewhere_first: bool| {
+        let mut diag = fluent_diagnostic();
+        diag.set_span(primary);
+        if elsewhere_first {
+            diag.label_or_note(other, "elsewhere");
+        }
+        diag.label_or_note(primary, "first");
+        if !elsewhere_first {
+            diag.label_or_note(other, "elsewhere");
+        }
+        diag.resolve_labels_or_notes();
+        diag
+    };
+    let (before, after) = (build(true), build(false));
+    assert_eq!(before.span, after.span);
+    assert!(after.span.span_labels().iter().all(|label| label.label.is_none()));
+    let mut before_children = before.children.clone();
+    before_children.rotate_left(1);
+    assert_eq!(before_children, after.children);
+}
+
+/// The labels and notes `span_labels_from_iter` makes of `labels`, given in both orders.
+fn resolved_labels(
+    primary: Span,
+    labels: Vec<(Span, &'static str)>,
+) -> (Vec<(Span, DiagnosticMessage)>, Vec<(Option<Span>, DiagnosticMessage)>) {
+    let build = |labels: Vec<(Span, &'static str)>| {
+        let mut diag = fluent_diagnostic();
+        diag.set_span(primary);
+        diag.span_labels_from_iter(labels.into_iter().map(|(span, msg)| (span, msg.into())));
+        let labels: Vec<_> = diag
+            .span
+            .span_labels()
+            .into_iter()
+            .filter_map(|label| Some((label.span, label.label?)))
+            .collect();
+        let notes: Vec<_> = diag
+            .children
+            .iter()
+            .map(|child| (child.span.primary_span(), child.message[0].0.clone()))
+            .collect();
+        (labels, notes)
+    };
+    let forward = build(labels.clone());
+    let backward = build(labels.into_iter().rev().collect());
+    assert_eq!(forward, backward);
+    forward
+}
+
+#[test]
+fn nested_labels_keep_the_innermost() {
+    let statement = Span::with_root_ctxt(BytePos(0), BytePos(20));
+    let binding = Span::with_root_ctxt(BytePos(4), BytePos(5));
+    let initializer = Span::with_root_ctxt(BytePos(8), BytePos(19));
+    let (labels, notes) = resolved_labels(
+        statement,
+        vec![(statement, "statement"), (binding, "binding"), (initializer, "initializer")],
+    );
+    assert_eq!(
+        labels,
+        vec![
+            (binding, DiagnosticMessage::Str("binding".into())),
+            (initializer, DiagnosticMessage::Str("initializer".into())),
+        ]
+    );
+    // The statement is the primary span, so the note doesn't point at it again.
+    assert_eq!(notes, vec![(None, DiagnosticMessage::Str("statement".into()))]);
+}
+
+#[test]
+fn identical_labels_are_merged() {
+    let span = Span::with_root_ctxt(BytePos(0), BytePos(4));
+    let other = Span::with_root_ctxt(BytePos(2), BytePos(8));
+    let (labels, notes) = resolved_labels(
+        span,
+        vec![(span, "second"), (span, "first"), (other, "other"), (span, "first")],
+    );
+    assert_eq!(
+        labels,
+        vec![
+            (span, DiagnosticMessage::Str("first".into())),
+            (span, DiagnosticMessage::Str("second".into())),
+            (other, DiagnosticMessage::Str("other".into())),
+        ]
+    );
+    assert!(notes.is_empty());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "is quoted")]
+fn quoted_arg_of_infer_message() {
+    let mut diag = Diagnostic::new(
+        Level::Error,
+        DiagnosticMessage::FluentIdentifier("infer_test_slug".into(), None),
+    );
+    diag.set_arg("name", "`T`");
+}
+
+#[test]
+fn quoted_code_arg_of_infer_message() {
+    let mut diag = Diagnostic::new(
+        Level::Error,
+        DiagnosticMessage::FluentIdentifier("infer_test_slug".into(), None),
+    );
+    diag.set_arg("name", "T");
+    diag.set_code_arg("names", "`T`, `U`");
+    assert_eq!(
+        diag.rendered_args(),
+        vec![("name", "T".to_string()), ("names", "`T`, `U`".to_string())]
+    );
+
+    let mut diag = fluent_diagnostic();
+    diag.set_arg("name", "`T`");
+    assert_eq!(diag.rendered_args(), vec![("name", "`T`".to_string())]);
+}
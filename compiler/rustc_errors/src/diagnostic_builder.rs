@@ -1,6 +1,7 @@
-use crate::diagnostic::IntoDiagnosticArg;
+use crate::diagnostic::{DiagnosticLocation, IntoDiagnosticArg};
 use crate::{
-    Diagnostic, DiagnosticId, DiagnosticMessage, DiagnosticStyledString, ErrorGuaranteed,
+    Diagnostic, DiagnosticId, DiagnosticMessage, DiagnosticStyledString, EmittedErrorId,
+    ErrorGuaranteed, IntoLabelMessage, IntoNoteMessage, IntoSuggestionMessage,
     SubdiagnosticMessage,
 };
 use crate::{Handler, Level, MultiSpan, StashKey};
@@ -48,6 +49,20 @@ struct DiagnosticBuilderInner<'a> {
     diagnostic: Box<Diagnostic>,
 }
 
+impl<'a> DiagnosticBuilderInner<'a> {
+    fn new(handler: &'a Handler, mut diagnostic: Diagnostic) -> Self {
+        // With `-Z no-suggestions`, suggestions are dropped as they are added instead of
+        // when the diagnostic is emitted.
+        if handler.flags.no_suggestions {
+            diagnostic.disable_suggestions();
+        }
+        DiagnosticBuilderInner {
+            state: DiagnosticBuilderState::Emittable(handler),
+            diagnostic: Box::new(diagnostic),
+        }
+    }
+}
+
 #[derive(Clone)]
 enum DiagnosticBuilderState<'a> {
     /// Initial state of a `DiagnosticBuilder`, before `.emit()` or `.cancel()`.
@@ -104,6 +119,7 @@ impl IsError<{ Level::Error { lint: false } }> for () {}
 impl<'a> DiagnosticBuilder<'a, ErrorGuaranteed> {
     /// Convenience function for internal use, clients should use one of the
     /// `struct_*` methods on [`Handler`].
+    #[track_caller]
     pub(crate) fn new_guaranteeing_error<M: Into<DiagnosticMessage>, const L: Level>(
         handler: &'a Handler,
         message: M,
@@ -112,10 +128,10 @@ pub(crate) fn new_guaranteeing_error<M: Into<DiagnosticMessage>, const L: Level>
         (): sealed_level_is_error::IsError<L>,
     {
         Self {
-            inner: DiagnosticBuilderInner {
-                state: DiagnosticBuilderState::Emittable(handler),
-                diagnostic: Box::new(Diagnostic::new_with_code(L, None, message)),
-            },
+            inner: DiagnosticBuilderInner::new(
+                handler,
+                Diagnostic::new_with_code(L, None, message),
+            ),
             _marker: PhantomData,
         }
     }
@@ -171,6 +187,7 @@ fn diagnostic_builder_emit_producing_guarantee(db: &mut DiagnosticBuilder<'_, Se
 impl<'a> DiagnosticBuilder<'a, ()> {
     /// Convenience function for internal use, clients should use one of the
     /// `struct_*` methods on [`Handler`].
+    #[track_caller]
     pub(crate) fn new<M: Into<DiagnosticMessage>>(
         handler: &'a Handler,
         level: Level,
@@ -184,13 +201,7 @@ pub(crate) fn new<M: Into<DiagnosticMessage>>(
     /// diagnostic.
     pub(crate) fn new_diagnostic(handler: &'a Handler, diagnostic: Diagnostic) -> Self {
         debug!("Created new diagnostic");
-        Self {
-            inner: DiagnosticBuilderInner {
-                state: DiagnosticBuilderState::Emittable(handler),
-                diagnostic: Box::new(diagnostic),
-            },
-            _marker: PhantomData,
-        }
+        Self { inner: DiagnosticBuilderInner::new(handler, diagnostic), _marker: PhantomData }
     }
 }
 
@@ -213,6 +224,7 @@ fn diagnostic_builder_emit_producing_guarantee(db: &mut DiagnosticBuilder<'_, Se
 impl<'a> DiagnosticBuilder<'a, !> {
     /// Convenience function for internal use, clients should use one of the
     /// `struct_*` methods on [`Handler`].
+    #[track_caller]
     pub(crate) fn new_fatal(handler: &'a Handler, message: impl Into<DiagnosticMessage>) -> Self {
         let diagnostic = Diagnostic::new_with_code(Level::Fatal, None, message);
         Self::new_diagnostic_fatal(handler, diagnostic)
@@ -222,13 +234,7 @@ pub(crate) fn new_fatal(handler: &'a Handler, message: impl Into<DiagnosticMessa
     /// diagnostic.
     pub(crate) fn new_diagnostic_fatal(handler: &'a Handler, diagnostic: Diagnostic) -> Self {
         debug!("Created new diagnostic");
-        Self {
-            inner: DiagnosticBuilderInner {
-                state: DiagnosticBuilderState::Emittable(handler),
-                diagnostic: Box::new(diagnostic),
-            },
-            _marker: PhantomData,
-        }
+        Self { inner: DiagnosticBuilderInner::new(handler, diagnostic), _marker: PhantomData }
     }
 }
 
@@ -290,6 +296,7 @@ impl<'a, G: EmissionGuarantee> DiagnosticBuilder<'a, G> {
     /// Emit the diagnostic.
     #[track_caller]
     pub fn emit(&mut self) -> G {
+        self.inner.diagnostic.emitted_at = Some(DiagnosticLocation::caller());
         G::diagnostic_builder_emit_producing_guarantee(self)
     }
 
@@ -398,7 +405,13 @@ pub fn downgrade_to_delayed_bug(&mut self,) -> &mut Self
     /// the diagnostic was constructed. However, the label span is *not* considered a
     /// ["primary span"][`MultiSpan`]; only the `Span` supplied when creating the diagnostic is
     /// primary.
-    pub fn span_label(&mut self, span: Span, label: impl Into<SubdiagnosticMessage>) -> &mut Self);
+    #[rustc_lint_diagnostics]
+    pub fn span_label(&mut self, span: Span, label: impl IntoLabelMessage) -> &mut Self);
+
+    forward!(
+    /// See [`Diagnostic::label_or_note()`].
+    #[rustc_lint_diagnostics]
+    pub fn label_or_note(&mut self, span: Span, msg: impl Into<SubdiagnosticMessage>) -> &mut Self);
 
     forward!(
     /// Labels all the given spans with the provided label.
@@ -409,6 +422,13 @@ pub fn span_labels(
         label: impl AsRef<str>,
     ) -> &mut Self);
 
+    forward!(
+    /// See [`Diagnostic::span_labels_from_iter()`].
+    pub fn span_labels_from_iter(
+        &mut self,
+        labels: impl IntoIterator<Item = (Span, DiagnosticMessage)>,
+    ) -> &mut Self);
+
     forward!(pub fn note_expected_found(
         &mut self,
         expected_label: &dyn fmt::Display,
@@ -433,99 +453,123 @@ pub fn span_labels(
         found: DiagnosticStyledString,
     ) -> &mut Self);
 
-    forward!(pub fn note(&mut self, msg: impl Into<SubdiagnosticMessage>) -> &mut Self);
-    forward!(pub fn note_once(&mut self, msg: impl Into<SubdiagnosticMessage>) -> &mut Self);
-    forward!(pub fn span_note(
-        &mut self,
-        sp: impl Into<MultiSpan>,
-        msg: impl Into<SubdiagnosticMessage>,
-    ) -> &mut Self);
+    forward!(
+        #[rustc_lint_diagnostics]
+        pub fn note(&mut self, msg: impl IntoNoteMessage) -> &mut Self
+    );
+    forward!(pub fn note_once(&mut self, msg: impl IntoNoteMessage) -> &mut Self);
+    forward!(
+        #[rustc_lint_diagnostics]
+        pub fn explanatory_note(&mut self, msg: impl IntoNoteMessage) -> &mut Self
+    );
+    forward!(
+        #[rustc_lint_diagnostics]
+        pub fn span_note(
+            &mut self,
+            sp: impl Into<MultiSpan>,
+            msg: impl IntoNoteMessage,
+        ) -> &mut Self
+    );
     forward!(pub fn span_note_once(
         &mut self,
         sp: impl Into<MultiSpan>,
-        msg: impl Into<SubdiagnosticMessage>,
-    ) -> &mut Self);
-    forward!(pub fn warn(&mut self, msg: impl Into<SubdiagnosticMessage>) -> &mut Self);
-    forward!(pub fn span_warn(
-        &mut self,
-        sp: impl Into<MultiSpan>,
-        msg: impl Into<SubdiagnosticMessage>,
-    ) -> &mut Self);
-    forward!(pub fn help(&mut self, msg: impl Into<SubdiagnosticMessage>) -> &mut Self);
-    forward!(pub fn span_help(
-        &mut self,
-        sp: impl Into<MultiSpan>,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoNoteMessage,
     ) -> &mut Self);
+    forward!(
+        #[rustc_lint_diagnostics]
+        pub fn warn(&mut self, msg: impl IntoNoteMessage) -> &mut Self
+    );
+    forward!(
+        #[rustc_lint_diagnostics]
+        pub fn span_warn(
+            &mut self,
+            sp: impl Into<MultiSpan>,
+            msg: impl IntoNoteMessage,
+        ) -> &mut Self
+    );
+    forward!(
+        #[rustc_lint_diagnostics]
+        pub fn help(&mut self, msg: impl IntoNoteMessage) -> &mut Self
+    );
+    forward!(
+        #[rustc_lint_diagnostics]
+        pub fn span_help(
+            &mut self,
+            sp: impl Into<MultiSpan>,
+            msg: impl IntoNoteMessage,
+        ) -> &mut Self
+    );
     forward!(pub fn help_use_latest_edition(&mut self,) -> &mut Self);
     forward!(pub fn set_is_lint(&mut self,) -> &mut Self);
+    forward!(pub fn set_no_dedup(&mut self,) -> &mut Self);
+    forward!(pub fn related_to(&mut self, id: EmittedErrorId) -> &mut Self);
 
     forward!(pub fn disable_suggestions(&mut self,) -> &mut Self);
     forward!(pub fn clear_suggestions(&mut self,) -> &mut Self);
 
     forward!(pub fn multipart_suggestion(
         &mut self,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: Vec<(Span, String)>,
         applicability: Applicability,
     ) -> &mut Self);
     forward!(pub fn multipart_suggestion_verbose(
         &mut self,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: Vec<(Span, String)>,
         applicability: Applicability,
     ) -> &mut Self);
     forward!(pub fn tool_only_multipart_suggestion(
         &mut self,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: Vec<(Span, String)>,
         applicability: Applicability,
     ) -> &mut Self);
     forward!(pub fn span_suggestion(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: impl ToString,
         applicability: Applicability,
     ) -> &mut Self);
     forward!(pub fn span_suggestions(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestions: impl Iterator<Item = String>,
         applicability: Applicability,
     ) -> &mut Self);
     forward!(pub fn multipart_suggestions(
         &mut self,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestions: impl Iterator<Item = Vec<(Span, String)>>,
         applicability: Applicability,
     ) -> &mut Self);
     forward!(pub fn span_suggestion_short(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: impl ToString,
         applicability: Applicability,
     ) -> &mut Self);
     forward!(pub fn span_suggestion_verbose(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: impl ToString,
         applicability: Applicability,
     ) -> &mut Self);
     forward!(pub fn span_suggestion_hidden(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: impl ToString,
         applicability: Applicability,
     ) -> &mut Self);
     forward!(pub fn tool_only_span_suggestion(
         &mut self,
         sp: Span,
-        msg: impl Into<SubdiagnosticMessage>,
+        msg: impl IntoSuggestionMessage,
         suggestion: impl ToString,
         applicability: Applicability,
     ) -> &mut Self);
@@ -539,10 +583,10 @@ pub fn span_labels(
         arg: impl IntoDiagnosticArg,
     ) -> &mut Self);
 
-    forward!(pub fn subdiagnostic(
-        &mut self,
-        subdiagnostic: impl crate::AddSubdiagnostic
-    ) -> &mut Self);
+    forward!(
+        #[track_caller]
+        pub fn subdiagnostic(&mut self, subdiagnostic: impl crate::AddSubdiagnostic) -> &mut Self
+    );
 }
 
 impl<G: EmissionGuarantee> Debug for DiagnosticBuilder<'_, G> {
@@ -0,0 +1,445 @@
+//! Dumping of diagnostics for `-Zdump-diagnostics`.
+//!
+//! The [`DumpEmitter`] writes every diagnostic which reaches it as one JSON object per line,
+//! before handing it on to the emitter chosen by `--error-format`. Unlike the JSON emitter, it
+//! writes the Fluent identifiers of the messages and the arguments of the diagnostic instead of
+//! the rendered text, so that [`load_diagnostics`] can read the diagnostics back and they can be
+//! rendered again later, under a different locale or with reworded messages. Messages which
+//! were built as strings can't be translated, so only their text is written.
+//!
+//! The format of the dump is *unstable*. Spans are written as byte offsets into their files, and
+//! lose the macro expansions they come from.
+
+use crate::diagnostic::SuggestionsDisabled;
+use crate::emitter::Emitter;
+use crate::translation::Translate;
+use crate::{
+    CodeSuggestion, CompilationSummary, Diagnostic, DiagnosticArgValue, DiagnosticId,
+    DiagnosticMessage, FatalError, FluentBundle, Level, MultiSpan, Style, SubDiagnostic,
+    Substitution, SubstitutionPart, SuggestionStyle,
+};
+use rustc_data_structures::sync::Lrc;
+use rustc_lint_defs::Applicability;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{BytePos, Span, DUMMY_SP};
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[cfg(test)]
+mod tests;
+
+/// Writes every diagnostic to `dst` before emitting it with the wrapped emitter.
+pub struct DumpEmitter {
+    inner: Box<dyn Emitter + Send>,
+    /// `None` once writing to it failed.
+    dst: Option<Box<dyn Write + Send>>,
+}
+
+impl DumpEmitter {
+    pub fn new(inner: Box<dyn Emitter + Send>, dst: Box<dyn Write + Send>) -> DumpEmitter {
+        DumpEmitter { inner, dst: Some(dst) }
+    }
+
+    fn dump(&mut self, diag: &Diagnostic) -> io::Result<()> {
+        let Some(dst) = &mut self.dst else { return Ok(()) };
+        let dumped = DumpedDiagnostic::new(diag, self.inner.source_map().map(|sm| &**sm));
+        serde_json::to_writer(&mut *dst, &dumped).map_err(io::Error::from)?;
+        writeln!(dst)?;
+        dst.flush()
+    }
+}
+
+impl Translate for DumpEmitter {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.inner.fluent_bundle()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        self.inner.fallback_fluent_bundle()
+    }
+}
+
+impl Emitter for DumpEmitter {
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        let result = self.dump(diag);
+        self.inner.emit_diagnostic(diag);
+        if let Err(e) = result {
+            // Stop dumping, so that the diagnostics emitted while aborting don't fail the same
+            // way.
+            self.dst = None;
+            let msg = format!("failed to write the diagnostics dump: {}", e);
+            self.inner.emit_diagnostic(&Diagnostic::new(Level::Fatal, msg));
+            FatalError.raise();
+        }
+    }
+
+    fn emit_artifact_notification(&mut self, path: &Path, artifact_type: &str) {
+        self.inner.emit_artifact_notification(path, artifact_type);
+    }
+
+    fn emit_diagnostic_digest(&mut self, digest: &str) {
+        self.inner.emit_diagnostic_digest(digest);
+    }
+
+    fn emit_future_breakage_report(&mut self, diags: Vec<Diagnostic>) {
+        self.inner.emit_future_breakage_report(diags);
+    }
+
+    fn emit_unused_externs(&mut self, lint_level: rustc_lint_defs::Level, unused_externs: &[&str]) {
+        self.inner.emit_unused_externs(lint_level, unused_externs);
+    }
+
+    fn emit_compilation_summary(&mut self, summary: &CompilationSummary) {
+        self.inner.emit_compilation_summary(summary);
+    }
+
+    fn should_show_explain(&self) -> bool {
+        self.inner.should_show_explain()
+    }
+
+    fn supports_color(&self) -> bool {
+        self.inner.supports_color()
+    }
+
+    fn shows_suggestion_tags(&self) -> bool {
+        self.inner.shows_suggestion_tags()
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        self.inner.source_map()
+    }
+}
+
+/// Reads back the diagnostics of a dump written by a [`DumpEmitter`], with their spans pointing
+/// into the files of `sm` of the same names.
+pub fn load_diagnostics(dump: &str, sm: &SourceMap) -> Result<Vec<Diagnostic>, String> {
+    dump.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let dumped: DumpedDiagnostic = serde_json::from_str(line).map_err(|e| e.to_string())?;
+            dumped.load(sm)
+        })
+        .collect()
+}
+
+#[derive(Serialize, Deserialize)]
+struct DumpedDiagnostic {
+    level: DumpedLevel,
+    message: Vec<DumpedStyledMessage>,
+    code: Option<DiagnosticId>,
+    span: DumpedMultiSpan,
+    children: Vec<DumpedSubdiagnostic>,
+    /// `None` if suggestions were disabled for the diagnostic.
+    suggestions: Option<Vec<DumpedSuggestion>>,
+    args: Vec<(String, DiagnosticArgValue<'static>)>,
+    is_lint: bool,
+}
+
+impl DumpedDiagnostic {
+    fn new(diag: &Diagnostic, sm: Option<&SourceMap>) -> DumpedDiagnostic {
+        DumpedDiagnostic {
+            level: diag.level.into(),
+            message: DumpedStyledMessage::new_all(&diag.message),
+            code: diag.code.clone(),
+            span: DumpedMultiSpan::new(&diag.span, sm),
+            children: diag
+                .children
+                .iter()
+                .map(|child| DumpedSubdiagnostic::new(child, sm))
+                .collect(),
+            suggestions: diag.suggestions.as_ref().ok().map(|suggestions| {
+                suggestions.iter().map(|sugg| DumpedSuggestion::new(sugg, sm)).collect()
+            }),
+            args: diag
+                .args()
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+            is_lint: diag.is_lint,
+        }
+    }
+
+    fn load(self, sm: &SourceMap) -> Result<Diagnostic, String> {
+        let mut diag = Diagnostic::new_with_code(self.level.into(), self.code, "");
+        diag.message = DumpedStyledMessage::load_all(self.message);
+        diag.span = self.span.load(sm)?;
+        diag.sort_span = diag.span.primary_span().unwrap_or(DUMMY_SP);
+        diag.children =
+            self.children.into_iter().map(|child| child.load(sm)).collect::<Result<_, String>>()?;
+        diag.suggestions = match self.suggestions {
+            Some(suggestions) => Ok(suggestions
+                .into_iter()
+                .map(|sugg| sugg.load(sm))
+                .collect::<Result<_, String>>()?),
+            None => Err(SuggestionsDisabled),
+        };
+        diag.replace_args(
+            self.args.into_iter().map(|(name, value)| (Cow::Owned(name), value)).collect(),
+        );
+        diag.is_lint = self.is_lint;
+        Ok(diag)
+    }
+}
+
+/// The levels of emitted diagnostics, without the lint expectations of `Level`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum DumpedLevel {
+    Bug,
+    Fatal,
+    Error { lint: bool },
+    Warning,
+    Note,
+    OnceNote,
+    ExplanatoryNote,
+    Help,
+    FailureNote,
+}
+
+impl From<Level> for DumpedLevel {
+    fn from(level: Level) -> DumpedLevel {
+        match level {
+            Level::Bug | Level::DelayedBug => DumpedLevel::Bug,
+            Level::Fatal => DumpedLevel::Fatal,
+            Level::Error { lint } => DumpedLevel::Error { lint },
+            Level::Warning(_) => DumpedLevel::Warning,
+            Level::Note => DumpedLevel::Note,
+            Level::OnceNote => DumpedLevel::OnceNote,
+            Level::ExplanatoryNote => DumpedLevel::ExplanatoryNote,
+            Level::Help => DumpedLevel::Help,
+            Level::FailureNote => DumpedLevel::FailureNote,
+            Level::Allow | Level::Expect(_) => panic!("{:?} diagnostics aren't emitted", level),
+        }
+    }
+}
+
+impl From<DumpedLevel> for Level {
+    fn from(level: DumpedLevel) -> Level {
+        match level {
+            DumpedLevel::Bug => Level::Bug,
+            DumpedLevel::Fatal => Level::Fatal,
+            DumpedLevel::Error { lint } => Level::Error { lint },
+            DumpedLevel::Warning => Level::Warning(None),
+            DumpedLevel::Note => Level::Note,
+            DumpedLevel::OnceNote => Level::OnceNote,
+            DumpedLevel::ExplanatoryNote => Level::ExplanatoryNote,
+            DumpedLevel::Help => Level::Help,
+            DumpedLevel::FailureNote => Level::FailureNote,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum DumpedMessage {
+    /// A translatable message, rendered with the arguments of its diagnostic.
+    Fluent { id: String, attr: Option<String> },
+    /// The text of a message which was built as a string, and so can't be translated.
+    Eager(String),
+}
+
+impl DumpedMessage {
+    fn new(msg: &DiagnosticMessage) -> DumpedMessage {
+        match msg {
+            DiagnosticMessage::Str(text) => DumpedMessage::Eager(text.clone()),
+            DiagnosticMessage::FluentIdentifier(id, attr) => DumpedMessage::Fluent {
+                id: id.to_string(),
+                attr: attr.as_ref().map(|attr| attr.to_string()),
+            },
+        }
+    }
+
+    fn load(self) -> DiagnosticMessage {
+        match self {
+            DumpedMessage::Fluent { id, attr } => {
+                DiagnosticMessage::FluentIdentifier(id.into(), attr.map(Into::into))
+            }
+            DumpedMessage::Eager(text) => DiagnosticMessage::Str(text),
+        }
+    }
+}
+
+/// A part of the message of a diagnostic, which is highlighted for `Style::Highlight`, like the
+/// types in "expected `u8`, found `u16`" notes.
+#[derive(Serialize, Deserialize)]
+struct DumpedStyledMessage {
+    message: DumpedMessage,
+    highlighted: bool,
+}
+
+impl DumpedStyledMessage {
+    fn new_all(message: &[(DiagnosticMessage, Style)]) -> Vec<DumpedStyledMessage> {
+        message
+            .iter()
+            .map(|(msg, style)| DumpedStyledMessage {
+                message: DumpedMessage::new(msg),
+                highlighted: *style == Style::Highlight,
+            })
+            .collect()
+    }
+
+    fn load_all(message: Vec<DumpedStyledMessage>) -> Vec<(DiagnosticMessage, Style)> {
+        message
+            .into_iter()
+            .map(|part| {
+                let style = if part.highlighted { Style::Highlight } else { Style::NoStyle };
+                (part.message.load(), style)
+            })
+            .collect()
+    }
+}
+
+/// A span as the byte offsets of its start and end in the file `file`.
+#[derive(Serialize, Deserialize)]
+struct DumpedSpan {
+    file: String,
+    lo: u32,
+    hi: u32,
+}
+
+impl DumpedSpan {
+    /// Returns `None` for spans which don't point into a file, like dummy spans.
+    fn new(span: Span, sm: Option<&SourceMap>) -> Option<DumpedSpan> {
+        let sm = sm?;
+        if span.is_dummy() {
+            return None;
+        }
+        let file = sm.lookup_source_file(span.lo());
+        Some(DumpedSpan {
+            file: file.name.prefer_local().to_string(),
+            lo: (span.lo() - file.start_pos).0,
+            hi: (span.hi() - file.start_pos).0,
+        })
+    }
+
+    fn load(&self, sm: &SourceMap) -> Result<Span, String> {
+        let start_pos = sm
+            .files()
+            .iter()
+            .find(|file| file.name.prefer_local().to_string() == self.file)
+            .map(|file| file.start_pos)
+            .ok_or_else(|| format!("the source map has no file `{}`", self.file))?;
+        Ok(Span::with_root_ctxt(start_pos + BytePos(self.lo), start_pos + BytePos(self.hi)))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct DumpedMultiSpan {
+    primary_spans: Vec<DumpedSpan>,
+    labels: Vec<(DumpedSpan, DumpedMessage)>,
+}
+
+impl DumpedMultiSpan {
+    fn new(span: &MultiSpan, sm: Option<&SourceMap>) -> DumpedMultiSpan {
+        DumpedMultiSpan {
+            primary_spans: span
+                .primary_spans()
+                .iter()
+                .filter_map(|&span| DumpedSpan::new(span, sm))
+                .collect(),
+            labels: span
+                .span_labels()
+                .into_iter()
+                .filter_map(|label| {
+                    Some((DumpedSpan::new(label.span, sm)?, DumpedMessage::new(&label.label?)))
+                })
+                .collect(),
+        }
+    }
+
+    fn load(self, sm: &SourceMap) -> Result<MultiSpan, String> {
+        let primary_spans =
+            self.primary_spans.iter().map(|span| span.load(sm)).collect::<Result<_, String>>()?;
+        let mut span = MultiSpan::from_spans(primary_spans);
+        for (label_span, label) in self.labels {
+            span.push_span_label(label_span.load(sm)?, label.load());
+        }
+        Ok(span)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct DumpedSubdiagnostic {
+    level: DumpedLevel,
+    message: Vec<DumpedStyledMessage>,
+    span: DumpedMultiSpan,
+    render_span: Option<DumpedMultiSpan>,
+}
+
+impl DumpedSubdiagnostic {
+    fn new(child: &SubDiagnostic, sm: Option<&SourceMap>) -> DumpedSubdiagnostic {
+        DumpedSubdiagnostic {
+            level: child.level.into(),
+            message: DumpedStyledMessage::new_all(&child.message),
+            span: DumpedMultiSpan::new(&child.span, sm),
+            render_span: child.render_span.as_ref().map(|span| DumpedMultiSpan::new(span, sm)),
+        }
+    }
+
+    fn load(self, sm: &SourceMap) -> Result<SubDiagnostic, String> {
+        Ok(SubDiagnostic {
+            level: self.level.into(),
+            message: DumpedStyledMessage::load_all(self.message),
+            span: self.span.load(sm)?,
+            render_span: self.render_span.map(|span| span.load(sm)).transpose()?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct DumpedSuggestion {
+    /// The parts of each of the alternative substitutions.
+    substitutions: Vec<Vec<(DumpedSpan, String)>>,
+    msg: DumpedMessage,
+    style: SuggestionStyle,
+    applicability: Applicability,
+    tag: Option<String>,
+}
+
+impl DumpedSuggestion {
+    fn new(sugg: &CodeSuggestion, sm: Option<&SourceMap>) -> DumpedSuggestion {
+        DumpedSuggestion {
+            substitutions: sugg
+                .substitutions
+                .iter()
+                .map(|subst| {
+                    subst
+                        .parts
+                        .iter()
+                        .filter_map(|part| {
+                            Some((DumpedSpan::new(part.span, sm)?, part.snippet.to_string()))
+                        })
+                        .collect()
+                })
+                .collect(),
+            msg: DumpedMessage::new(&sugg.msg),
+            style: sugg.style,
+            applicability: sugg.applicability,
+            tag: sugg.tag.as_ref().map(|tag| tag.to_string()),
+        }
+    }
+
+    fn load(self, sm: &SourceMap) -> Result<CodeSuggestion, String> {
+        let substitutions = self
+            .substitutions
+            .into_iter()
+            .map(|parts| {
+                let parts = parts
+                    .into_iter()
+                    .map(|(span, snippet)| Ok(SubstitutionPart { span: span.load(sm)?, snippet }))
+                    .collect::<Result<_, String>>()?;
+                Ok(Substitution { parts })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(CodeSuggestion {
+            substitutions,
+            msg: self.msg.load(),
+            style: self.style,
+            applicability: self.applicability,
+            tag: self.tag.map(Cow::Owned),
+        })
+    }
+}
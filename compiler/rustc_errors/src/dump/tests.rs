@@ -0,0 +1,180 @@
+use super::*;
+
+use crate::emitter::EmitterWriter;
+use crate::SubdiagnosticMessage;
+use rustc_span::source_map::FilePathMapping;
+
+use std::str;
+use std::sync::{Arc, Mutex};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+fn human_emitter(sm: &Lrc<SourceMap>, dst: Box<dyn Write + Send>) -> EmitterWriter {
+    let fallback_bundle =
+        crate::fallback_fluent_bundle(rustc_error_messages::DEFAULT_LOCALE_RESOURCES, false);
+    EmitterWriter::new(
+        dst,
+        Some(sm.clone()),
+        None,
+        fallback_bundle,
+        false,
+        false,
+        false,
+        None,
+        false,
+    )
+    .ui_testing(true)
+}
+
+/// Renders `diag` like in the `.stderr` files of UI tests.
+fn render(sm: &Lrc<SourceMap>, diag: &Diagnostic) -> String {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    human_emitter(sm, Box::new(Shared { data: output.clone() })).emit_diagnostic(diag);
+    let bytes = output.lock().unwrap();
+    str::from_utf8(&bytes).unwrap().to_owned()
+}
+
+#[test]
+fn dumped_diagnostics_load_back() {
+    rustc_span::create_default_session_globals_then(|| {
+        let code = "fn foo(x: &mut Vec<&u8>, y: &u8) {\n    x.push(y);\n}\n";
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let span = |start: &str, len: usize| {
+            let lo = code.find(start).unwrap();
+            Span::with_root_ctxt(BytePos(lo as u32), BytePos((lo + len) as u32))
+        };
+
+        let mut diag = Diagnostic::new_with_code(
+            Level::Error { lint: false },
+            Some(DiagnosticId::Error("E0623".to_owned())),
+            DiagnosticMessage::FluentIdentifier("infer_lifetime_mismatch".into(), None),
+        );
+        diag.set_span(span("x.push(y)", 9));
+        diag.span_label(
+            span("&u8>", 3),
+            "these two types are declared with different lifetimes...",
+        );
+        diag.span_label(span("&u8)", 3), "");
+        diag.span_label(span("x.push(y)", 9), "...but data from `y` flows into `x` here");
+        diag.set_arg("param_name", "'a");
+        diag.set_arg("is_reuse", false);
+        diag.set_arg("is_impl", false);
+        diag.multipart_suggestion(
+            SubdiagnosticMessage::FluentIdentifier("infer_lifetime_param_suggestion".into()),
+            vec![
+                (span("(x", 0), "<'a>".to_owned()),
+                (span("u8>", 0), "'a ".to_owned()),
+                (span("u8)", 0), "'a ".to_owned()),
+            ],
+            Applicability::MaybeIncorrect,
+        );
+
+        let dump = Arc::new(Mutex::new(Vec::new()));
+        let mut emitter = DumpEmitter::new(
+            Box::new(human_emitter(&sm, Box::new(io::sink()))),
+            Box::new(Shared { data: dump.clone() }),
+        );
+        emitter.emit_diagnostic(&diag);
+        let dump = str::from_utf8(&dump.lock().unwrap()).unwrap().to_owned();
+
+        assert_eq!(dump.lines().count(), 1);
+        assert!(dump.contains(r#"{"fluent":{"id":"infer_lifetime_mismatch","attr":null}}"#));
+        assert!(dump.contains(r#"{"eager":"...but data from `y` flows into `x` here"}"#));
+        assert!(dump.contains(r#"["param_name",{"Str":"'a"}]"#));
+
+        let loaded = load_diagnostics(&dump, &sm).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(render(&sm, &loaded[0]), render(&sm, &diag));
+    })
+}
+
+macro_rules! ui_test_file {
+    ($name:literal) => {
+        include_str!(concat!("../../../../src/test/ui/lifetimes/lifetime-errors/", $name))
+    };
+}
+
+/// The dump written by compiling the UI test `pin-receiver-lifetime-mismatch.rs` with
+/// `-Zdump-diagnostics`, with the directory of the test replaced by `$DIR` like in its `.stderr`.
+const UI_TEST_DUMP: &str = include_str!("pin-receiver-lifetime-mismatch.jsonl");
+
+#[test]
+fn loaded_ui_test_dump_renders_like_the_ui_test() {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(
+            Path::new("$DIR/pin-receiver-lifetime-mismatch.rs").to_owned().into(),
+            ui_test_file!("pin-receiver-lifetime-mismatch.rs").to_owned(),
+        );
+
+        let loaded = load_diagnostics(UI_TEST_DUMP, &sm).unwrap();
+        let rendered: String = loaded.iter().map(|diag| render(&sm, diag)).collect();
+        // The emitter ends each diagnostic with an empty line, which compiletest trims.
+        assert_eq!(
+            rendered.trim_end(),
+            ui_test_file!("pin-receiver-lifetime-mismatch.stderr").trim_end()
+        );
+    })
+}
+
+#[test]
+fn loading_needs_the_source_files() {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), "fn main() {}\n".to_owned());
+        let mut diag = Diagnostic::new(Level::Error { lint: false }, "oops");
+        diag.set_span(Span::with_root_ctxt(BytePos(3), BytePos(7)));
+        let dump = serde_json::to_string(&DumpedDiagnostic::new(&diag, Some(&sm))).unwrap();
+
+        let other_sm = SourceMap::new(FilePathMapping::empty());
+        assert_eq!(
+            load_diagnostics(&dump, &other_sm).unwrap_err(),
+            "the source map has no file `test.rs`"
+        );
+    })
+}
+
+struct FailingWriter;
+
+impl Write for FailingWriter {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn failing_to_dump_is_a_fatal_error() {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let mut emitter = DumpEmitter::new(
+            Box::new(human_emitter(&sm, Box::new(Shared { data: output.clone() }))),
+            Box::new(FailingWriter),
+        );
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            emitter.emit_diagnostic(&Diagnostic::new(Level::Error { lint: false }, "oops"));
+        }));
+        assert!(result.unwrap_err().is::<crate::FatalErrorMarker>());
+        assert_eq!(
+            str::from_utf8(&output.lock().unwrap()).unwrap(),
+            "error: oops\n\nerror: failed to write the diagnostics dump: disk full\n\n"
+        );
+    })
+}
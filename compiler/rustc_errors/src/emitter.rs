@@ -16,8 +16,9 @@
 use crate::styled_buffer::StyledBuffer;
 use crate::translation::Translate;
 use crate::{
-    CodeSuggestion, Diagnostic, DiagnosticId, DiagnosticMessage, FluentBundle, Handler,
-    LazyFallbackBundle, Level, MultiSpan, SubDiagnostic, SubstitutionHighlight, SuggestionStyle,
+    CodeSuggestion, CompilationSummary, Diagnostic, DiagnosticId, DiagnosticMessage, FluentBundle,
+    Handler, LazyFallbackBundle, Level, MultiSpan, SubDiagnostic, SubstitutionHighlight,
+    SuggestionStyle,
 };
 
 use rustc_lint_defs::pluralize;
@@ -36,8 +37,11 @@
 use termcolor::{Buffer, Color, WriteColor};
 use tracing::*;
 
+#[cfg(test)]
+mod tests;
+
 /// Default column width, used in tests and when terminal dimensions cannot be determined.
-const DEFAULT_COLUMN_WIDTH: usize = 140;
+pub const DEFAULT_COLUMN_WIDTH: usize = 140;
 
 /// Describes the way the content of the `rendered` field of the json output is generated
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -209,8 +213,18 @@ pub trait Emitter: Translate {
     /// other formats can, and will, simply ignore it.
     fn emit_artifact_notification(&mut self, _path: &Path, _artifact_type: &str) {}
 
+    /// Emit a digest of an emitted diagnostic, see `Diagnostic::digest`.
+    /// This is currently only supported for the JSON format,
+    /// other formats can, and will, simply ignore it.
+    fn emit_diagnostic_digest(&mut self, _digest: &str) {}
+
     fn emit_future_breakage_report(&mut self, _diags: Vec<Diagnostic>) {}
 
+    /// Emit the summary of the diagnostics of the compilation, as the last message.
+    /// This is currently only supported for the JSON format,
+    /// other formats can, and will, simply ignore it.
+    fn emit_compilation_summary(&mut self, _summary: &CompilationSummary) {}
+
     /// Emit list of unused externs
     fn emit_unused_externs(
         &mut self,
@@ -229,6 +243,12 @@ fn supports_color(&self) -> bool {
         false
     }
 
+    /// Checks if suggestions should be followed by their tag, see
+    /// `Diagnostic::tag_last_suggestion`.
+    fn shows_suggestion_tags(&self) -> bool {
+        false
+    }
+
     fn source_map(&self) -> Option<&Lrc<SourceMap>>;
 
     /// Formats the substitutions of the primary_span
@@ -295,6 +315,10 @@ fn primary_span_formatted<'a>(
                         substitution,
                     )
                 };
+                let msg = match &sugg.tag {
+                    Some(tag) if self.shows_suggestion_tags() => format!("{} [{}]", msg, tag),
+                    _ => msg,
+                };
                 primary_span.push_span_label(sugg.substitutions[0].parts[0].span, msg);
 
                 // We return only the modified primary_span
@@ -568,6 +592,10 @@ fn should_show_explain(&self) -> bool {
     fn supports_color(&self) -> bool {
         self.dst.supports_color()
     }
+
+    fn shows_suggestion_tags(&self) -> bool {
+        self.tag_suggestions
+    }
 }
 
 /// An emitter that does nothing when emitting a non-fatal diagnostic.
@@ -648,6 +676,7 @@ pub struct EmitterWriter {
     short_message: bool,
     teach: bool,
     ui_testing: bool,
+    tag_suggestions: bool,
     diagnostic_width: Option<usize>,
 
     macro_backtrace: bool,
@@ -680,6 +709,7 @@ pub fn stderr(
             short_message,
             teach,
             ui_testing: false,
+            tag_suggestions: false,
             diagnostic_width,
             macro_backtrace,
         }
@@ -704,6 +734,7 @@ pub fn new(
             short_message,
             teach,
             ui_testing: false,
+            tag_suggestions: false,
             diagnostic_width,
             macro_backtrace,
         }
@@ -714,6 +745,25 @@ pub fn ui_testing(mut self, ui_testing: bool) -> Self {
         self
     }
 
+    /// Allows to modify `Self` to show or hide the tags of suggestions.
+    pub fn tag_suggestions(mut self, tag_suggestions: bool) -> Self {
+        self.tag_suggestions = tag_suggestions;
+        self
+    }
+
+    /// The message of `suggestion`, followed by its tag if tags are shown.
+    fn suggestion_msg(
+        &self,
+        suggestion: &CodeSuggestion,
+        style: Style,
+    ) -> Vec<(DiagnosticMessage, Style)> {
+        let mut msg = vec![(suggestion.msg.to_owned(), style)];
+        if let Some(tag) = suggestion.tag.as_ref().filter(|_| self.tag_suggestions) {
+            msg.push((DiagnosticMessage::Str(format!(" [{}]", tag)), style));
+        }
+        msg
+    }
+
     fn maybe_anonymized(&self, line_num: usize) -> Cow<'static, str> {
         if self.ui_testing {
             Cow::Borrowed(ANONYMIZED_LINE_NUM)
@@ -1244,8 +1294,15 @@ fn get_max_line_num(&mut self, span: &MultiSpan, children: &[SubDiagnostic]) ->
             .max(primary)
     }
 
+    /// The width at which message text is soft-wrapped, which is only done when a width is
+    /// explicitly requested with `--diagnostic-width`.
+    fn wrap_width(&self) -> Option<usize> {
+        self.diagnostic_width.filter(|_| !self.short_message)
+    }
+
     /// Adds a left margin to every line but the first, given a padding length and the label being
-    /// displayed, keeping the provided highlighting.
+    /// displayed, keeping the provided highlighting. The text is soft-wrapped at `wrap_width`, if
+    /// there is one.
     fn msg_to_buffer(
         &self,
         buffer: &mut StyledBuffer,
@@ -1254,6 +1311,7 @@ fn msg_to_buffer(
         padding: usize,
         label: &str,
         override_style: Option<Style>,
+        wrap_width: Option<usize>,
     ) {
         // The extra 5 ` ` is padding that's always needed to align to the `note: `:
         //
@@ -1282,6 +1340,7 @@ fn style_or_override(style: Style, override_: Option<Style>) -> Style {
         }
 
         let mut line_number = 0;
+        let mut col = padding.len();
 
         // Provided the following diagnostic message:
         //
@@ -1303,7 +1362,11 @@ fn style_or_override(style: Style, override_: Option<Style>) -> Style {
         //                very *weird* formats
         //                see?
         for &(ref text, ref style) in msg.iter() {
-            let text = self.translate_message(text, args);
+            let mut text = self.translate_message(text, args);
+            if let Some(width) = wrap_width {
+                let (wrapped, end) = soft_wrap(&text, col, padding.len(), width);
+                (text, col) = (Cow::Owned(wrapped), end);
+            }
             let lines = text.split('\n').collect::<Vec<_>>();
             if lines.len() > 1 {
                 for (i, line) in lines.iter().enumerate() {
@@ -1342,7 +1405,15 @@ fn emit_message_default(
                 buffer.append(0, level.to_str(), Style::MainHeaderMsg);
                 buffer.append(0, ": ", Style::NoStyle);
             }
-            self.msg_to_buffer(&mut buffer, msg, args, max_line_num_len, "note", None);
+            self.msg_to_buffer(
+                &mut buffer,
+                msg,
+                args,
+                max_line_num_len,
+                "note",
+                None,
+                self.wrap_width(),
+            );
         } else {
             let mut label_width = 0;
             // The failure note level itself does not provide any useful diagnostic information
@@ -1362,12 +1433,19 @@ fn emit_message_default(
                 buffer.append(0, ": ", header_style);
                 label_width += 2;
             }
+            let mut line_number = 0;
+            let mut col = label_width;
             for &(ref text, _) in msg.iter() {
                 let text = self.translate_message(text, args);
+                let mut text = normalize_whitespace(&text);
+                // Failure notes point at commands to run, which are best left intact.
+                if let Some(width) = self.wrap_width().filter(|_| *level != Level::FailureNote) {
+                    (text, col) = soft_wrap(&text, col, label_width, width);
+                }
                 // Account for newlines to align output to its label.
-                for (line, text) in normalize_whitespace(&text).lines().enumerate() {
+                for (line, text) in text.lines().enumerate() {
                     buffer.append(
-                        0 + line,
+                        line_number + line,
                         &format!(
                             "{}{}",
                             if line == 0 { String::new() } else { " ".repeat(label_width) },
@@ -1376,6 +1454,7 @@ fn emit_message_default(
                         header_style,
                     );
                 }
+                line_number += text.lines().count().saturating_sub(1);
             }
         }
 
@@ -1690,11 +1769,12 @@ fn emit_suggestion_default(
 
         self.msg_to_buffer(
             &mut buffer,
-            &[(suggestion.msg.to_owned(), Style::NoStyle)],
+            &self.suggestion_msg(suggestion, Style::NoStyle),
             args,
             max_line_num_len,
             "suggestion",
             Some(Style::HeaderMsg),
+            None,
         );
 
         let mut row_num = 2;
@@ -2018,7 +2098,7 @@ fn emit_messages_default(
                         } else if sugg.style == SuggestionStyle::HideCodeAlways {
                             if let Err(e) = self.emit_message_default(
                                 &MultiSpan::new(),
-                                &[(sugg.msg.to_owned(), Style::HeaderMsg)],
+                                &self.suggestion_msg(sugg, Style::HeaderMsg),
                                 args,
                                 &None,
                                 &Level::Help,
@@ -2347,6 +2427,104 @@ fn normalize_whitespace(str: &str) -> String {
     s
 }
 
+/// Messages aren't wrapped when there's less room than this left for each of their lines, as
+/// that would only make them harder to read.
+const MIN_WRAPPED_TEXT_WIDTH: usize = 20;
+
+/// Soft-wraps `text`, which starts at column `col`, so that it fits in `width` columns when its
+/// continuation lines are indented by `indent` columns. Lines are only broken at spaces outside
+/// of backtick-quoted code, and quoted code which doesn't fit on a line of its own is elided in
+/// the middle. Returns the wrapped text and the column at which it ends.
+fn soft_wrap(text: &str, mut col: usize, indent: usize, width: usize) -> (String, usize) {
+    let available = width.saturating_sub(indent);
+    if available < MIN_WRAPPED_TEXT_WIDTH {
+        let col = match text.rsplit_once('\n') {
+            Some((_, last)) => indent + last.chars().count(),
+            None => col + text.chars().count(),
+        };
+        return (text.to_string(), col);
+    }
+    let mut wrapped = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            wrapped.push('\n');
+            col = indent;
+        }
+        for (j, word) in split_words(line).into_iter().enumerate() {
+            let word = elide_quoted(word, available);
+            let len = word.chars().count();
+            if j > 0 {
+                if col + 1 + len > width && col > indent {
+                    wrapped.push('\n');
+                    col = indent;
+                } else {
+                    wrapped.push(' ');
+                    col += 1;
+                }
+            }
+            wrapped.push_str(&word);
+            col += len;
+        }
+    }
+    (wrapped, col)
+}
+
+/// Splits `line` at the spaces which aren't part of backtick-quoted code.
+fn split_words(line: &str) -> Vec<&str> {
+    let mut words = vec![];
+    let mut start = 0;
+    let mut in_code = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '`' => in_code = !in_code,
+            ' ' if !in_code => {
+                words.push(&line[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    words.push(&line[start..]);
+    words
+}
+
+/// Shortens the backtick-quoted code in `word` so that it fits in `max` columns, e.g. to
+/// `Foo<...>::Bar`. Words without quoted code are left as they are, and so are words with more
+/// than one piece of quoted code, as eliding across pieces could leave a backtick unmatched.
+fn elide_quoted(word: &str, max: usize) -> Cow<'_, str> {
+    let len = word.chars().count();
+    if len <= max {
+        return Cow::Borrowed(word);
+    }
+    let (Some(open), Some(close)) = (word.find('`'), word.rfind('`')) else {
+        return Cow::Borrowed(word);
+    };
+    if open == close {
+        return Cow::Borrowed(word);
+    }
+    let code: Vec<char> = word[open + 1..close].chars().collect();
+    if code.contains(&'`') {
+        return Cow::Borrowed(word);
+    }
+    // The code has to keep at least a little bit of its start and end to be recognizable.
+    let budget = (code.len() + max).saturating_sub(len + 3).max(6);
+    if budget + 3 >= code.len() {
+        return Cow::Borrowed(word);
+    }
+    let is_boundary = |i: usize| matches!(code[i - 1], '<' | '(' | ' ') || code[i] == '>';
+    let half = budget - budget / 2;
+    let head = (1..=half).rev().find(|&i| is_boundary(i)).unwrap_or(half);
+    let tail_start = code.len() - (budget - head);
+    let tail = (tail_start..code.len()).find(|&i| is_boundary(i)).unwrap_or(tail_start);
+    Cow::Owned(format!(
+        "{}{}...{}{}",
+        &word[..=open],
+        code[..head].iter().collect::<String>(),
+        code[tail..].iter().collect::<String>(),
+        &word[close..],
+    ))
+}
+
 fn draw_col_separator(buffer: &mut StyledBuffer, line: usize, col: usize) {
     buffer.puts(line, col, "| ", Style::LineNumber);
 }
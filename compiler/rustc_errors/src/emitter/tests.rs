@@ -0,0 +1,66 @@
+use super::*;
+
+#[test]
+fn soft_wrap_keeps_long_words_whole() {
+    // A word longer than the width gets a line of its own instead of being split.
+    assert_eq!(
+        soft_wrap("a verylongwordthatexceedsthewidth b", 0, 0, 20),
+        ("a\nverylongwordthatexceedsthewidth\nb".to_string(), 1)
+    );
+}
+
+#[test]
+fn soft_wrap_counts_chars() {
+    // Multibyte characters take up a single column each.
+    assert_eq!(
+        soft_wrap("die Größe des Typs ändert sich während der Übersetzung", 4, 4, 30),
+        ("die Größe des Typs ändert\nsich während der\nÜbersetzung".to_string(), 15)
+    );
+}
+
+#[test]
+fn soft_wrap_keeps_quoted_code_together() {
+    assert_eq!(
+        soft_wrap("expected `Foo`, found `Bar` in `Baz<Qux>`", 0, 0, 20),
+        ("expected `Foo`,\nfound `Bar` in\n`Baz<Qux>`".to_string(), 10)
+    );
+    assert_eq!(
+        split_words("the type `Vec<(u8, u8)>` is"),
+        ["the", "type", "`Vec<(u8, u8)>`", "is"]
+    );
+}
+
+#[test]
+fn soft_wrap_leaves_narrow_messages_alone() {
+    let text = "this message would only get harder to read";
+    assert_eq!(soft_wrap(text, 30, 30, 45), (text.to_string(), 30 + text.chars().count()));
+}
+
+#[test]
+fn elide_quoted_at_boundaries() {
+    assert_eq!(
+        elide_quoted("`Vec<HashMap<String, Option<Box<dyn Fn(u8) -> u8>>>>`", 24),
+        "`Vec<...u8) -> u8>>>>`"
+    );
+    assert_eq!(elide_quoted("`Größe<Äpfel<Öl>, Übermäßig<Lang>>`", 20), "`Größe<...Lang>>`");
+}
+
+#[test]
+fn elide_quoted_leaves_unquoted_and_fitting_words_alone() {
+    assert_eq!(
+        elide_quoted("verylongwordthatexceedsthewidth", 20),
+        "verylongwordthatexceedsthewidth"
+    );
+    assert_eq!(elide_quoted("`Vec<u8>`", 20), "`Vec<u8>`");
+    // An unmatched backtick doesn't start any quoted code.
+    assert_eq!(
+        elide_quoted("`verylongwordthatexceedsthewidth", 20),
+        "`verylongwordthatexceedsthewidth"
+    );
+}
+
+#[test]
+fn elide_quoted_leaves_several_pieces_of_code_alone() {
+    let word = "`Foo<Bar<Baz, Quux>>`::`method_name_that_is_long`";
+    assert_eq!(elide_quoted(word, 20), word);
+}
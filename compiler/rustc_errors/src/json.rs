@@ -16,14 +16,16 @@
 use crate::translation::Translate;
 use crate::DiagnosticId;
 use crate::{
-    CodeSuggestion, FluentBundle, LazyFallbackBundle, MultiSpan, SpanLabel, SubDiagnostic,
+    CodeSuggestion, DiagnosticMetadata, FluentBundle, LazyFallbackBundle, MultiSpan, SpanLabel,
+    SubDiagnostic,
 };
 use rustc_lint_defs::Applicability;
 
 use rustc_data_structures::sync::Lrc;
 use rustc_error_messages::FluentArgs;
 use rustc_span::hygiene::ExpnData;
-use rustc_span::Span;
+use rustc_span::{FileName, RealFileName, Span};
+use std::collections::BTreeMap;
 use std::io::{self, Write};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
@@ -42,6 +44,7 @@ pub struct JsonEmitter {
     fallback_bundle: LazyFallbackBundle,
     pretty: bool,
     ui_testing: bool,
+    tag_suggestions: bool,
     json_rendered: HumanReadableErrorType,
     diagnostic_width: Option<usize>,
     macro_backtrace: bool,
@@ -66,6 +69,7 @@ pub fn stderr(
             fallback_bundle,
             pretty,
             ui_testing: false,
+            tag_suggestions: false,
             json_rendered,
             diagnostic_width,
             macro_backtrace,
@@ -112,6 +116,7 @@ pub fn new(
             fallback_bundle,
             pretty,
             ui_testing: false,
+            tag_suggestions: false,
             json_rendered,
             diagnostic_width,
             macro_backtrace,
@@ -121,6 +126,10 @@ pub fn new(
     pub fn ui_testing(self, ui_testing: bool) -> Self {
         Self { ui_testing, ..self }
     }
+
+    pub fn tag_suggestions(self, tag_suggestions: bool) -> Self {
+        Self { tag_suggestions, ..self }
+    }
 }
 
 impl Translate for JsonEmitter {
@@ -133,15 +142,21 @@ fn fallback_fluent_bundle(&self) -> &FluentBundle {
     }
 }
 
+impl JsonEmitter {
+    fn emit(&mut self, val: EmitTyped<'_>) -> io::Result<()> {
+        if self.pretty {
+            writeln!(&mut self.dst, "{}", serde_json::to_string_pretty(&val).unwrap())
+        } else {
+            writeln!(&mut self.dst, "{}", serde_json::to_string(&val).unwrap())
+        }
+        .and_then(|_| self.dst.flush())
+    }
+}
+
 impl Emitter for JsonEmitter {
     fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
         let data = Diagnostic::from_errors_diagnostic(diag, self);
-        let result = if self.pretty {
-            writeln!(&mut self.dst, "{}", serde_json::to_string_pretty(&data).unwrap())
-        } else {
-            writeln!(&mut self.dst, "{}", serde_json::to_string(&data).unwrap())
-        }
-        .and_then(|_| self.dst.flush());
+        let result = self.emit(EmitTyped::Diagnostic(data));
         if let Err(e) = result {
             panic!("failed to print diagnostics: {:?}", e);
         }
@@ -149,17 +164,20 @@ fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
 
     fn emit_artifact_notification(&mut self, path: &Path, artifact_type: &str) {
         let data = ArtifactNotification { artifact: path, emit: artifact_type };
-        let result = if self.pretty {
-            writeln!(&mut self.dst, "{}", serde_json::to_string_pretty(&data).unwrap())
-        } else {
-            writeln!(&mut self.dst, "{}", serde_json::to_string(&data).unwrap())
-        }
-        .and_then(|_| self.dst.flush());
+        let result = self.emit(EmitTyped::Artifact(data));
         if let Err(e) = result {
             panic!("failed to print notification: {:?}", e);
         }
     }
 
+    fn emit_diagnostic_digest(&mut self, digest: &str) {
+        let data = DiagnosticDigest { digest };
+        let result = self.emit(EmitTyped::Digest(data));
+        if let Err(e) = result {
+            panic!("failed to print diagnostic digest: {:?}", e);
+        }
+    }
+
     fn emit_future_breakage_report(&mut self, diags: Vec<crate::Diagnostic>) {
         let data: Vec<FutureBreakageItem> = diags
             .into_iter()
@@ -171,12 +189,7 @@ fn emit_future_breakage_report(&mut self, diags: Vec<crate::Diagnostic>) {
             })
             .collect();
         let report = FutureIncompatReport { future_incompat_report: data };
-        let result = if self.pretty {
-            writeln!(&mut self.dst, "{}", serde_json::to_string_pretty(&report).unwrap())
-        } else {
-            writeln!(&mut self.dst, "{}", serde_json::to_string(&report).unwrap())
-        }
-        .and_then(|_| self.dst.flush());
+        let result = self.emit(EmitTyped::FutureIncompat(report));
         if let Err(e) = result {
             panic!("failed to print future breakage report: {:?}", e);
         }
@@ -185,17 +198,24 @@ fn emit_future_breakage_report(&mut self, diags: Vec<crate::Diagnostic>) {
     fn emit_unused_externs(&mut self, lint_level: rustc_lint_defs::Level, unused_externs: &[&str]) {
         let lint_level = lint_level.as_str();
         let data = UnusedExterns { lint_level, unused_extern_names: unused_externs };
-        let result = if self.pretty {
-            writeln!(&mut self.dst, "{}", serde_json::to_string_pretty(&data).unwrap())
-        } else {
-            writeln!(&mut self.dst, "{}", serde_json::to_string(&data).unwrap())
-        }
-        .and_then(|_| self.dst.flush());
+        let result = self.emit(EmitTyped::UnusedExtern(data));
         if let Err(e) = result {
             panic!("failed to print unused externs: {:?}", e);
         }
     }
 
+    fn emit_compilation_summary(&mut self, summary: &crate::CompilationSummary) {
+        let data = CompilationSummary {
+            errors: summary.errors,
+            warnings: summary.warnings,
+            error_codes: &summary.error_codes,
+        };
+        let result = self.emit(EmitTyped::CompilationSummary(data));
+        if let Err(e) = result {
+            panic!("failed to print compilation summary: {:?}", e);
+        }
+    }
+
     fn source_map(&self) -> Option<&Lrc<SourceMap>> {
         Some(&self.sm)
     }
@@ -207,6 +227,18 @@ fn should_show_explain(&self) -> bool {
 
 // The following data types are provided just for serialisation.
 
+/// The messages of the JSON output, each tagged with its kind in `$message_type`.
+#[derive(Serialize)]
+#[serde(tag = "$message_type", rename_all = "snake_case")]
+enum EmitTyped<'a> {
+    Diagnostic(Diagnostic),
+    Artifact(ArtifactNotification<'a>),
+    Digest(DiagnosticDigest<'a>),
+    FutureIncompat(FutureIncompatReport),
+    UnusedExtern(UnusedExterns<'a, 'a, 'a>),
+    CompilationSummary(CompilationSummary<'a>),
+}
+
 #[derive(Serialize)]
 struct Diagnostic {
     /// The primary error message.
@@ -219,11 +251,22 @@ struct Diagnostic {
     children: Vec<Diagnostic>,
     /// The message as rustc would render it.
     rendered: Option<String>,
+    /// The indices, in the order they were emitted, of earlier errors which may have
+    /// caused this one.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_diagnostics: Vec<usize>,
+    /// Structured information for tools which only the top-level diagnostic carries.
+    #[serde(skip_serializing_if = "DiagnosticMetadata::is_empty")]
+    metadata: DiagnosticMetadata,
 }
 
 #[derive(Serialize)]
 struct DiagnosticSpan {
     file_name: String,
+    /// The path of the file on this machine, if the file was included by `include!` and
+    /// `--remap-path-prefix` changed `file_name`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    local_file_name: Option<String>,
     byte_start: u32,
     byte_end: u32,
     /// 1-based.
@@ -288,6 +331,22 @@ struct ArtifactNotification<'a> {
     emit: &'a str,
 }
 
+#[derive(Serialize)]
+struct DiagnosticDigest<'a> {
+    /// The Fluent identifier and sorted argument names of the preceding diagnostic.
+    digest: &'a str,
+}
+
+#[derive(Serialize)]
+struct CompilationSummary<'a> {
+    /// The number of errors emitted, without duplicates.
+    errors: usize,
+    /// The number of warnings emitted, without duplicates.
+    warnings: usize,
+    /// How many of the errors had each error code.
+    error_codes: &'a BTreeMap<String, usize>,
+}
+
 #[derive(Serialize)]
 struct FutureBreakageItem {
     diagnostic: Diagnostic,
@@ -322,6 +381,8 @@ fn from_errors_diagnostic(diag: &crate::Diagnostic, je: &JsonEmitter) -> Diagnos
                 spans: DiagnosticSpan::from_suggestion(sugg, &args, je),
                 children: vec![],
                 rendered: None,
+                related_diagnostics: vec![],
+                metadata: DiagnosticMetadata::default(),
             }
         });
 
@@ -352,6 +413,7 @@ fn flush(&mut self) -> io::Result<()> {
                 je.macro_backtrace,
             )
             .ui_testing(je.ui_testing)
+            .tag_suggestions(je.tag_suggestions)
             .emit_diagnostic(diag);
         let output = Arc::try_unwrap(output.0).unwrap().into_inner().unwrap();
         let output = String::from_utf8(output).unwrap();
@@ -369,6 +431,8 @@ fn flush(&mut self) -> io::Result<()> {
                 .chain(sugg)
                 .collect(),
             rendered: Some(output),
+            related_diagnostics: diag.related.iter().map(|id| id.index()).collect(),
+            metadata: diag.metadata.clone(),
         }
     }
 
@@ -389,6 +453,8 @@ fn from_sub_diagnostic(
                 .unwrap_or_else(|| DiagnosticSpan::from_multispan(&diag.span, args, je)),
             children: vec![],
             rendered: None,
+            related_diagnostics: vec![],
+            metadata: DiagnosticMetadata::default(),
         }
     }
 }
@@ -452,8 +518,20 @@ fn from_span_full(
             })
         });
 
+        // Generated files which are `include!`d may be gone by the time an editor opens them
+        // under their remapped name. For any other file, the remapping hides the local path.
+        let local_file_name = match &start.file.name {
+            FileName::Real(RealFileName::Remapped { local_path: Some(local_path), .. })
+                if je.sm.include_site(span).is_some() =>
+            {
+                Some(local_path.display().to_string())
+            }
+            _ => None,
+        };
+
         DiagnosticSpan {
             file_name: je.sm.filename_for_diagnostics(&start.file.name).to_string(),
+            local_file_name,
             byte_start: start.file.original_relative_byte_pos(span.lo()).0,
             byte_end: start.file.original_relative_byte_pos(span.hi()).0,
             line_start: start.line,
@@ -40,34 +40,39 @@ fn flush(&mut self) -> io::Result<()> {
     }
 }
 
+/// Emits the diagnostics from `emit` for `code` with a JSON emitter and returns the output.
+fn emit_json(code: &str, emit: impl FnOnce(&Handler)) -> String {
+    let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+    let fallback_bundle =
+        crate::fallback_fluent_bundle(rustc_error_messages::DEFAULT_LOCALE_RESOURCES, false);
+
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let je = JsonEmitter::new(
+        Box::new(Shared { data: output.clone() }),
+        None,
+        sm,
+        None,
+        fallback_bundle,
+        true,
+        HumanReadableErrorType::Short(ColorConfig::Never),
+        None,
+        false,
+    );
+
+    let handler = Handler::with_emitter(true, None, Box::new(je));
+    emit(&handler);
+
+    let bytes = output.lock().unwrap();
+    str::from_utf8(&bytes).unwrap().to_owned()
+}
+
 /// Test the span yields correct positions in JSON.
 fn test_positions(code: &str, span: (u32, u32), expected_output: SpanTestData) {
     rustc_span::create_default_session_globals_then(|| {
-        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
-        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
-        let fallback_bundle =
-            crate::fallback_fluent_bundle(rustc_error_messages::DEFAULT_LOCALE_RESOURCES, false);
-
-        let output = Arc::new(Mutex::new(Vec::new()));
-        let je = JsonEmitter::new(
-            Box::new(Shared { data: output.clone() }),
-            None,
-            sm,
-            None,
-            fallback_bundle,
-            true,
-            HumanReadableErrorType::Short(ColorConfig::Never),
-            None,
-            false,
-        );
-
         let span = Span::with_root_ctxt(BytePos(span.0), BytePos(span.1));
-        let handler = Handler::with_emitter(true, None, Box::new(je));
-        handler.span_err(span, "foo");
-
-        let bytes = output.lock().unwrap();
-        let actual_output = str::from_utf8(&bytes).unwrap();
-        let actual_output: TestData = serde_json::from_str(actual_output).unwrap();
+        let actual_output = emit_json(code, |handler| handler.span_err(span, "foo"));
+        let actual_output: TestData = serde_json::from_str(&actual_output).unwrap();
         let spans = actual_output.spans;
         assert_eq!(spans.len(), 1);
 
@@ -202,3 +207,69 @@ fn span_after_crlf() {
         },
     )
 }
+
+#[test]
+fn metadata() {
+    rustc_span::create_default_session_globals_then(|| {
+        let output = emit_json("", |handler| {
+            handler.struct_err("foo").emit();
+            let mut err = handler.struct_err("bar");
+            err.metadata.impl_candidates.push(crate::ImplCandidate {
+                krate: "core".to_owned(),
+                self_ty: "T".to_owned(),
+                trait_args: vec!["u8".to_owned()],
+                is_blanket: true,
+            });
+            err.emit();
+            let mut note = handler.struct_note_without_error("baz");
+            note.metadata.suppressed_errors = Some(3);
+            note.emit();
+        });
+        let diagnostics: Vec<serde_json::Value> = serde_json::Deserializer::from_str(&output)
+            .into_iter()
+            .map(|diagnostic| diagnostic.unwrap())
+            .collect();
+        assert_eq!(diagnostics[0].get("metadata"), None);
+        assert_eq!(
+            diagnostics[1]["metadata"],
+            serde_json::json!({
+                "impl_candidates": [
+                    { "crate": "core", "self_ty": "T", "trait_args": ["u8"], "is_blanket": true }
+                ]
+            })
+        );
+        assert_eq!(diagnostics[2]["metadata"], serde_json::json!({ "suppressed_errors": 3 }));
+    })
+}
+
+#[test]
+fn compilation_summary() {
+    rustc_span::create_default_session_globals_then(|| {
+        let output = emit_json("", |handler| {
+            let code = || DiagnosticId::Error("E0282".to_owned());
+            handler.struct_err_with_code("foo", code()).note("a note").emit();
+            handler.struct_err_with_code("bar", code()).emit();
+            handler.struct_err("baz").emit();
+            handler.struct_warn("qux").emit();
+            handler.print_error_count(&Registry::new(&[]));
+            handler.emit_compilation_summary();
+        });
+        let messages: Vec<serde_json::Value> = serde_json::Deserializer::from_str(&output)
+            .into_iter()
+            .map(|message| message.unwrap())
+            .collect();
+        assert_eq!(messages[0]["$message_type"], "diagnostic");
+        assert_eq!(messages[0]["children"][0].get("$message_type"), None);
+        // The recap of `print_error_count` is not counted.
+        assert_eq!(messages.len(), 6);
+        assert_eq!(
+            messages[5],
+            serde_json::json!({
+                "$message_type": "compilation_summary",
+                "errors": 3,
+                "warnings": 1,
+                "error_codes": { "E0282": 2 }
+            })
+        );
+    })
+}
@@ -22,6 +22,7 @@
 pub use emitter::ColorConfig;
 
 use rustc_lint_defs::LintExpectationId;
+use serde::{Deserialize, Serialize};
 use Level::*;
 
 use emitter::{is_case_difference, Emitter, EmitterWriter};
@@ -32,15 +33,18 @@
 use rustc_data_structures::AtomicRef;
 pub use rustc_error_messages::{
     fallback_fluent_bundle, fluent, fluent_bundle, DiagnosticMessage, FluentBundle,
-    LanguageIdentifier, LazyFallbackBundle, MultiSpan, SpanLabel, SubdiagnosticMessage,
-    DEFAULT_LOCALE_RESOURCES,
+    IntoLabelMessage, IntoNoteMessage, IntoSuggestionMessage, LabelMessage, LanguageIdentifier,
+    LazyFallbackBundle, MultiSpan, NoteMessage, SpanLabel, SubdiagnosticMessage,
+    SuggestionMessage, DEFAULT_LOCALE_RESOURCES,
 };
 pub use rustc_lint_defs::{pluralize, Applicability};
 use rustc_span::source_map::SourceMap;
 use rustc_span::HashStableContext;
-use rustc_span::{Loc, Span};
+use rustc_span::{Loc, Span, DUMMY_SP};
+use translation::Translate;
 
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::hash::Hash;
 use std::num::NonZeroUsize;
 use std::panic;
@@ -52,6 +56,7 @@
 pub mod annotate_snippet_emitter_writer;
 mod diagnostic;
 mod diagnostic_builder;
+pub mod dump;
 pub mod emitter;
 pub mod json;
 mod lock;
@@ -60,6 +65,9 @@
 mod styled_buffer;
 pub mod translation;
 
+#[cfg(test)]
+mod tests;
+
 pub use snippet::Style;
 
 pub type PResult<'a, T> = Result<T, DiagnosticBuilder<'a, ErrorGuaranteed>>;
@@ -71,7 +79,7 @@
 #[cfg(all(target_arch = "x86_64", target_pointer_width = "64"))]
 rustc_data_structures::static_assert_size!(PResult<'_, bool>, 24);
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Encodable, Decodable)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Encodable, Decodable, Serialize, Deserialize)]
 pub enum SuggestionStyle {
     /// Hide the suggested code when displaying this suggestion inline.
     HideCodeInline,
@@ -126,6 +134,8 @@ pub struct CodeSuggestion {
     /// which are useful for users but not useful for
     /// tools like rustfix
     pub applicability: Applicability,
+    /// Which code produced this suggestion, see `Diagnostic::tag_last_suggestion`.
+    pub tag: Option<Cow<'static, str>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Hash, Encodable, Decodable)]
@@ -370,9 +380,22 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 
 impl error::Error for ExplicitBug {}
 
+/// The number of diagnostics a compilation emitted, for emitters to report at the very end.
+#[derive(Clone, Debug, Default)]
+pub struct CompilationSummary {
+    /// The number of errors, without duplicates.
+    pub errors: usize,
+    /// The number of warnings, without duplicates.
+    pub warnings: usize,
+    /// How many of the errors had each error code.
+    pub error_codes: BTreeMap<String, usize>,
+}
+
 pub use diagnostic::{
     AddSubdiagnostic, DecorateLint, Diagnostic, DiagnosticArg, DiagnosticArgFromDisplay,
-    DiagnosticArgValue, DiagnosticId, DiagnosticStyledString, IntoDiagnosticArg, SubDiagnostic,
+    DiagnosticArgValue, DiagnosticId, DiagnosticLocation, DiagnosticMetadata,
+    DiagnosticStyledString, EmittedErrorId, ImplCandidate, IntoDiagnosticArg, SubDiagnostic,
+    SubdiagnosticPriority,
 };
 pub use diagnostic_builder::{DiagnosticBuilder, EmissionGuarantee, LintDiagnosticBuilder};
 use std::backtrace::Backtrace;
@@ -419,6 +442,19 @@ struct HandlerInner {
     /// twice.
     emitted_diagnostics: FxHashSet<u128>,
 
+    /// This set contains a hash of the slug, primary span and arguments of every
+    /// diagnostic built from a `SessionDiagnostic` struct that has been emitted by
+    /// this handler. Two such diagnostics are duplicates of each other even if their
+    /// children differ trivially, which `emitted_diagnostics` doesn't catch.
+    emitted_struct_diagnostics: FxHashSet<u128>,
+
+    /// How often each explanatory note (keyed by a hash of its message) has been
+    /// emitted, to cap them at `HandlerFlags::max_explanatory_notes`.
+    explanatory_note_counts: FxHashMap<u128, usize>,
+
+    /// The primary span and code of every emitted error, indexed by `EmittedErrorId`.
+    emitted_errors: Vec<(Span, Option<DiagnosticId>)>,
+
     /// Stashed diagnostics emitted in one stage of the compiler that may be
     /// stolen by other stages (e.g. to improve them and add more information).
     /// The stashed diagnostics count towards the total error count.
@@ -428,6 +464,10 @@ struct HandlerInner {
     /// The warning count, used for a recap upon finishing
     deduplicated_warn_count: usize,
 
+    /// The summary of the diagnostics of this compilation, taken before the recap of
+    /// `print_error_count` adds diagnostics of its own.
+    compilation_summary: Option<CompilationSummary>,
+
     future_breakage_diagnostics: Vec<Diagnostic>,
 
     /// The [`Self::unstable_expect_diagnostics`] should be empty when this struct is
@@ -486,6 +526,27 @@ pub struct HandlerFlags {
     pub macro_backtrace: bool,
     /// If true, identical diagnostics are reported only once.
     pub deduplicate_diagnostics: bool,
+    /// The number of times each explanatory note is emitted, unlimited if `None`.
+    /// (rustc: see `-Z max-explanatory-notes`)
+    pub max_explanatory_notes: Option<usize>,
+    /// If true, the `.teach` attribute of a Fluent message is emitted as an extra note
+    /// the first time a diagnostic with its error code is emitted.
+    /// (rustc: see `-Z teach`)
+    pub teach: bool,
+    /// If true, a digest of each translatable diagnostic is emitted after it.
+    /// (rustc: see `-Z emit-diagnostic-digests`)
+    pub emit_diagnostic_digests: bool,
+    /// If true, suggestions are never attached to diagnostics.
+    /// (rustc: see `-Z no-suggestions`)
+    pub no_suggestions: bool,
+    /// If true, diagnostics note where in the compiler they were created and emitted, and which
+    /// diagnostic struct they were created from.
+    /// (rustc: see `-Z track-diagnostics`)
+    pub track_diagnostics: bool,
+    /// If true, the compiler is run by the test suite, which disables the checks that would
+    /// make the differences to the expected output harder to read.
+    /// (rustc: see `-Z ui-testing`)
+    pub ui_testing: bool,
 }
 
 impl Drop for HandlerInner {
@@ -581,6 +642,7 @@ pub fn with_emitter_and_flags(
                 warn_count: 0,
                 deduplicated_err_count: 0,
                 deduplicated_warn_count: 0,
+                compilation_summary: None,
                 emitter,
                 delayed_span_bugs: Vec::new(),
                 delayed_good_path_bugs: Vec::new(),
@@ -588,6 +650,9 @@ pub fn with_emitter_and_flags(
                 taught_diagnostics: Default::default(),
                 emitted_diagnostic_codes: Default::default(),
                 emitted_diagnostics: Default::default(),
+                emitted_struct_diagnostics: Default::default(),
+                explanatory_note_counts: Default::default(),
+                emitted_errors: Default::default(),
                 stashed_diagnostics: Default::default(),
                 future_breakage_diagnostics: Vec::new(),
                 check_unstable_expect_diagnostics: false,
@@ -614,6 +679,7 @@ pub fn reset_err_count(&self) {
         inner.warn_count = 0;
         inner.deduplicated_err_count = 0;
         inner.deduplicated_warn_count = 0;
+        inner.compilation_summary = None;
 
         // actually free the underlying memory (which `clear` would not do)
         inner.delayed_span_bugs = Default::default();
@@ -621,6 +687,9 @@ pub fn reset_err_count(&self) {
         inner.taught_diagnostics = Default::default();
         inner.emitted_diagnostic_codes = Default::default();
         inner.emitted_diagnostics = Default::default();
+        inner.emitted_struct_diagnostics = Default::default();
+        inner.explanatory_note_counts = Default::default();
+        inner.emitted_errors = Default::default();
         inner.stashed_diagnostics = Default::default();
     }
 
@@ -647,6 +716,7 @@ pub fn emit_stashed_diagnostics(&self) -> Option<ErrorGuaranteed> {
     /// * `can_emit_warnings` is `true`
     /// * `is_force_warn` was set in `DiagnosticId::Lint`
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_span_warn(
         &self,
         span: impl Into<MultiSpan>,
@@ -663,6 +733,7 @@ pub fn struct_span_warn(
     /// Attempting to `.emit()` the builder will only emit if either:
     /// * `can_emit_warnings` is `true`
     /// * `is_force_warn` was set in `DiagnosticId::Lint`
+    #[track_caller]
     pub fn struct_span_warn_with_expectation(
         &self,
         span: impl Into<MultiSpan>,
@@ -676,6 +747,7 @@ pub fn struct_span_warn_with_expectation(
 
     /// Construct a builder at the `Allow` level at the given `span` and with the `msg`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_span_allow(
         &self,
         span: impl Into<MultiSpan>,
@@ -689,6 +761,7 @@ pub fn struct_span_allow(
     /// Construct a builder at the `Warning` level at the given `span` and with the `msg`.
     /// Also include a code.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_span_warn_with_code(
         &self,
         span: impl Into<MultiSpan>,
@@ -706,6 +779,7 @@ pub fn struct_span_warn_with_code(
     /// * `can_emit_warnings` is `true`
     /// * `is_force_warn` was set in `DiagnosticId::Lint`
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_warn(&self, msg: impl Into<DiagnosticMessage>) -> DiagnosticBuilder<'_, ()> {
         DiagnosticBuilder::new(self, Level::Warning(None), msg)
     }
@@ -716,6 +790,7 @@ pub fn struct_warn(&self, msg: impl Into<DiagnosticMessage>) -> DiagnosticBuilde
     /// Attempting to `.emit()` the builder will only emit if either:
     /// * `can_emit_warnings` is `true`
     /// * `is_force_warn` was set in `DiagnosticId::Lint`
+    #[track_caller]
     pub fn struct_warn_with_expectation(
         &self,
         msg: impl Into<DiagnosticMessage>,
@@ -726,12 +801,14 @@ pub fn struct_warn_with_expectation(
 
     /// Construct a builder at the `Allow` level with the `msg`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_allow(&self, msg: impl Into<DiagnosticMessage>) -> DiagnosticBuilder<'_, ()> {
         DiagnosticBuilder::new(self, Level::Allow, msg)
     }
 
     /// Construct a builder at the `Expect` level with the `msg`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_expect(
         &self,
         msg: impl Into<DiagnosticMessage>,
@@ -742,6 +819,7 @@ pub fn struct_expect(
 
     /// Construct a builder at the `Error` level at the given `span` and with the `msg`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_span_err(
         &self,
         span: impl Into<MultiSpan>,
@@ -754,6 +832,7 @@ pub fn struct_span_err(
 
     /// Construct a builder at the `Error` level at the given `span`, with the `msg`, and `code`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_span_err_with_code(
         &self,
         span: impl Into<MultiSpan>,
@@ -768,6 +847,7 @@ pub fn struct_span_err_with_code(
     /// Construct a builder at the `Error` level with the `msg`.
     // FIXME: This method should be removed (every error should have an associated error code).
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_err(
         &self,
         msg: impl Into<DiagnosticMessage>,
@@ -777,12 +857,14 @@ pub fn struct_err(
 
     /// This should only be used by `rustc_middle::lint::struct_lint_level`. Do not use it for hard errors.
     #[doc(hidden)]
+    #[track_caller]
     pub fn struct_err_lint(&self, msg: impl Into<DiagnosticMessage>) -> DiagnosticBuilder<'_, ()> {
         DiagnosticBuilder::new(self, Level::Error { lint: true }, msg)
     }
 
     /// Construct a builder at the `Error` level with the `msg` and the `code`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_err_with_code(
         &self,
         msg: impl Into<DiagnosticMessage>,
@@ -795,6 +877,7 @@ pub fn struct_err_with_code(
 
     /// Construct a builder at the `Warn` level with the `msg` and the `code`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_warn_with_code(
         &self,
         msg: impl Into<DiagnosticMessage>,
@@ -807,6 +890,7 @@ pub fn struct_warn_with_code(
 
     /// Construct a builder at the `Fatal` level at the given `span` and with the `msg`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_span_fatal(
         &self,
         span: impl Into<MultiSpan>,
@@ -819,6 +903,7 @@ pub fn struct_span_fatal(
 
     /// Construct a builder at the `Fatal` level at the given `span`, with the `msg`, and `code`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_span_fatal_with_code(
         &self,
         span: impl Into<MultiSpan>,
@@ -832,18 +917,21 @@ pub fn struct_span_fatal_with_code(
 
     /// Construct a builder at the `Error` level with the `msg`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_fatal(&self, msg: impl Into<DiagnosticMessage>) -> DiagnosticBuilder<'_, !> {
         DiagnosticBuilder::new_fatal(self, msg)
     }
 
     /// Construct a builder at the `Help` level with the `msg`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_help(&self, msg: impl Into<DiagnosticMessage>) -> DiagnosticBuilder<'_, ()> {
         DiagnosticBuilder::new(self, Level::Help, msg)
     }
 
     /// Construct a builder at the `Note` level with the `msg`.
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_note_without_error(
         &self,
         msg: impl Into<DiagnosticMessage>,
@@ -852,12 +940,14 @@ pub fn struct_note_without_error(
     }
 
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn span_fatal(&self, span: impl Into<MultiSpan>, msg: impl Into<DiagnosticMessage>) -> ! {
         self.emit_diag_at_span(Diagnostic::new(Fatal, msg), span);
         FatalError.raise()
     }
 
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn span_fatal_with_code(
         &self,
         span: impl Into<MultiSpan>,
@@ -869,6 +959,7 @@ pub fn span_fatal_with_code(
     }
 
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn span_err(
         &self,
         span: impl Into<MultiSpan>,
@@ -878,6 +969,7 @@ pub fn span_err(
     }
 
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn span_err_with_code(
         &self,
         span: impl Into<MultiSpan>,
@@ -891,11 +983,13 @@ pub fn span_err_with_code(
     }
 
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn span_warn(&self, span: impl Into<MultiSpan>, msg: impl Into<DiagnosticMessage>) {
         self.emit_diag_at_span(Diagnostic::new(Warning(None), msg), span);
     }
 
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn span_warn_with_code(
         &self,
         span: impl Into<MultiSpan>,
@@ -928,6 +1022,7 @@ pub fn span_bug_no_panic(&self, span: impl Into<MultiSpan>, msg: impl Into<Diagn
         self.emit_diag_at_span(Diagnostic::new(Bug, msg), span);
     }
 
+    #[track_caller]
     pub fn span_note_without_error(
         &self,
         span: impl Into<MultiSpan>,
@@ -936,6 +1031,7 @@ pub fn span_note_without_error(
         self.emit_diag_at_span(Diagnostic::new(Note, msg), span);
     }
 
+    #[track_caller]
     pub fn span_note_diag(
         &self,
         span: Span,
@@ -947,19 +1043,23 @@ pub fn span_note_diag(
     }
 
     // NOTE: intentionally doesn't raise an error so rustc_codegen_ssa only reports fatal errors in the main thread
+    #[track_caller]
     pub fn fatal(&self, msg: impl Into<DiagnosticMessage>) -> FatalError {
         self.inner.borrow_mut().fatal(msg)
     }
 
+    #[track_caller]
     pub fn err(&self, msg: impl Into<DiagnosticMessage>) -> ErrorGuaranteed {
         self.inner.borrow_mut().err(msg)
     }
 
+    #[track_caller]
     pub fn warn(&self, msg: impl Into<DiagnosticMessage>) {
         let mut db = DiagnosticBuilder::new(self, Warning(None), msg);
         db.emit();
     }
 
+    #[track_caller]
     pub fn note_without_error(&self, msg: impl Into<DiagnosticMessage>) {
         DiagnosticBuilder::new(self, Note, msg).emit();
     }
@@ -973,6 +1073,11 @@ pub fn err_count(&self) -> usize {
         self.inner.borrow().err_count()
     }
 
+    /// Whether diagnostics note where in the compiler they were created and emitted.
+    pub fn track_diagnostics(&self) -> bool {
+        self.inner.borrow().flags.track_diagnostics
+    }
+
     pub fn has_errors(&self) -> Option<ErrorGuaranteed> {
         if self.inner.borrow().has_errors() { Some(ErrorGuaranteed(())) } else { None }
     }
@@ -991,6 +1096,12 @@ pub fn print_error_count(&self, registry: &Registry) {
         self.inner.borrow_mut().print_error_count(registry)
     }
 
+    /// Emits the summary of the diagnostics of this compilation. This is the last thing
+    /// emitted, so it must only be called once compilation has finished or aborted.
+    pub fn emit_compilation_summary(&self) {
+        self.inner.borrow_mut().emit_compilation_summary()
+    }
+
     pub fn take_future_breakage_diagnostics(&self) -> Vec<Diagnostic> {
         std::mem::take(&mut self.inner.borrow_mut().future_breakage_diagnostics)
     }
@@ -999,6 +1110,26 @@ pub fn abort_if_errors(&self) {
         self.inner.borrow_mut().abort_if_errors()
     }
 
+    /// Translates `message` with `args` right away, for messages which are added to a diagnostic
+    /// more than once with different values of the same argument.
+    pub fn eagerly_translate_to_string<'a>(
+        &self,
+        message: DiagnosticMessage,
+        args: impl Iterator<Item = DiagnosticArg<'a>>,
+    ) -> String {
+        let inner = self.inner.borrow();
+        let args = FromIterator::from_iter(args);
+        inner.emitter.translate_message(&message, &args).into_owned()
+    }
+
+    /// Returns the last error emitted by this handler, with its primary span and code.
+    pub fn last_emitted_error(&self) -> Option<(EmittedErrorId, Span, Option<DiagnosticId>)> {
+        let inner = self.inner.borrow();
+        let index = inner.emitted_errors.len().checked_sub(1)?;
+        let (span, code) = inner.emitted_errors[index].clone();
+        Some((EmittedErrorId(index), span, code))
+    }
+
     /// `true` if we haven't taught a diagnostic with this code already.
     /// The caller must then teach the user about such a diagnostic.
     ///
@@ -1132,6 +1263,8 @@ fn emit_stashed_diagnostics(&mut self) -> Option<ErrorGuaranteed> {
 
     // FIXME(eddyb) this should ideally take `diagnostic` by value.
     fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaranteed> {
+        diagnostic.resolve_labels_or_notes();
+
         // The `LintExpectationId` can be stable or unstable depending on when it was created.
         // Diagnostics created before the definition of `HirId`s are unstable and can not yet
         // be stored. Instead, they are buffered until the `LintExpectationId` is replaced by
@@ -1178,6 +1311,17 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
             return None;
         }
 
+        if self.flags.no_suggestions {
+            // `DiagnosticBuilder`s of this handler already drop suggestions as they are added,
+            // so a suggestion here was attached to a diagnostic built some other way.
+            debug_assert!(
+                diagnostic.suggestions.as_ref().map_or(true, |suggestions| suggestions.is_empty()),
+                "suggestion attached to a diagnostic with `-Z no-suggestions`: {:?}",
+                diagnostic,
+            );
+            diagnostic.disable_suggestions();
+        }
+
         if let Some(ref code) = diagnostic.code {
             self.emitted_diagnostic_codes.insert(code.clone());
         }
@@ -1189,27 +1333,110 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
             !this.emitted_diagnostics.insert(diagnostic_hash)
         };
 
+        // Diagnostics from the same struct with the same primary span and arguments are
+        // emitted twice only by mistake (e.g. a retry path reporting the same error again).
+        let struct_already_emitted = |this: &mut Self| {
+            let Some(identity) = diagnostic.struct_identity() else { return false };
+            let mut hasher = StableHasher::new();
+            identity.hash(&mut hasher);
+            let identity_hash = hasher.finish();
+            !this.emitted_struct_diagnostics.insert(identity_hash)
+        };
+        let is_struct_duplicate = !diagnostic.no_dedup && struct_already_emitted(self);
+        // compiletest disables deduplication for every test, including the ones emitting the
+        // same error twice on purpose, so they are left alone.
+        if is_struct_duplicate
+            && !self.flags.deduplicate_diagnostics
+            && !self.flags.ui_testing
+            && cfg!(debug_assertions)
+        {
+            self.delay_span_bug(
+                diagnostic.span.clone(),
+                "the same struct diagnostic was emitted twice with identical arguments",
+            );
+        }
+
         // Only emit the diagnostic if we've been asked to deduplicate or
         // haven't already emitted an equivalent diagnostic.
-        if !(self.flags.deduplicate_diagnostics && already_emitted(self)) {
+        if !(self.flags.deduplicate_diagnostics
+            && !diagnostic.no_dedup
+            && (is_struct_duplicate || already_emitted(self)))
+        {
             debug!(?diagnostic);
             debug!(?self.emitted_diagnostics);
             let already_emitted_sub = |sub: &mut SubDiagnostic| {
                 debug!(?sub);
-                if sub.level != Level::OnceNote {
-                    return false;
+                match sub.level {
+                    Level::OnceNote => {
+                        let mut hasher = StableHasher::new();
+                        sub.hash(&mut hasher);
+                        let diagnostic_hash = hasher.finish();
+                        debug!(?diagnostic_hash);
+                        !self.emitted_diagnostics.insert(diagnostic_hash)
+                    }
+                    Level::ExplanatoryNote => {
+                        let Some(max) = self.flags.max_explanatory_notes else { return false };
+                        // Only the message is hashed, so the note counts as the same one
+                        // regardless of where it points to.
+                        let mut hasher = StableHasher::new();
+                        sub.message.hash(&mut hasher);
+                        let count =
+                            self.explanatory_note_counts.entry(hasher.finish()).or_default();
+                        *count += 1;
+                        *count > max
+                    }
+                    _ => false,
                 }
-                let mut hasher = StableHasher::new();
-                sub.hash(&mut hasher);
-                let diagnostic_hash = hasher.finish();
-                debug!(?diagnostic_hash);
-                !self.emitted_diagnostics.insert(diagnostic_hash)
             };
 
             diagnostic.children.drain_filter(already_emitted_sub).for_each(|_| {});
 
+            if self.flags.teach && diagnostic.has_teach_attr() {
+                if diagnostic.code.as_ref().map_or(false, |code| self.must_teach(code)) {
+                    diagnostic.note(SubdiagnosticMessage::FluentAttr(Cow::Borrowed("teach")));
+                }
+            }
+
+            let related_spans: Vec<_> = diagnostic
+                .related
+                .iter()
+                .filter_map(|&EmittedErrorId(index)| self.emitted_errors.get(index))
+                .filter(|(span, _)| !span.is_dummy())
+                .map(|&(span, _)| span)
+                .collect();
+            for span in related_spans {
+                diagnostic.span_note(span, fluent::errors::related_error_note);
+            }
+
+            if self.flags.track_diagnostics {
+                let created_at = diagnostic.created_at.to_string();
+                diagnostic.note(format!("-Ztrack-diagnostics: created at {}", created_at));
+                let tracked: Vec<_> = diagnostic
+                    .tracked_subdiagnostics()
+                    .iter()
+                    .map(|(name, added_at)| {
+                        format!("-Ztrack-diagnostics: `{}` added at {}", name, added_at)
+                    })
+                    .collect();
+                for note in tracked {
+                    diagnostic.note(note);
+                }
+                if let Some(emitted_at) = diagnostic.emitted_at.as_ref().map(ToString::to_string) {
+                    diagnostic.note(format!("-Ztrack-diagnostics: emitted at {}", emitted_at));
+                }
+            }
+
             self.emitter.emit_diagnostic(&diagnostic);
+            if self.flags.emit_diagnostic_digests {
+                if let Some(digest) = diagnostic.digest() {
+                    self.emitter.emit_diagnostic_digest(&digest);
+                }
+            }
             if diagnostic.is_error() {
+                self.emitted_errors.push((
+                    diagnostic.span.primary_span().unwrap_or(DUMMY_SP),
+                    diagnostic.code.clone(),
+                ));
                 self.deduplicated_err_count += 1;
             } else if let Warning(_) = diagnostic.level {
                 self.deduplicated_warn_count += 1;
@@ -1246,6 +1473,7 @@ fn treat_err_as_bug(&self) -> bool {
 
     fn print_error_count(&mut self, registry: &Registry) {
         self.emit_stashed_diagnostics();
+        self.compilation_summary = Some(self.summarize_compilation());
 
         let warnings = match self.deduplicated_warn_count {
             0 => String::new(),
@@ -1315,6 +1543,26 @@ fn print_error_count(&mut self, registry: &Registry) {
         }
     }
 
+    fn summarize_compilation(&self) -> CompilationSummary {
+        let mut error_codes = BTreeMap::new();
+        for (_, code) in &self.emitted_errors {
+            if let Some(DiagnosticId::Error(code)) = code {
+                *error_codes.entry(code.clone()).or_insert(0) += 1;
+            }
+        }
+        CompilationSummary {
+            errors: self.deduplicated_err_count,
+            warnings: self.deduplicated_warn_count,
+            error_codes,
+        }
+    }
+
+    fn emit_compilation_summary(&mut self) {
+        let summary =
+            self.compilation_summary.take().unwrap_or_else(|| self.summarize_compilation());
+        self.emitter.emit_compilation_summary(&summary);
+    }
+
     fn stash(&mut self, key: (Span, StashKey), diagnostic: Diagnostic) {
         // Track the diagnostic for counts, but don't panic-if-treat-err-as-bug
         // yet; that happens when we actually emit the diagnostic.
@@ -1422,16 +1670,19 @@ fn failure(&mut self, msg: impl Into<DiagnosticMessage>) {
         self.emit_diagnostic(&mut Diagnostic::new(FailureNote, msg));
     }
 
+    #[track_caller]
     fn fatal(&mut self, msg: impl Into<DiagnosticMessage>) -> FatalError {
         self.emit(Fatal, msg);
         FatalError
     }
 
+    #[track_caller]
     fn err(&mut self, msg: impl Into<DiagnosticMessage>) -> ErrorGuaranteed {
         self.emit(Error { lint: false }, msg)
     }
 
     /// Emit an error; level should be `Error` or `Fatal`.
+    #[track_caller]
     fn emit(&mut self, level: Level, msg: impl Into<DiagnosticMessage>) -> ErrorGuaranteed {
         if self.treat_err_as_bug() {
             self.bug(msg);
@@ -1542,6 +1793,9 @@ pub enum Level {
     Note,
     /// A note that is only emitted once.
     OnceNote,
+    /// A note explaining a general rule behind an error, which is emitted at most
+    /// `HandlerFlags::max_explanatory_notes` times.
+    ExplanatoryNote,
     Help,
     FailureNote,
     Allow,
@@ -1564,7 +1818,7 @@ fn color(self) -> ColorSpec {
             Warning(_) => {
                 spec.set_fg(Some(Color::Yellow)).set_intense(cfg!(windows));
             }
-            Note | OnceNote => {
+            Note | OnceNote | ExplanatoryNote => {
                 spec.set_fg(Some(Color::Green)).set_intense(true);
             }
             Help => {
@@ -1581,7 +1835,7 @@ pub fn to_str(self) -> &'static str {
             Bug | DelayedBug => "error: internal compiler error",
             Fatal | Error { .. } => "error",
             Warning(_) => "warning",
-            Note | OnceNote => "note",
+            Note | OnceNote | ExplanatoryNote => "note",
             Help => "help",
             FailureNote => "failure-note",
             Allow => panic!("Shouldn't call on allowed error"),
@@ -1626,6 +1880,48 @@ pub fn add_elided_lifetime_in_path_suggestion(
     );
 }
 
+/// Joins the `lines` of the code of a suggestion at `span` with newlines, indenting every line
+/// after the first one like the line `span` starts on.
+///
+/// This is what `code("...", "...")` in `#[suggestion(..)]` attributes expands to, and can be
+/// used by manual implementations for suggestions that insert multiple lines of code.
+pub fn multiline_suggestion_code(span: Span, lines: &[String]) -> String {
+    let indentation = rustc_span::indentation_before(span).unwrap_or_default();
+    lines.join(&format!("\n{}", indentation))
+}
+
+/// Normalizes the parts of a multipart suggestion built from independent pieces: drops parts
+/// which repeat an earlier one, and merges parts whose spans touch into one part, keeping the
+/// order of insertions at the same position.
+///
+/// Tools applying suggestions like rustfix reject parts replacing adjacent or identical ranges
+/// as overlapping, even if applying them one after the other would be fine.
+pub fn merge_touching_suggestion_parts(parts: Vec<(Span, String)>) -> Vec<(Span, String)> {
+    let mut unique: Vec<(Span, String)> = Vec::with_capacity(parts.len());
+    for part in parts {
+        if !unique.contains(&part) {
+            unique.push(part);
+        }
+    }
+    // Stable, so insertions at the same position stay in order, and empty spans sort before
+    // the replacements starting where they are.
+    unique.sort_by_key(|(span, _)| (span.lo(), span.hi()));
+
+    let mut merged: Vec<(Span, String)> = Vec::with_capacity(unique.len());
+    for (span, snippet) in unique {
+        match merged.last_mut() {
+            Some((last_span, last_snippet))
+                if last_span.hi() == span.lo() && last_span.ctxt() == span.ctxt() =>
+            {
+                *last_span = last_span.to(span);
+                last_snippet.push_str(&snippet);
+            }
+            _ => merged.push((span, snippet)),
+        }
+    }
+    merged
+}
+
 /// Useful type to use with `Result<>` indicate that an error has already
 /// been reported to the user, so no need to continue checking.
 #[derive(Clone, Copy, Debug, Encodable, Decodable, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -0,0 +1,125 @@
+use super::*;
+use crate::translation::Translate;
+use rustc_span::BytePos;
+
+/// Keeps every diagnostic which reaches it.
+struct RecordingEmitter {
+    emitted: Lrc<Lock<Vec<Diagnostic>>>,
+}
+
+impl Translate for RecordingEmitter {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        None
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        panic!("recording emitter attempted to translate message")
+    }
+}
+
+impl Emitter for RecordingEmitter {
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        None
+    }
+
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        self.emitted.lock().push(diag.clone());
+    }
+}
+
+#[test]
+fn no_dedup_diagnostic_is_emitted_again() {
+    rustc_span::create_default_session_globals_then(|| {
+        let emitted = Lrc::new(Lock::new(vec![]));
+        let handler = Handler::with_emitter_and_flags(
+            Box::new(RecordingEmitter { emitted: emitted.clone() }),
+            HandlerFlags { deduplicate_diagnostics: true, ..Default::default() },
+        );
+        let span = Span::with_root_ctxt(BytePos(0), BytePos(4));
+        for _ in 0..2 {
+            handler
+                .struct_span_err_with_code(
+                    span,
+                    "type annotations needed",
+                    DiagnosticId::Error("E0282".to_owned()),
+                )
+                .emit();
+        }
+        // Summaries of different functions can have the same text.
+        for _ in 0..2 {
+            handler
+                .struct_err("2 more type annotation errors in this function were not shown")
+                .set_no_dedup()
+                .emit();
+        }
+
+        let emitted = emitted.lock();
+        let summaries = emitted.iter().filter(|diag| diag.no_dedup).count();
+        assert_eq!(summaries, 2);
+        assert_eq!(emitted.len() - summaries, 1);
+    })
+}
+
+#[test]
+fn touching_suggestion_parts_are_merged() {
+    rustc_span::create_default_session_globals_then(|| {
+        let span = |lo, hi| Span::with_root_ctxt(BytePos(lo), BytePos(hi));
+        let parts = vec![
+            (span(4, 5), "&'a ".to_owned()),
+            (span(2, 2), "<'a>".to_owned()),
+            (span(4, 4), "/* first */".to_owned()),
+            (span(8, 8), "'a ".to_owned()),
+            (span(2, 2), "<'a>".to_owned()),
+            (span(5, 6), "T".to_owned()),
+        ];
+        assert_eq!(
+            merge_touching_suggestion_parts(parts),
+            [
+                (span(2, 2), "<'a>".to_owned()),
+                (span(4, 6), "/* first */&'a T".to_owned()),
+                (span(8, 8), "'a ".to_owned()),
+            ]
+        );
+    })
+}
+
+#[test]
+fn struct_diagnostic_is_deduplicated_by_level_and_code() {
+    rustc_span::create_default_session_globals_then(|| {
+        let emitted = Lrc::new(Lock::new(vec![]));
+        let handler = Handler::with_emitter_and_flags(
+            Box::new(RecordingEmitter { emitted: emitted.clone() }),
+            HandlerFlags { deduplicate_diagnostics: true, ..Default::default() },
+        );
+        let span = Span::with_root_ctxt(BytePos(0), BytePos(4));
+        let emit = |level, code: Option<&str>, note| {
+            let message = DiagnosticMessage::FluentIdentifier("test_slug".into(), None);
+            let mut diag = Diagnostic::new_with_code(
+                level,
+                code.map(|code| DiagnosticId::Error(code.to_owned())),
+                message,
+            );
+            diag.set_span(span);
+            diag.set_arg("name", "x");
+            diag.note(note);
+            handler.emit_diagnostic(&mut diag);
+        };
+        // The notes differ, but the slug, span and arguments are the same.
+        emit(Level::Error { lint: false }, Some("E0623"), "first note");
+        emit(Level::Error { lint: false }, Some("E0623"), "second note");
+        // Not duplicates of the error above.
+        emit(Level::Warning(None), Some("E0623"), "first note");
+        emit(Level::Error { lint: false }, Some("E0495"), "first note");
+
+        let emitted = emitted.lock();
+        let levels: Vec<_> = emitted.iter().map(|diag| (diag.level, diag.code.clone())).collect();
+        assert_eq!(
+            levels,
+            [
+                (Level::Error { lint: false }, Some(DiagnosticId::Error("E0623".to_owned()))),
+                (Level::Warning(None), Some(DiagnosticId::Error("E0623".to_owned()))),
+                (Level::Error { lint: false }, Some(DiagnosticId::Error("E0495".to_owned()))),
+            ]
+        );
+    })
+}
@@ -24,11 +24,12 @@
     AttemptLocalParseRecovery, CommaRecoveryMode, ForceCollect, Parser, RecoverColon, RecoverComma,
 };
 use rustc_parse::validate_attr;
+use rustc_session::cstore::CfgStrippedImpl;
 use rustc_session::lint::builtin::{UNUSED_ATTRIBUTES, UNUSED_DOC_COMMENTS};
 use rustc_session::lint::BuiltinLintDiagnostics;
 use rustc_session::parse::{feature_err, ParseSess};
 use rustc_session::Limit;
-use rustc_span::symbol::{sym, Ident};
+use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::{FileName, LocalExpnId, Span};
 
 use smallvec::SmallVec;
@@ -1021,6 +1022,11 @@ fn take_mac_call(self) -> (P<ast::MacCall>, Self::AttrsTy, AddSemicolon) {
         unreachable!()
     }
     fn pre_flat_map_node_collect_attr(_cfg: &StripUnconfigured<'_>, _attr: &ast::Attribute) {}
+    /// The record to leave of this node if it is a trait impl which `cfg` removes because of a
+    /// feature.
+    fn cfg_stripped_impl(&self, _cfg: &ast::Attribute) -> Option<CfgStrippedImpl> {
+        None
+    }
     fn post_flat_map_node_collect_bang(_output: &mut Self::OutputTy, _add_semicolon: AddSemicolon) {
     }
     fn wrap_flat_map_node_noop_flat_map(
@@ -1032,6 +1038,18 @@ fn wrap_flat_map_node_noop_flat_map(
     }
 }
 
+/// `path` as recorded in a `CfgStrippedImpl`: the names of its segments, without the keywords
+/// like `crate` it starts with.
+fn written_path(path: &ast::Path) -> Symbol {
+    let segments: Vec<_> = path
+        .segments
+        .iter()
+        .skip_while(|segment| segment.ident.is_path_segment_keyword())
+        .map(|segment| segment.ident.as_str())
+        .collect();
+    Symbol::intern(&segments.join("::"))
+}
+
 impl InvocationCollectorNode for P<ast::Item> {
     const KIND: AstFragmentKind = AstFragmentKind::Items;
     fn to_annotatable(self) -> Annotatable {
@@ -1053,6 +1071,28 @@ fn take_mac_call(self) -> (P<ast::MacCall>, Self::AttrsTy, AddSemicolon) {
             _ => unreachable!(),
         }
     }
+    fn cfg_stripped_impl(&self, cfg: &ast::Attribute) -> Option<CfgStrippedImpl> {
+        let ItemKind::Impl(impl_) = &self.kind else {
+            return None;
+        };
+        let TyKind::Path(None, self_ty) = &impl_.self_ty.kind else {
+            return None;
+        };
+        // Only `#[cfg(feature = "..")]` itself, anything more involved can't be fixed by enabling
+        // a single feature.
+        let predicates = cfg.meta_item_list()?;
+        let [NestedMetaItem::MetaItem(predicate)] = &predicates[..] else {
+            return None;
+        };
+        if !predicate.has_name(sym::feature) {
+            return None;
+        }
+        Some(CfgStrippedImpl {
+            trait_path: written_path(&impl_.of_trait.as_ref()?.path),
+            self_ty_path: written_path(self_ty),
+            feature: predicate.value_str()?,
+        })
+    }
     fn wrap_flat_map_node_noop_flat_map(
         mut node: Self,
         collector: &mut InvocationCollector<'_, '_>,
@@ -1658,9 +1698,18 @@ fn flat_map_node<Node: InvocationCollectorNode<OutputTy: Default>>(
             return match self.take_first_attr(&mut node) {
                 Some((attr, pos, derives)) => match attr.name_or_empty() {
                     sym::cfg => {
+                        let stripped_impl =
+                            if self.cx.sess.opts.unstable_opts.metadata_include_cfg_impls {
+                                node.cfg_stripped_impl(&attr)
+                            } else {
+                                None
+                            };
                         if self.expand_cfg_true(&mut node, attr, pos) {
                             continue;
                         }
+                        if let Some(stripped_impl) = stripped_impl {
+                            self.cx.sess.parse_sess.cfg_stripped_impls.lock().push(stripped_impl);
+                        }
                         Default::default()
                     }
                     sym::cfg_attr => {
@@ -8,7 +8,7 @@
 
 use rustc_data_structures::sync::Lrc;
 use rustc_errors::emitter::EmitterWriter;
-use rustc_errors::{Handler, MultiSpan, PResult};
+use rustc_errors::{Diagnostic, Handler, Level, PResult};
 
 use std::io;
 use std::io::prelude::*;
@@ -124,6 +124,40 @@ fn flush(&mut self) -> io::Result<()> {
 }
 
 fn test_harness(file_text: &str, span_labels: Vec<SpanLabel>, expected_output: &str) {
+    check_rendering(file_text, &span_labels, expected_output, |diag, labels| {
+        for (span, label) in labels {
+            diag.span_label(span, label);
+        }
+    })
+}
+
+/// Like `test_harness`, but adds the labels with `Diagnostic::span_labels_from_iter`, both in the
+/// given and in the reverse order, which must not make a difference.
+fn resolved_labels_test_harness(
+    file_text: &str,
+    span_labels: Vec<SpanLabel>,
+    expected_output: &str,
+) {
+    for reverse in [false, true] {
+        check_rendering(file_text, &span_labels, expected_output, |diag, mut labels| {
+            if reverse {
+                labels.reverse();
+            }
+            diag.span_labels_from_iter(
+                labels.into_iter().map(|(span, label)| (span, label.into())),
+            );
+        })
+    }
+}
+
+/// Checks how an error at the first of `span_labels` is rendered, once `add_labels` adds the
+/// labels to it.
+fn check_rendering(
+    file_text: &str,
+    span_labels: &[SpanLabel],
+    expected_output: &str,
+    add_labels: impl FnOnce(&mut Diagnostic, Vec<(Span, &'static str)>),
+) {
     create_default_session_if_not_set_then(|_| {
         let output = Arc::new(Mutex::new(Vec::new()));
 
@@ -133,13 +167,16 @@ fn test_harness(file_text: &str, span_labels: Vec<SpanLabel>, expected_output: &
         source_map.new_source_file(Path::new("test.rs").to_owned().into(), file_text.to_owned());
 
         let primary_span = make_span(&file_text, &span_labels[0].start, &span_labels[0].end);
-        let mut msp = MultiSpan::from_span(primary_span);
+        let mut labels = vec![];
         for span_label in span_labels {
             let span = make_span(&file_text, &span_label.start, &span_label.end);
-            msp.push_span_label(span, span_label.label);
+            labels.push((span, span_label.label));
             println!("span: {:?} label: {:?}", span, span_label.label);
             println!("text: {:?}", source_map.span_to_snippet(span));
         }
+        let mut diag = Diagnostic::new(Level::Error { lint: false }, "foo");
+        diag.set_span(primary_span);
+        add_labels(&mut diag, labels);
 
         let emitter = EmitterWriter::new(
             Box::new(Shared { data: output.clone() }),
@@ -153,7 +190,7 @@ fn test_harness(file_text: &str, span_labels: Vec<SpanLabel>, expected_output: &
             false,
         );
         let handler = Handler::with_emitter(true, None, Box::new(emitter));
-        handler.span_err(msp, "foo");
+        handler.emit_diagnostic(&mut diag);
 
         assert!(
             expected_output.chars().next() == Some('\n'),
@@ -1014,3 +1051,86 @@ fn foo() {
 "#,
     );
 }
+
+#[test]
+fn nested_resolved_labels() {
+    resolved_labels_test_harness(
+        r#"
+fn foo() {
+    let x = a + b;
+}
+"#,
+        vec![
+            SpanLabel {
+                start: Position { string: "x", count: 1 },
+                end: Position { string: "x", count: 1 },
+                label: "binding",
+            },
+            SpanLabel {
+                start: Position { string: "let", count: 1 },
+                end: Position { string: ";", count: 1 },
+                label: "statement",
+            },
+            SpanLabel {
+                start: Position { string: "a", count: 1 },
+                end: Position { string: "b", count: 1 },
+                label: "initializer",
+            },
+        ],
+        r#"
+error: foo
+ --> test.rs:3:9
+  |
+3 |     let x = a + b;
+  |         ^   ----- initializer
+  |         |
+  |         binding
+  |
+note: statement
+ --> test.rs:3:5
+  |
+3 |     let x = a + b;
+  |     ^^^^^^^^^^^^^^
+
+"#,
+    );
+}
+
+#[test]
+fn identical_resolved_labels() {
+    resolved_labels_test_harness(
+        r#"
+fn foo() {
+    let x = a + b;
+}
+"#,
+        vec![
+            SpanLabel {
+                start: Position { string: "x", count: 1 },
+                end: Position { string: "x", count: 1 },
+                label: "second",
+            },
+            SpanLabel {
+                start: Position { string: "x", count: 1 },
+                end: Position { string: "x", count: 1 },
+                label: "first",
+            },
+            SpanLabel {
+                start: Position { string: "x", count: 1 },
+                end: Position { string: "x", count: 1 },
+                label: "second",
+            },
+        ],
+        r#"
+error: foo
+ --> test.rs:3:9
+  |
+3 |     let x = a + b;
+  |         ^
+  |         |
+  |         first
+  |         second
+
+"#,
+    );
+}
@@ -689,6 +689,17 @@ pub fn span_for_bound_removal(&self, predicate_pos: usize, bound_pos: usize) ->
             bounds[bound_pos - 1].span().shrink_to_hi().to(span)
         }
     }
+
+    /// Returns the span to remove in order to drop the where-clause bound containing `span`,
+    /// taking the whole predicate (or where-clause) with it if that bound is the only one.
+    pub fn span_for_where_clause_bound_removal(&self, span: Span) -> Option<Span> {
+        let (predicate_pos, predicate) =
+            self.predicates.iter().enumerate().find(|(_, predicate)| {
+                predicate.in_where_clause() && predicate.span().contains(span)
+            })?;
+        let bound_pos = predicate.bounds().iter().position(|bound| bound.span().contains(span))?;
+        Some(self.span_for_bound_removal(predicate_pos, bound_pos))
+    }
 }
 
 /// A single predicate in a where-clause.
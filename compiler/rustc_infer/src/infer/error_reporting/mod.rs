@@ -58,7 +58,7 @@
 };
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
-use rustc_errors::{pluralize, struct_span_err, Diagnostic, ErrorGuaranteed};
+use rustc_errors::{fluent, pluralize, struct_span_err, Diagnostic, ErrorGuaranteed};
 use rustc_errors::{Applicability, DiagnosticBuilder, DiagnosticStyledString, MultiSpan};
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LocalDefId};
@@ -79,10 +79,16 @@
 mod note;
 
 mod need_type_info;
-pub use need_type_info::TypeAnnotationNeeded;
+pub use need_type_info::{CannotSatisfyPredicate, InferenceFailure, TypeAnnotationNeeded};
+pub(crate) use need_type_info::InferenceErrorBudget;
 
 pub mod nice_region_error;
 
+pub mod span_attribution;
+
+#[cfg(test)]
+mod tests;
+
 pub(super) fn note_and_explain_region<'tcx>(
     tcx: TyCtxt<'tcx>,
     err: &mut Diagnostic,
@@ -201,6 +207,7 @@ fn msg_span_from_early_bound_and_free_regions<'tcx>(
     }
 }
 
+#[allow(rustc::untranslatable_diagnostic)]
 fn emit_msg_span(
     err: &mut Diagnostic,
     prefix: &str,
@@ -217,6 +224,7 @@ fn emit_msg_span(
     }
 }
 
+#[allow(rustc::untranslatable_diagnostic)]
 fn label_msg_span(
     err: &mut Diagnostic,
     prefix: &str,
@@ -233,6 +241,7 @@ fn label_msg_span(
     }
 }
 
+#[allow(rustc::untranslatable_diagnostic)]
 pub fn unexpected_hidden_region_diagnostic<'tcx>(
     tcx: TyCtxt<'tcx>,
     span: Span,
@@ -285,7 +294,6 @@ pub fn unexpected_hidden_region_diagnostic<'tcx>(
                     hidden_region.to_string(),
                     None,
                     format!("captures `{}`", hidden_region),
-                    None,
                 )
             }
         }
@@ -333,9 +341,41 @@ pub fn report_region_errors(
 
         debug!("report_region_errors: {} errors after preprocessing", errors.len());
 
-        for error in errors {
+        // Two errors requiring opposite relations between the same pair of regions, e.g.
+        // from the two branches of an `if`, are reported together.
+        let mut mirrors = FxHashMap::default();
+        for (i, error) in errors.iter().enumerate() {
+            let RegionResolutionError::ConcreteFailure(_, sub, sup) = error else { continue };
+            if sub == sup || mirrors.values().any(|&(j, _)| j == i) {
+                continue;
+            }
+            let mirror = errors.iter().enumerate().skip(i + 1).find_map(|(j, other)| match other {
+                RegionResolutionError::ConcreteFailure(origin, other_sub, other_sup)
+                    if other_sub == sup && other_sup == sub =>
+                {
+                    Some((j, origin.span()))
+                }
+                _ => None,
+            });
+            if let Some(mirror) = mirror {
+                mirrors.insert(i, mirror);
+            }
+        }
+        let mut merged = FxHashSet::default();
+
+        for (i, error) in errors.into_iter().enumerate() {
             debug!("report_region_errors: error = {:?}", error);
 
+            if merged.contains(&i) {
+                continue;
+            }
+            if let Some(&(j, mirror_span)) = mirrors.get(&i)
+                && self.try_report_mirrored_region_errors(&error, mirror_span)
+            {
+                merged.insert(j);
+                continue;
+            }
+
             if !self.try_report_nice_region_error(&error) {
                 match error.clone() {
                     // These errors could indicate all manner of different
@@ -458,6 +498,7 @@ fn process_errors(
     }
 
     /// Adds a note if the types come from similarly named crates
+    #[allow(rustc::untranslatable_diagnostic)]
     fn check_and_note_conflicting_crates(&self, err: &mut Diagnostic, terr: TypeError<'tcx>) {
         use hir::def_id::CrateNum;
         use rustc_hir::definitions::DisambiguatedDefPathData;
@@ -579,6 +620,7 @@ fn path_generic_args(
         }
     }
 
+    #[allow(rustc::untranslatable_diagnostic)]
     fn note_error_origin(
         &self,
         err: &mut Diagnostic,
@@ -1427,6 +1469,7 @@ fn lifetime_display(lifetime: Region<'_>) -> String {
         level = "debug",
         skip(self, diag, secondary_span, swap_secondary_and_primary, prefer_label)
     )]
+    #[allow(rustc::untranslatable_diagnostic)]
     pub fn note_type_err(
         &self,
         diag: &mut Diagnostic,
@@ -1879,6 +1922,7 @@ pub fn get_impl_future_output_ty(&self, ty: Ty<'tcx>) -> Option<Binder<'tcx, Ty<
     /// This routine checks if the found type `T` implements `Future<Output=U>` where `U` is the
     /// expected type. If this is the case, and we are inside of an async body, it suggests adding
     /// `.await` to the tail of the expression.
+    #[allow(rustc::untranslatable_diagnostic)]
     fn suggest_await_on_expect_found(
         &self,
         cause: &ObligationCause<'tcx>,
@@ -2091,6 +2135,7 @@ fn suggest_as_ref_where_appropriate(
         }
     }
 
+    #[allow(rustc::untranslatable_diagnostic)]
     pub fn report_and_explain_type_error(
         &self,
         trace: TypeTrace<'tcx>,
@@ -2294,6 +2339,7 @@ pub fn report_generic_bound_failure(
             .emit();
     }
 
+    #[allow(rustc::untranslatable_diagnostic)]
     pub fn construct_generic_bound_failure(
         &self,
         generic_param_scope: LocalDefId,
@@ -2619,10 +2665,8 @@ fn report_sub_sup_conflict(
                         "...",
                         None,
                     );
-                    err.span_note(
-                        sup_trace.cause.span,
-                        &format!("...so that the {}", sup_trace.cause.as_requirement_str()),
-                    );
+                    let msg = self.requirement_msg(fluent::infer::subtype, sup_trace);
+                    err.label_or_note(sup_trace.cause.span, msg);
 
                     err.note_expected_found(&"", sup_expected, &"", sup_found);
                     err.emit();
@@ -2751,6 +2795,7 @@ fn consts(
 }
 
 impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
+    #[allow(rustc::untranslatable_diagnostic)]
     fn report_inference_failure(
         &self,
         var_origin: RegionVariableOrigin,
@@ -2810,7 +2855,7 @@ pub enum FailureCode {
 
 pub trait ObligationCauseExt<'tcx> {
     fn as_failure_code(&self, terr: TypeError<'tcx>) -> FailureCode;
-    fn as_requirement_str(&self) -> &'static str;
+    fn as_requirement_kind(&self) -> &'static str;
 }
 
 impl<'tcx> ObligationCauseExt<'tcx> for ObligationCause<'tcx> {
@@ -2857,30 +2902,85 @@ fn as_failure_code(&self, terr: TypeError<'tcx>) -> FailureCode {
         }
     }
 
-    fn as_requirement_str(&self) -> &'static str {
+    fn as_requirement_kind(&self) -> &'static str {
         use crate::traits::ObligationCauseCode::*;
+        // This match is intentionally exhaustive, so that new cause codes have to decide
+        // whether they deserve their own wording in `infer_requirement`.
         match self.code() {
-            CompareImplItemObligation { kind: ty::AssocKind::Fn, .. } => {
-                "method type is compatible with trait"
-            }
-            CompareImplItemObligation { kind: ty::AssocKind::Type, .. } => {
-                "associated type is compatible with trait"
-            }
-            CompareImplItemObligation { kind: ty::AssocKind::Const, .. } => {
-                "const is compatible with trait"
-            }
-            ExprAssignable => "expression is assignable",
-            IfExpression { .. } => "`if` and `else` have incompatible types",
-            IfExpressionWithNoElse => "`if` missing an `else` returns `()`",
-            MainFunctionType => "`main` function has the correct type",
-            StartFunctionType => "`#[start]` function has the correct type",
-            IntrinsicType => "intrinsic has the correct type",
-            MethodReceiver => "method receiver has the correct type",
-            _ => "types are compatible",
+            CompareImplItemObligation { kind: ty::AssocKind::Fn, .. } => "method_compat",
+            CompareImplItemObligation { kind: ty::AssocKind::Type, .. } => "type_compat",
+            CompareImplItemObligation { kind: ty::AssocKind::Const, .. } => "const_compat",
+            ExprAssignable => "expr_assignable",
+            IfExpression { .. } => "if_else_different",
+            IfExpressionWithNoElse => "no_else",
+            MainFunctionType => "fn_main_correct_type",
+            StartFunctionType => "fn_start_correct_type",
+            IntrinsicType => "intrinsic_correct_type",
+            MethodReceiver => "method_correct_type",
+            ReturnType | ReturnValue(_) | ReturnNoExpression | OpaqueReturnType(_) => "return_type",
+            MiscObligation
+            | SliceOrArrayElem
+            | TupleElem
+            | ProjectionWf(_)
+            | ItemObligation(_)
+            | BindingObligation(..)
+            | ReferenceOutlivesReferent(_)
+            | ObjectTypeBound(..)
+            | ObjectCastObligation(..)
+            | Coercion { .. }
+            | AssignmentLhsSized
+            | TupleInitializerSized
+            | StructInitializerSized
+            | VariableType(_)
+            | SizedArgumentType(_)
+            | SizedReturnType
+            | SizedYieldType
+            | SizedBoxType
+            | InlineAsmSized
+            | RepeatElementCopy { .. }
+            | FieldSized { .. }
+            | ConstSized
+            | SharedStatic
+            | BuiltinDerivedObligation(_)
+            | ImplDerivedObligation(_)
+            | DerivedObligation(_)
+            | FunctionArgumentObligation { .. }
+            | CheckAssociatedTypeBounds { .. }
+            | MatchExpressionArm(_)
+            | Pattern { .. }
+            | ConstPatternStructural
+            | LetElse
+            | UnifyReceiver(_)
+            | BlockTailExpression(_)
+            | TrivialBound
+            | OpaqueType
+            | AwaitableExpr(_)
+            | ForLoopIterator
+            | QuestionMark
+            | WellFormed(_)
+            | MatchImpl(..)
+            | BinOp { .. } => "other",
         }
     }
 }
 
+/// All values returned by `ObligationCauseExt::as_requirement_kind`, each of which has to be
+/// handled by the select expression of the `infer_requirement` message.
+pub const REQUIREMENT_KINDS: &[&str] = &[
+    "method_compat",
+    "type_compat",
+    "const_compat",
+    "expr_assignable",
+    "if_else_different",
+    "no_else",
+    "fn_main_correct_type",
+    "fn_start_correct_type",
+    "intrinsic_correct_type",
+    "method_correct_type",
+    "return_type",
+    "other",
+];
+
 /// This is a bare signal of what kind of type we're dealing with. `ty::TyKind` tracks
 /// extra information about each type, but we only care about the category.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -3025,6 +3125,7 @@ pub fn could_remove_semicolon(
 
     /// Suggest returning a local binding with a compatible type if the block
     /// has no return expression.
+    #[allow(rustc::untranslatable_diagnostic)]
     pub fn consider_returning_binding(
         &self,
         blk: &'tcx hir::Block<'tcx>,
@@ -1,24 +1,35 @@
+use crate::infer::error_reporting::span_attribution::{attribute_to_header, user_facing_span};
 use crate::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use crate::infer::InferCtxt;
-use rustc_errors::{pluralize, struct_span_err, Applicability, DiagnosticBuilder, ErrorGuaranteed};
+use rustc_errors::emitter::DEFAULT_COLUMN_WIDTH;
+use rustc_errors::{
+    fluent, struct_span_err, AddSubdiagnostic, Applicability, Diagnostic, DiagnosticBuilder,
+    DiagnosticId, DiagnosticMessage, EmittedErrorId, ErrorGuaranteed, Level, MultiSpan,
+    NoteMessage,
+};
 use rustc_hir as hir;
 use rustc_hir::def::Res;
 use rustc_hir::def::{CtorOf, DefKind, Namespace};
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::intravisit::{self, Visitor};
-use rustc_hir::{Body, Closure, Expr, ExprKind, FnRetTy, HirId, Local, LocalSource};
+use rustc_hir::{Body, Closure, ConstContext, Expr, ExprKind, FnRetTy, HirId, Local, LocalSource};
 use rustc_middle::hir::nested_filter;
 use rustc_middle::infer::unify_key::{ConstVariableOrigin, ConstVariableOriginKind};
 use rustc_middle::ty::adjustment::{Adjust, Adjustment, AutoBorrow, AutoBorrowMutability};
+use rustc_middle::ty::print::{with_crate_prefix, with_no_trimmed_paths};
 use rustc_middle::ty::print::{FmtPrinter, PrettyPrinter, Print, Printer};
 use rustc_middle::ty::subst::{GenericArg, GenericArgKind, Subst, SubstsRef};
 use rustc_middle::ty::{self, DefIdTree, InferConst};
-use rustc_middle::ty::{IsSuggestable, Ty, TyCtxt, TypeckResults};
-use rustc_span::symbol::{kw, Ident};
+use rustc_middle::ty::{IsNameableFrom, IsSuggestable, Ty, TyCtxt, TypeVisitable, TypeckResults};
+use rustc_span::hygiene::{ExpnData, ExpnKind, MacroKind};
+use rustc_span::source_map::SourceMap;
+use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::{BytePos, Span};
 use std::borrow::Cow;
 use std::iter;
+use std::mem;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum TypeAnnotationNeeded {
     /// ```compile_fail,E0282
     /// let x = "hello".chars().rev().collect();
@@ -46,6 +57,77 @@ fn into(self) -> rustc_errors::DiagnosticId {
     }
 }
 
+/// An inference failure error which hasn't been turned into a diagnostic yet, so that the
+/// caller can attach context of its own, see [InferCtxt::build_inference_failure].
+#[must_use]
+pub struct InferenceFailure<'tcx> {
+    err: DiagnosticBuilder<'tcx, ErrorGuaranteed>,
+    error_code: TypeAnnotationNeeded,
+    source_span: Option<Span>,
+}
+
+impl<'tcx> InferenceFailure<'tcx> {
+    /// Whether this is an E0282, E0283 or E0284 error.
+    pub fn error_code(&self) -> TypeAnnotationNeeded {
+        self.error_code
+    }
+
+    /// The span of the expression or pattern which needs an annotation, if one was found.
+    pub fn source_span(&self) -> Option<Span> {
+        self.source_span
+    }
+
+    #[track_caller]
+    pub fn subdiagnostic(&mut self, subdiagnostic: impl AddSubdiagnostic) -> &mut Self {
+        self.err.subdiagnostic(subdiagnostic);
+        self
+    }
+
+    pub fn into_diagnostic(self) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
+        self.err
+    }
+}
+
+/// How many type annotation errors with some error code were emitted for a function, and
+/// which ones were left out because of `-Zinference-error-budget`.
+#[derive(Default)]
+pub(crate) struct InferenceErrorBudget {
+    reported: usize,
+    suppressed: usize,
+    /// The primary spans of the first few suppressed errors.
+    suppressed_spans: Vec<Span>,
+}
+
+/// The number of suppressed errors pointed at by their summary.
+const MAX_SUMMARIZED_SPANS: usize = 10;
+
+/// Notes the obligation which couldn't be selected because of the inference failure.
+#[derive(SessionSubdiagnostic)]
+#[note(infer::cannot_satisfy_predicate)]
+pub struct CannotSatisfyPredicate<'tcx> {
+    pub predicate: ty::Predicate<'tcx>,
+}
+
+/// The labels about what couldn't be inferred which all type annotation errors have,
+/// from the E0282, E0283 and E0284 errors to their fallbacks and the errors in generators, see
+/// [InferCtxt::inference_failure_details].
+#[derive(Default)]
+struct InferenceFailureDetails {
+    /// Added together, so that labels with the same span share an underline.
+    labels: Vec<(Span, DiagnosticMessage)>,
+}
+
+impl AddSubdiagnostic for InferenceFailureDetails {
+    #[track_caller]
+    fn add_to_diagnostic(self, diag: &mut Diagnostic) {
+        diag.track_subdiagnostic("InferenceFailureDetails");
+        diag.span_labels_from_iter(self.labels);
+    }
+}
+
+/// The name of the binding introduced by `InferSourceKind::LetIntroduction`.
+const LET_INTRODUCTION_NAME: &str = "value";
+
 /// Information about a constant or a type containing inference variables.
 pub struct InferenceDiagnosticsData {
     pub name: String,
@@ -83,15 +165,19 @@ fn cannot_infer_msg(&self) -> String {
         format!("cannot infer {} `{}`{}", self.kind.prefix_string(), self.name, suffix)
     }
 
-    fn where_x_is_specified(&self, in_type: Ty<'_>) -> String {
+    /// Sets the arguments of `infer_where_x_is_specified`, which the suggestions to give
+    /// `in_type` to a binding end with.
+    fn set_where_x_is_specified_args(&self, err: &mut Diagnostic, in_type: Ty<'_>) {
         if in_type.is_ty_infer() {
-            String::new()
+            err.set_arg("specified", "none");
         } else if self.name == "_" {
             // FIXME: Consider specializing this message if there is a single `_`
             // in the type.
-            ", where the placeholders `_` are specified".to_string()
+            err.set_arg("specified", "placeholders");
         } else {
-            format!(", where the {} `{}` is specified", self.kind.prefix_string(), self.name)
+            err.set_arg("specified", "param");
+            err.set_arg("param_prefix", self.kind.definite_prefix_string().into_owned());
+            err.set_arg("param_name", self.name.clone());
         }
     }
 }
@@ -120,13 +206,24 @@ fn suffix_string(&self) -> String {
 }
 
 impl UnderspecifiedArgKind {
+    /// Describes the argument, e.g. "type for type parameter". This is used by both the
+    /// labels and the suggestions so that they always agree on the wording.
     fn prefix_string(&self) -> Cow<'static, str> {
         match self {
             Self::Type { prefix } => format!("type for {}", prefix).into(),
-            Self::Const { is_parameter: true } => "the value of const parameter".into(),
+            Self::Const { is_parameter: true } => "the value of the const parameter".into(),
             Self::Const { is_parameter: false } => "the value of the constant".into(),
         }
     }
+
+    /// Like `prefix_string`, but always starting with "the", for use in the middle
+    /// of a sentence.
+    fn definite_prefix_string(&self) -> Cow<'static, str> {
+        match self {
+            Self::Type { .. } => format!("the {}", self.prefix_string()).into(),
+            Self::Const { .. } => self.prefix_string(),
+        }
+    }
 }
 
 fn fmt_printer<'a, 'tcx>(infcx: &'a InferCtxt<'_, 'tcx>, ns: Namespace) -> FmtPrinter<'a, 'tcx> {
@@ -176,6 +273,26 @@ fn ty_to_string<'tcx>(infcx: &InferCtxt<'_, 'tcx>, ty: Ty<'tcx>) -> String {
     }
 }
 
+/// The number of nodes of `ty` which tell the user something about it once printed.
+///
+/// Inference variables and the tuples holding them are rendered as `_` and `(_, _)`, which
+/// the user could have written without any help, so a suggestion whose type has no such
+/// node is not worth making.
+fn known_type_nodes<'tcx>(infcx: &InferCtxt<'_, 'tcx>, ty: Ty<'tcx>) -> usize {
+    infcx
+        .resolve_vars_if_possible(ty)
+        .walk()
+        .filter(|arg| match arg.unpack() {
+            GenericArgKind::Type(ty) => match *ty.kind() {
+                ty::Infer(_) => false,
+                ty::Tuple(tys) => tys.is_empty(),
+                _ => true,
+            },
+            GenericArgKind::Lifetime(_) | GenericArgKind::Const(_) => false,
+        })
+        .count()
+}
+
 /// We don't want to directly use `ty_to_string` for closures as their type isn't really
 /// something users are familar with. Directly printing the `fn_sig` of closures also
 /// doesn't work as they actually use the "rust-call" API.
@@ -295,19 +412,23 @@ pub fn extract_inference_diagnostics_data(
         }
     }
 
-    /// Used as a fallback in [InferCtxt::emit_inference_failure_err]
+    /// Used as a fallback in [InferCtxt::build_inference_failure]
     /// in case we weren't able to get a better error.
     fn bad_inference_failure_err(
         &self,
         span: Span,
         arg_data: InferenceDiagnosticsData,
         error_code: TypeAnnotationNeeded,
-    ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
-        let error_code = error_code.into();
-        let mut err =
-            self.tcx.sess.struct_span_err_with_code(span, "type annotations needed", error_code);
-        err.span_label(span, arg_data.cannot_infer_msg());
-        err
+    ) -> InferenceFailure<'tcx> {
+        let mut err = self.tcx.sess.struct_span_err_with_code(
+            span,
+            fluent::infer::type_annotations_needed,
+            error_code.into(),
+        );
+        err.set_arg("source_kind", "other");
+        err.set_arg("source_name", "");
+        err.subdiagnostic(self.inference_failure_details(span, &arg_data));
+        InferenceFailure { err, error_code, source_span: None }
     }
 
     pub fn emit_inference_failure_err(
@@ -318,6 +439,100 @@ pub fn emit_inference_failure_err(
         error_code: TypeAnnotationNeeded,
         should_label_span: bool,
     ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
+        self.build_inference_failure(body_id, failure_span, arg, error_code, should_label_span)
+            .into_diagnostic()
+    }
+
+    /// Like [InferCtxt::emit_inference_failure_err], but returns the error before it is
+    /// turned into a diagnostic, for callers which add subdiagnostics of their own.
+    pub fn build_inference_failure(
+        &self,
+        body_id: Option<hir::BodyId>,
+        failure_span: Span,
+        arg: GenericArg<'tcx>,
+        error_code: TypeAnnotationNeeded,
+        should_label_span: bool,
+    ) -> InferenceFailure<'tcx> {
+        let mut failure = self.build_inference_failure_unbudgeted(
+            body_id,
+            failure_span,
+            arg,
+            error_code,
+            should_label_span,
+        );
+        // Editors can't point into code generated into a file which is `include!`d, as it may
+        // be gone by the next build, so point out where it was included.
+        if let Some(site) = failure
+            .err
+            .span
+            .primary_span()
+            .and_then(|span| self.tcx.sess.source_map().include_site(span))
+        {
+            failure.err.span_note(site, fluent::infer::included_here);
+        }
+        failure
+    }
+
+    /// Counts `diag`, a type annotation error which is about to be emitted, towards the budget
+    /// of its function for errors with its error code. Once the function has run out of that
+    /// budget, the error isn't emitted and only counted towards the summary emitted by
+    /// [InferCtxt::emit_inference_error_summaries] instead.
+    ///
+    /// Only the errors about ambiguous obligations are budgeted, as the other type annotation
+    /// errors are only reported when there are no errors yet.
+    pub fn charge_inference_error_budget(&self, diag: &mut Diagnostic) {
+        let limit = self.tcx.sess.opts.unstable_opts.inference_error_budget;
+        let Some(typeck_results) = self.in_progress_typeck_results else { return };
+        let (Some(code), Level::Error { lint: false }) = (&diag.code, diag.level()) else { return };
+        if limit == 0 {
+            return;
+        }
+        let owner = typeck_results.borrow().hir_owner;
+        let mut budgets = self.inference_error_budgets.borrow_mut();
+        let budget = budgets.entry((owner, code.clone())).or_default();
+        if budget.reported < limit {
+            budget.reported += 1;
+            return;
+        }
+        budget.suppressed += 1;
+        if budget.suppressed_spans.len() < MAX_SUMMARIZED_SPANS
+            && let Some(span) = diag.span.primary_span()
+        {
+            budget.suppressed_spans.push(span);
+        }
+        diag.downgrade_to_delayed_bug();
+    }
+
+    /// Notes how many type annotation errors of each kind were left out for the functions
+    /// checked with this inference context, see [InferCtxt::charge_inference_error_budget].
+    pub fn emit_inference_error_summaries(&self) {
+        let budgets = std::mem::take(&mut *self.inference_error_budgets.borrow_mut());
+        for ((_, code), budget) in budgets {
+            if budget.suppressed == 0 {
+                continue;
+            }
+            let DiagnosticId::Error(code) = code else { continue };
+            let mut note =
+                self.tcx.sess.struct_note_without_error(fluent::infer::inference_errors_summarized);
+            note.set_span(MultiSpan::from_spans(budget.suppressed_spans));
+            note.set_arg("count", budget.suppressed);
+            note.set_arg("code", code);
+            note.note(fluent::_subdiag::note);
+            note.metadata.suppressed_errors = Some(budget.suppressed);
+            // Each summary counts the errors of its own function.
+            note.set_no_dedup();
+            note.emit();
+        }
+    }
+
+    fn build_inference_failure_unbudgeted(
+        &self,
+        body_id: Option<hir::BodyId>,
+        failure_span: Span,
+        arg: GenericArg<'tcx>,
+        error_code: TypeAnnotationNeeded,
+        should_label_span: bool,
+    ) -> InferenceFailure<'tcx> {
         let arg = self.resolve_vars_if_possible(arg);
         let arg_data = self.extract_inference_diagnostics_data(arg, None);
 
@@ -336,78 +551,184 @@ pub fn emit_inference_failure_err(
             local_visitor.visit_expr(expr);
         }
 
-        let Some(InferSource { span, kind }) = local_visitor.infer_source else {
+        let Some(InferSource { mut span, kind }) = local_visitor.infer_source else {
+            let failure_span = self.attribute_to_nested_construct(body_id, failure_span, arg);
             return self.bad_inference_failure_err(failure_span, arg_data, error_code)
         };
 
-        let error_code = error_code.into();
+        let const_context = body_id.and_then(|body_id| {
+            self.tcx.hir().body_const_context(self.tcx.hir().body_owner_def_id(body_id))
+        });
+        // The initializer of a `const` or `static`, an array length or an enum discriminant.
+        let in_const_initializer =
+            matches!(const_context, Some(ConstContext::Const | ConstContext::Static(_)));
+        let mut failure_span = failure_span;
+        if in_const_initializer && let Some(body_id) = body_id {
+            // Point at what the user wrote in the initializer rather than at the expansion of
+            // a macro used in it.
+            let body_span = self.tcx.hir().body(body_id).value.span;
+            span = span.find_ancestor_inside(body_span).unwrap_or(span);
+            failure_span = failure_span.find_ancestor_inside(body_span).unwrap_or(failure_span);
+        }
+        // Calling a function which isn't `const` is an error in a const context anyway, so
+        // there's no use in suggesting how to call it.
+        let calls_non_const_fn = const_context.is_some()
+            && match kind {
+                InferSourceKind::GenericArg { def_id, .. }
+                | InferSourceKind::FullyQualifiedMethodCall { def_id, .. } => {
+                    matches!(self.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+                        && !self.tcx.is_const_fn_raw(def_id)
+                }
+                _ => false,
+            };
+
         let mut err = self.tcx.sess.struct_span_err_with_code(
             span,
-            &format!("type annotations needed{}", kind.ty_msg(self)),
-            error_code,
+            if in_const_initializer {
+                fluent::infer::type_annotations_needed_in_const
+            } else {
+                fluent::infer::type_annotations_needed
+            },
+            error_code.into(),
         );
+        kind.set_source_kind_args(self, &mut err);
+        if let Some(id) = self.related_tainting_error(body_id) {
+            err.related_to(id);
+        }
 
+        let mut details = InferenceFailureDetails::default();
         if should_label_span && !failure_span.overlaps(span) {
-            err.span_label(failure_span, "type must be known at this point");
+            details.labels.push((failure_span, "type must be known at this point".into()));
+        }
+        // The label of a generic argument is only known once its parameters are looked at.
+        if !matches!(kind, InferSourceKind::GenericArg { .. }) {
+            err.subdiagnostic(mem::take(&mut details));
         }
 
         match kind {
-            InferSourceKind::LetBinding { insert_span, pattern_name, ty } => {
-                let suggestion_msg = if let Some(name) = pattern_name {
-                    format!(
-                        "consider giving `{}` an explicit type{}",
-                        name,
-                        arg_data.where_x_is_specified(ty)
-                    )
+            InferSourceKind::LetBinding { insert_span, pattern_name, binding, let_span, ty } => {
+                let module = self.tcx.parent_module_from_def_id(typeck_results.hir_owner);
+                if let Some(expn_data) = binding_macro_expansion(let_span) {
+                    // The annotation would have to go into the macro, which the user may not
+                    // be able to change, so point out the macro instead of suggesting it.
+                    err.span_label(expn_data.call_site, fluent::infer::macro_binding_call_label);
+                    match expn_data.macro_def_id {
+                        Some(def_id) if def_id.is_local() => {
+                            err.span_note(let_span, fluent::infer::macro_binding_note);
+                        }
+                        Some(def_id) => {
+                            err.set_arg("macro_name", self.tcx.item_name(def_id));
+                            err.set_arg("macro_crate", self.tcx.crate_name(def_id.krate));
+                            err.note(fluent::infer::external_macro_binding_note);
+                        }
+                        None => {}
+                    }
+                } else if let Some(item) = ty.find_unnameable_item(self.tcx, module.to_def_id()) {
+                    // Suggesting the annotation would only lead to a privacy error.
+                    self.note_unnameable_type(&mut err, ty, item);
+                } else if known_type_nodes(self, ty) == 0 {
+                    // Nothing is known about the type, so the annotation would be `: _`.
                 } else {
-                    format!(
-                        "consider giving this pattern a type{}",
-                        arg_data.where_x_is_specified(ty)
-                    )
-                };
-                err.span_suggestion_verbose(
-                    insert_span,
-                    &suggestion_msg,
-                    format!(": {}", ty_to_string(self, ty)),
-                    Applicability::HasPlaceholders,
-                );
+                    arg_data.set_where_x_is_specified_args(&mut err, ty);
+                    let suggestion_msg = if let Some(name) = pattern_name {
+                        err.set_arg("pattern_name", name);
+                        fluent::infer::let_binding_suggestion
+                    } else {
+                        fluent::infer::pattern_type_suggestion
+                    };
+                    err.span_suggestion_verbose(
+                        insert_span,
+                        suggestion_msg,
+                        format!(": {}", ty_to_string(self, ty)),
+                        Applicability::HasPlaceholders,
+                    );
+                    err.tag_last_suggestion("InferSourceKind::LetBinding");
+                }
+                if let (Some(name), Some(binding), Some(body_id)) = (pattern_name, binding, body_id)
+                {
+                    let uses = self.find_binding_uses(body_id, binding);
+                    self.note_empty_collection(&mut err, typeck_results, binding, ty, &uses);
+                    if self.tcx.sess.opts.unstable_opts.explain_inference {
+                        self.note_binding_uses(&mut err, name, &uses);
+                    }
+                }
             }
             InferSourceKind::ClosureArg { insert_span, ty } => {
+                arg_data.set_where_x_is_specified_args(&mut err, ty);
                 err.span_suggestion_verbose(
                     insert_span,
-                    &format!(
-                        "consider giving this closure parameter an explicit type{}",
-                        arg_data.where_x_is_specified(ty)
-                    ),
+                    fluent::infer::closure_arg_suggestion,
                     format!(": {}", ty_to_string(self, ty)),
                     Applicability::HasPlaceholders,
                 );
+                err.tag_last_suggestion("InferSourceKind::ClosureArg");
             }
             InferSourceKind::GenericArg {
                 insert_span,
                 argument_index,
                 generics_def_id,
-                def_id: _,
+                def_id,
+                substs,
                 generic_args,
+                through_self,
             } => {
                 let generics = self.tcx.generics_of(generics_def_id);
-                let is_type = matches!(arg.unpack(), GenericArgKind::Type(_));
-
-                let cannot_infer_msg = format!(
-                    "cannot infer {} of the {} parameter `{}`{}",
-                    if is_type { "type" } else { "the value" },
-                    if is_type { "type" } else { "const" },
-                    generics.params[argument_index].name,
-                    // We use the `generics_def_id` here, as even when suggesting `None::<T>`,
-                    // the type parameter `T` was still declared on the enum, not on the
-                    // variant.
-                    InferenceDiagnosticsParentData::for_parent_def_id(self.tcx, generics_def_id)
-                        .map_or(String::new(), |parent| parent.suffix_string()),
-                );
+                // Mention all the parameters of this path which are still unknown, instead of
+                // emitting the same error for each one of them.
+                let uninferred: Vec<_> = iter::zip(generics.own_substs(substs), &generics.params)
+                    .filter(|(arg, _)| match arg.unpack() {
+                        GenericArgKind::Type(ty) => ty.is_ty_var(),
+                        GenericArgKind::Const(ct) => {
+                            matches!(ct.kind(), ty::ConstKind::Infer(InferConst::Var(_)))
+                        }
+                        GenericArgKind::Lifetime(_) => false,
+                    })
+                    .map(|(_, param)| param)
+                    .collect();
+                let (param_descr, param_names) = if let [_, _, ..] = &uninferred[..] {
+                    let type_params = uninferred
+                        .iter()
+                        .filter(|param| matches!(param.kind, ty::GenericParamDefKind::Type { .. }))
+                        .count();
+                    let descr = if type_params == uninferred.len() {
+                        "the types of the type parameters"
+                    } else if type_params == 0 {
+                        "the values of the const parameters"
+                    } else {
+                        "the generic parameters"
+                    };
+                    (descr.into(), listify(uninferred.iter().map(|param| param.name)))
+                } else {
+                    let descr = match arg.unpack() {
+                        GenericArgKind::Const(_) => {
+                            UnderspecifiedArgKind::Const { is_parameter: true }.prefix_string()
+                        }
+                        _ => "type of the type parameter".into(),
+                    };
+                    (descr, listify(iter::once(generics.params[argument_index].name)))
+                };
+                err.set_arg("generic_param_descr", &*param_descr);
+                err.set_code_arg("generic_param_names", param_names);
+                // We use the `generics_def_id` here, as even when suggesting `None::<T>`,
+                // the type parameter `T` was still declared on the enum, not on the variant.
+                match InferenceDiagnosticsParentData::for_parent_def_id(self.tcx, generics_def_id) {
+                    Some(parent) => {
+                        err.set_arg("generic_parent_descr", parent.prefix);
+                        err.set_arg("generic_parent_name", parent.name);
+                    }
+                    None => {
+                        err.set_arg("generic_parent_descr", "none");
+                    }
+                }
+
+                details.labels.push((span, fluent::infer::cannot_infer_generic_args));
+                err.subdiagnostic(details);
 
-                err.span_label(span, cannot_infer_msg);
+                if calls_non_const_fn {
+                    return InferenceFailure { err, error_code, source_span: Some(span) };
+                }
 
-                let args = fmt_printer(self, Namespace::TypeNS)
+                let mut args = fmt_printer(self, Namespace::TypeNS)
                     .comma_sep(generic_args.iter().copied().map(|arg| {
                         if arg.is_suggestable(self.tcx, true) {
                             return arg;
@@ -435,19 +756,81 @@ pub fn emit_inference_failure_err(
                     .unwrap()
                     .into_buffer();
 
+                let mut applicability = Applicability::HasPlaceholders;
+                if let Some(method) = GenericReturnMethod::of(self.tcx, def_id) {
+                    err.set_arg("generic_method_name", method.name);
+                    err.set_arg("generic_method_bound", method.bound);
+                    err.note(fluent::infer::generic_return_method_note);
+                    if let Some(aggregated) = method.aggregated_item(self.tcx, substs) {
+                        err.note(aggregated.note());
+                        err.span_suggestions(
+                            insert_span,
+                            fluent::infer::collect_items_suggestion,
+                            aggregated.turbofishes().into_iter().map(String::from),
+                            Applicability::MaybeIncorrect,
+                        );
+                        err.tag_last_suggestion("GenericReturnMethod::aggregated_item");
+                        return InferenceFailure { err, error_code, source_span: Some(span) };
+                    }
+                    if let [_] = generic_args
+                        && let Some(ty) = method.prefilled_return_ty(self.tcx, substs)
+                    {
+                        args = ty_to_string(self, ty);
+                        applicability = Applicability::MaybeIncorrect;
+                    }
+                }
+
+                err.set_arg("count", generic_args.len());
+                // The parameters of the self type are known inside of its impl, so the ones
+                // of a path going through `Self` are declared on the associated item.
+                let declared_on =
+                    InferenceDiagnosticsParentData::for_parent_def_id(self.tcx, generics_def_id)
+                        .filter(|_| through_self);
+                let msg = match declared_on {
+                    Some(declared_on) => {
+                        err.set_arg("item_descr", declared_on.prefix);
+                        err.set_arg("item_name", declared_on.name);
+                        fluent::infer::self_generic_arg_suggestion
+                    }
+                    None => fluent::infer::generic_arg_suggestion,
+                };
                 err.span_suggestion_verbose(
                     insert_span,
-                    &format!(
-                        "consider specifying the generic argument{}",
-                        pluralize!(generic_args.len()),
-                    ),
+                    msg,
                     format!("::<{}>", args),
-                    Applicability::HasPlaceholders,
+                    applicability,
                 );
+                err.tag_last_suggestion("InferSourceKind::GenericArg");
             }
-            InferSourceKind::FullyQualifiedMethodCall { receiver, successor, substs, def_id } => {
+            // Computing the fully qualified path is expensive, and it's only used by the
+            // suggestion.
+            InferSourceKind::FullyQualifiedMethodCall { .. }
+                if self.tcx.sess.opts.unstable_opts.no_suggestions || calls_non_const_fn => {}
+            InferSourceKind::FullyQualifiedMethodCall {
+                receiver,
+                successor,
+                substs,
+                def_id,
+                trait_param,
+            } => {
                 let printer = fmt_printer(self, Namespace::ValueNS);
-                let def_path = printer.print_def_path(def_id, substs).unwrap().into_buffer();
+                let mut def_path = printer.print_def_path(def_id, substs).unwrap().into_buffer();
+
+                // Paths which don't fit on a line are unreadable in the rendered suggestion,
+                // so we import the trait and the self type and use their names instead.
+                let mut imports = None;
+                let width = self.tcx.sess.opts.diagnostic_width.unwrap_or(DEFAULT_COLUMN_WIDTH);
+                if def_path.len() > width
+                    && let Some(body_id) = body_id
+                    && let module = self.tcx.parent_module(body_id.hir_id)
+                    && let Some((short_path, use_items)) =
+                        self.shorten_fully_qualified_path(&def_path, def_id, substs, module)
+                {
+                    let (module, _, _) = self.tcx.hir().get_module(module);
+                    def_path = short_path;
+                    imports = (!use_items.is_empty())
+                        .then_some((module.spans.inject_use_span, use_items));
+                }
 
                 // We only care about whether we have to add `&` or `&mut ` for now.
                 // This is the case if the last adjustment is a borrow and the
@@ -468,39 +851,79 @@ pub fn emit_inference_failure_err(
                     _ => "",
                 };
 
-                let suggestion = vec![
-                    (receiver.span.shrink_to_lo(), format!("{def_path}({adjustment}")),
-                    (receiver.span.shrink_to_hi().with_hi(successor.1), successor.0.to_string()),
-                ];
-                err.multipart_suggestion_verbose(
-                    "try using a fully qualified path to specify the expected types",
-                    suggestion,
-                    Applicability::HasPlaceholders,
-                );
+                let suggestion: Vec<_> = imports
+                    .into_iter()
+                    .chain([
+                        (receiver.span.shrink_to_lo(), format!("{def_path}({adjustment}")),
+                        (successor.1, successor.0.to_string()),
+                    ])
+                    .collect();
+                let msg = match trait_param {
+                    Some(trait_param) => {
+                        err.set_arg("trait_param_name", trait_param);
+                        err.set_arg("trait_name", self.tcx.item_name(self.tcx.parent(def_id)));
+                        err.set_arg("method_name", self.tcx.item_name(def_id));
+                        err.help(fluent::infer::trait_generic_arg_help);
+                        fluent::infer::trait_generic_arg_suggestion
+                    }
+                    None => fluent::infer::fully_qualified_path_suggestion,
+                };
+                err.multipart_suggestion_verbose(msg, suggestion, Applicability::HasPlaceholders);
+                err.tag_last_suggestion("InferSourceKind::FullyQualifiedMethodCall");
+            }
+            InferSourceKind::ClosureReturn { ty, .. } if known_type_nodes(self, ty) == 0 => {
+                // Nothing is known about the return type, so the suggestion would be `-> _`.
             }
             InferSourceKind::ClosureReturn { ty, data, should_wrap_expr } => {
                 let ret = ty_to_string(self, ty);
-                let (arrow, post) = match data {
-                    FnRetTy::DefaultReturn(_) => ("-> ", " "),
-                    _ => ("", ""),
+                let (span, arrow, post) = match *data {
+                    FnRetTy::DefaultReturn(span) => (span, "-> ", " "),
+                    FnRetTy::Return(ret_ty) => {
+                        (return_ty_span(self.tcx.sess.source_map(), ret_ty.span), "", "")
+                    }
                 };
                 let suggestion = match should_wrap_expr {
                     Some(end_span) => vec![
-                        (data.span(), format!("{}{}{}{{ ", arrow, ret, post)),
+                        (span, format!("{}{}{}{{ ", arrow, ret, post)),
                         (end_span, " }".to_string()),
                     ],
-                    None => vec![(data.span(), format!("{}{}{}", arrow, ret, post))],
+                    None => vec![(span, format!("{}{}{}", arrow, ret, post))],
                 };
                 err.multipart_suggestion_verbose(
-                    "try giving this closure an explicit return type",
+                    fluent::infer::closure_return_suggestion,
                     suggestion,
                     Applicability::HasPlaceholders,
                 );
+                err.tag_last_suggestion("InferSourceKind::ClosureReturn");
+            }
+            InferSourceKind::LetIntroduction { insert_span, expr, ty } => {
+                let sm = self.tcx.sess.source_map();
+                if let Ok(snippet) = sm.span_to_snippet(expr.span) {
+                    let indent = sm.indentation_before(insert_span).unwrap_or_default();
+                    let ty = if ty.is_ty_infer() {
+                        "/* Type */".to_string()
+                    } else {
+                        ty_to_string(self, ty)
+                    };
+                    err.multipart_suggestion_verbose(
+                        fluent::infer::let_introduction_suggestion,
+                        vec![
+                            (
+                                insert_span.shrink_to_lo(),
+                                format!("let {LET_INTRODUCTION_NAME}: {ty} = {snippet};\n{indent}"),
+                            ),
+                            (expr.span, LET_INTRODUCTION_NAME.to_string()),
+                        ],
+                        Applicability::HasPlaceholders,
+                    );
+                    err.tag_last_suggestion("InferSourceKind::LetIntroduction");
+                }
             }
         }
-        err
+        InferenceFailure { err, error_code, source_span: Some(span) }
     }
 
+    #[allow(rustc::untranslatable_diagnostic)]
     pub fn need_type_info_err_in_generator(
         &self,
         kind: hir::GeneratorKind,
@@ -509,6 +932,14 @@ pub fn need_type_info_err_in_generator(
     ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
         let ty = self.resolve_vars_if_possible(ty);
         let data = self.extract_inference_diagnostics_data(ty.into(), None);
+        // The span may be from the desugaring of an `.await`, which the user didn't write.
+        let span = match self.in_progress_typeck_results {
+            Some(typeck_results) => {
+                let body_owner = typeck_results.borrow().hir_owner;
+                user_facing_span(self.tcx, span, body_owner).original
+            }
+            None => span,
+        };
 
         let mut err = struct_span_err!(
             self.tcx.sess,
@@ -517,9 +948,478 @@ pub fn need_type_info_err_in_generator(
             "type inside {} must be known in this context",
             kind,
         );
-        err.span_label(span, data.cannot_infer_msg());
+        err.subdiagnostic(self.inference_failure_details(span, &data));
         err
     }
+
+    /// The labels and notes of an error about the argument described by `arg_data` which
+    /// couldn't be inferred at `span`.
+    fn inference_failure_details(
+        &self,
+        span: Span,
+        arg_data: &InferenceDiagnosticsData,
+    ) -> InferenceFailureDetails {
+        InferenceFailureDetails { labels: vec![(span, arg_data.cannot_infer_msg().into())] }
+    }
+
+    /// Types which can't be inferred in a closure or `async` block are part of the type of
+    /// every closure and `async` block around it, and are reported for the outermost one,
+    /// at its whole span. Points at the header of the innermost one containing where the first
+    /// inference variable in `arg` was created instead.
+    fn attribute_to_nested_construct(
+        &self,
+        body_id: Option<hir::BodyId>,
+        failure_span: Span,
+        arg: GenericArg<'tcx>,
+    ) -> Span {
+        let origin = arg.walk().find_map(|arg| {
+            if let GenericArgKind::Type(ty) = arg.unpack()
+                && let ty::Infer(ty::TyVar(vid)) = *ty.kind()
+            {
+                Some(self.inner.borrow_mut().type_variables().var_origin(vid).span)
+            } else {
+                None
+            }
+        });
+        let (Some(body_id), Some(origin)) = (body_id, origin) else {
+            return failure_span;
+        };
+        let body_owner = self.tcx.hir().body_owner_def_id(body_id);
+        let header = user_facing_span(self.tcx, origin, body_owner).enclosing_construct_span;
+        attribute_to_header(failure_span, header)
+    }
+
+    /// Explains why `ty` can't be written out where the inference failure is, as it refers to
+    /// `item`, the result of `IsNameableFrom::find_unnameable_item`.
+    fn note_unnameable_type(&self, err: &mut Diagnostic, ty: Ty<'tcx>, item: DefId) {
+        let kind = self.tcx.def_kind(item);
+        let is_anonymous = matches!(kind, DefKind::Closure | DefKind::Generator);
+        err.set_arg("unnameable_ty", ty_to_string(self, ty));
+        err.set_arg("unnameable_item_is_anonymous", is_anonymous);
+        err.set_arg("unnameable_item_descr", kind.descr(item));
+        err.set_arg("unnameable_item_path", with_no_trimmed_paths!(self.tcx.def_path_str(item)));
+        err.note(fluent::infer::unnameable_type_note);
+    }
+
+    /// If this inference context is tainted by an error emitted in the body `body_id`, returns
+    /// that error, as it may be why the types in the body can't be inferred. Other
+    /// "type annotations needed" errors are not returned, as they are unlikely to be
+    /// the cause of this one.
+    fn related_tainting_error(&self, body_id: Option<hir::BodyId>) -> Option<EmittedErrorId> {
+        if !self.is_tainted_by_errors() {
+            return None;
+        }
+        let body_span = self.tcx.hir().body(body_id?).value.span;
+        let (id, span, code) = self.tcx.sess.diagnostic().last_emitted_error()?;
+        let is_inference_error = matches!(
+            code,
+            Some(DiagnosticId::Error(code)) if ["E0282", "E0283", "E0284"].contains(&&*code)
+        );
+        (!is_inference_error && body_span.contains(span)).then_some(id)
+    }
+
+    /// Shortens `def_path`, the fully qualified path of the trait method `def_id`, by
+    /// referring to its trait and self type by name. Returns the shortened path and the
+    /// `use` items which bring these names into `module`, where the path is used.
+    ///
+    /// Names which are taken by another item of `module`, and items which aren't accessible
+    /// from it, are left as they are.
+    fn shorten_fully_qualified_path(
+        &self,
+        def_path: &str,
+        def_id: DefId,
+        substs: SubstsRef<'tcx>,
+        module: LocalDefId,
+    ) -> Option<(String, String)> {
+        let trait_def_id = self.tcx.trait_of_item(def_id)?;
+        let self_def_id = match *substs.type_at(0).peel_refs().kind() {
+            ty::Adt(def, _) => Some(def.did()),
+            _ => None,
+        };
+
+        let mut short_path = def_path.to_string();
+        let mut use_items = String::new();
+        let mut names = vec![];
+        for import in self_def_id.into_iter().chain([trait_def_id]) {
+            let full_path = with_no_trimmed_paths!(self.tcx.def_path_str(import));
+            let name = self.tcx.item_name(import);
+            if full_path == name.as_str()
+                || names.contains(&name)
+                || !self.is_accessible_with_path(import, module)
+            {
+                continue;
+            }
+            let Some(needs_use) = self.name_is_free_for(module, name, import) else {
+                continue;
+            };
+            let Some(replaced) = replace_path(&short_path, &full_path, name.as_str()) else {
+                continue;
+            };
+            short_path = replaced;
+            names.push(name);
+            if needs_use {
+                use_items.push_str(&format!(
+                    "use {};\n",
+                    with_crate_prefix!(with_no_trimmed_paths!(self.tcx.def_path_str(import)))
+                ));
+            }
+        }
+        (!names.is_empty()).then_some((short_path, use_items))
+    }
+
+    /// Whether `def_id` and the modules on its path are accessible from `module`. Items from
+    /// other crates are only checked themselves, as their printed path is a visible one.
+    fn is_accessible_with_path(&self, def_id: DefId, module: LocalDefId) -> bool {
+        let tcx = self.tcx;
+        let module = module.to_def_id();
+        if !def_id.is_local() {
+            return tcx.visibility(def_id).is_accessible_from(module, tcx);
+        }
+        // The modules which `module` is in are accessible from it anyway.
+        iter::successors(Some(def_id), |&def_id| tcx.opt_parent(def_id))
+            .take_while(|&def_id| !tcx.is_descendant_of(module, def_id))
+            .all(|def_id| tcx.visibility(def_id).is_accessible_from(module, tcx))
+    }
+
+    /// Whether `name` can refer to `def_id` in `module`. Returns `Some(true)` if a `use`
+    /// item is needed for that, `Some(false)` if `name` already is `def_id` there and `None`
+    /// if another item of `module` is called `name`.
+    fn name_is_free_for(&self, module: LocalDefId, name: Symbol, def_id: DefId) -> Option<bool> {
+        let hir = self.tcx.hir();
+        let (module, _, _) = hir.get_module(module);
+        let mut needs_use = true;
+        for &item_id in module.item_ids {
+            let item = hir.item(item_id);
+            if item.ident.name != name {
+                continue;
+            }
+            let item_def_id = match item.kind {
+                hir::ItemKind::Use(path, _) => match path.res {
+                    Res::Def(DefKind::Ctor(..), ctor_def_id) => self.tcx.opt_parent(ctor_def_id),
+                    res => res.opt_def_id(),
+                },
+                _ => Some(item.def_id.to_def_id()),
+            };
+            if item_def_id != Some(def_id) {
+                return None;
+            }
+            needs_use = false;
+        }
+        Some(needs_use)
+    }
+
+    fn find_binding_uses(&self, body_id: hir::BodyId, binding: HirId) -> FindBindingUses<'tcx> {
+        let mut finder =
+            FindBindingUses { tcx: self.tcx, binding, uses: vec![], method_calls: vec![] };
+        finder.visit_expr(&self.tcx.hir().body(body_id).value);
+        finder
+    }
+
+    /// If the let binding `binding` of type `ty` is initialized with an empty std collection,
+    /// e.g. by `Vec::new()`, and only ever used as the receiver of methods which don't add
+    /// anything to it, notes that this is why its element type is unknown.
+    fn note_empty_collection(
+        &self,
+        err: &mut Diagnostic,
+        typeck_results: &TypeckResults<'tcx>,
+        binding: HirId,
+        ty: Ty<'tcx>,
+        uses: &FindBindingUses<'tcx>,
+    ) {
+        let tcx = self.tcx;
+        let ty::Adt(def, substs) = *self.resolve_vars_if_possible(ty).kind() else { return };
+        let Some(collection) = tcx.get_diagnostic_name(def.did()) else { return };
+        let is_map = match collection {
+            sym::Vec
+            | sym::VecDeque
+            | sym::LinkedList
+            | sym::BinaryHeap
+            | sym::HashSet
+            | sym::BTreeSet => false,
+            sym::HashMap | sym::BTreeMap => true,
+            _ => return,
+        };
+
+        let hir::Node::Local(&Local { init: Some(init), .. }) =
+            tcx.hir().get(tcx.hir().get_parent_node(binding))
+        else {
+            return;
+        };
+        let ExprKind::Call(callee, _) = init.kind else { return };
+        let ExprKind::Path(ref qpath) = callee.kind else { return };
+        let Res::Def(DefKind::AssocFn, def_id) = typeck_results.qpath_res(qpath, callee.hir_id)
+        else {
+            return;
+        };
+        // Only the inherent methods of the collection are known not to add anything to it,
+        // methods of traits or of other types may do anything.
+        let is_inherent_method = |def_id| {
+            tcx.impl_of_method(def_id).map_or(false, |impl_| {
+                tcx.impl_trait_ref(impl_).is_none() && tcx.type_of(impl_).ty_adt_def() == Some(def)
+            })
+        };
+        if init.span.from_expansion()
+            || !matches!(tcx.item_name(def_id).as_str(), "new" | "with_capacity")
+            || !is_inherent_method(def_id)
+        {
+            return;
+        }
+
+        // Any other use, e.g. passing `&mut v` to a function, may add elements to it.
+        if uses.method_calls.len() != uses.uses.len() {
+            return;
+        }
+        for &hir_id in &uses.method_calls {
+            match typeck_results.type_dependent_def_id(hir_id) {
+                Some(def_id)
+                    if is_inherent_method(def_id)
+                        && NON_ADDING_METHODS.contains(&tcx.item_name(def_id).as_str()) => {}
+                _ => return,
+            }
+        }
+
+        let is_unknown = |i| substs.type_at(i).is_ty_infer();
+        let unknown = if !is_map {
+            if !is_unknown(0) {
+                return;
+            }
+            "element"
+        } else {
+            match (is_unknown(0), is_unknown(1)) {
+                (true, true) => "key_and_value",
+                (true, false) => "key",
+                (false, true) => "value",
+                (false, false) => return,
+            }
+        };
+        err.set_arg("collection", collection);
+        err.set_arg("unknown_types", unknown);
+        err.note(fluent::infer::empty_collection_note);
+    }
+
+    /// Points at every use of the let binding `binding`, none of which were enough to
+    /// infer its type. Only used with `-Zexplain-inference`.
+    fn note_binding_uses(&self, err: &mut Diagnostic, name: Ident, uses: &FindBindingUses<'tcx>) {
+        if uses.uses.is_empty() {
+            return;
+        }
+
+        let use_spans: Vec<_> = uses.uses.iter().copied().take(MAX_BINDING_USES).collect();
+        let mut span = MultiSpan::from_spans(use_spans.clone());
+        for use_span in use_spans {
+            span.push_span_label(use_span, fluent::infer::unconstraining_use_label.as_message());
+        }
+        err.set_arg("binding_name", name);
+        err.span_note(span, fluent::infer::unconstraining_uses_note);
+    }
+
+    /// If the uninferred `self_ty` of an ambiguous obligation is the type of a closure
+    /// parameter of a closure passed to an `Option` or `Result` combinator, e.g. `x` in
+    /// `opt.map(|x| x.foo())`, points at that parameter. If the only candidate impl is
+    /// for `candidate`, this also suggests annotating the parameter with it.
+    pub fn note_combinator_closure_param(
+        &self,
+        err: &mut Diagnostic,
+        body_id: Option<hir::BodyId>,
+        self_ty: Ty<'tcx>,
+        candidate: Option<Ty<'tcx>>,
+    ) {
+        let self_ty = self.resolve_vars_if_possible(self_ty);
+        if !self_ty.is_ty_var() {
+            return;
+        }
+        let (Some(body_id), Some(typeck_results)) = (body_id, self.in_progress_typeck_results)
+        else {
+            return;
+        };
+        let typeck_results = typeck_results.borrow();
+
+        let mut finder = FindCombinatorClosureParam {
+            infer_source_finder: FindInferSourceVisitor::new(
+                &self,
+                &typeck_results,
+                self_ty.into(),
+            ),
+            found: None,
+        };
+        finder.visit_expr(&self.tcx.hir().body(body_id).value);
+        let Some((param_span, param_ty)) = finder.found else {
+            return;
+        };
+
+        // The error usually points at the parameter already, in which case only the
+        // suggestion adds anything.
+        if err.span.primary_span() != Some(param_span) {
+            err.span_note(param_span, fluent::infer::combinator_closure_param_note);
+        }
+        // Only `T`, `&T` and `&mut T` are handled, as those are the parameter
+        // types of the by-value and `as_ref`/`as_mut` combinator closures.
+        let prefix = match *param_ty.kind() {
+            ty::Ref(_, _, hir::Mutability::Not) => "&",
+            ty::Ref(_, _, hir::Mutability::Mut) => "&mut ",
+            _ => "",
+        };
+        if let Some(candidate) = candidate && candidate.is_suggestable(self.tcx, false) {
+            err.span_suggestion_verbose(
+                param_span.shrink_to_hi(),
+                fluent::infer::combinator_closure_param_suggestion,
+                format!(": {}{}", prefix, ty_to_string(self, candidate)),
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+}
+
+/// Finds an untyped closure parameter, of a closure passed to a method on `Option` or
+/// `Result`, whose type is the target of `infer_source_finder` or a reference to it.
+struct FindCombinatorClosureParam<'a, 'tcx> {
+    infer_source_finder: FindInferSourceVisitor<'a, 'tcx>,
+    found: Option<(Span, Ty<'tcx>)>,
+}
+
+impl<'a, 'tcx> FindCombinatorClosureParam<'a, 'tcx> {
+    fn is_option_or_result(&self, ty: Ty<'tcx>) -> bool {
+        let tcx = self.infer_source_finder.infcx.tcx;
+        match *ty.peel_refs().kind() {
+            ty::Adt(def, _) => {
+                tcx.is_diagnostic_item(sym::Option, def.did())
+                    || tcx.is_diagnostic_item(sym::Result, def.did())
+            }
+            _ => false,
+        }
+    }
+
+    fn check_closure_params(&mut self, body: &'tcx Body<'tcx>) {
+        let finder = &self.infer_source_finder;
+        for param in body.params {
+            if param.ty_span != param.pat.span {
+                continue;
+            }
+            let Some(param_ty) = finder.opt_node_type(param.hir_id) else { continue };
+            let inner_ty = match *param_ty.kind() {
+                ty::Ref(_, inner_ty, _) => inner_ty,
+                _ => param_ty,
+            };
+            if finder.generic_arg_is_target(inner_ty.into()) {
+                self.found = Some((param.pat.span, param_ty));
+                return;
+            }
+        }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for FindCombinatorClosureParam<'a, 'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.infer_source_finder.infcx.tcx.hir()
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        if self.found.is_some() {
+            return;
+        }
+        if let ExprKind::MethodCall(_, [receiver, args @ ..], _) = expr.kind
+            && let Some(receiver_ty) = self.infer_source_finder.opt_node_type(receiver.hir_id)
+            && self.is_option_or_result(receiver_ty)
+        {
+            for arg in args {
+                if let ExprKind::Closure(&Closure { body, .. }) = arg.kind {
+                    let body = self.infer_source_finder.infcx.tcx.hir().body(body);
+                    self.check_closure_params(body);
+                    if self.found.is_some() {
+                        return;
+                    }
+                }
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Replaces each occurrence of the path `full` in `path` with `short`, skipping the
+/// occurrences which are only part of a longer path.
+fn replace_path(path: &str, full: &str, short: &str) -> Option<String> {
+    let is_ident_char = |c: char| c == '_' || c.is_alphanumeric();
+    let mut replaced = String::with_capacity(path.len());
+    let mut last_end = 0;
+    for (start, _) in path.match_indices(full) {
+        let end = start + full.len();
+        if path[..start].ends_with(|c: char| c == ':' || is_ident_char(c))
+            || path[end..].starts_with(is_ident_char)
+        {
+            continue;
+        }
+        replaced.push_str(&path[last_end..start]);
+        replaced.push_str(short);
+        last_end = end;
+    }
+    if last_end == 0 {
+        return None;
+    }
+    replaced.push_str(&path[last_end..]);
+    Some(replaced)
+}
+
+/// The maximum number of uses pointed at by [`InferCtxt::note_binding_uses`].
+const MAX_BINDING_USES: usize = 10;
+
+/// The inherent methods of std collections which never add anything to them, used by
+/// [`InferCtxt::note_empty_collection`].
+const NON_ADDING_METHODS: &[&str] = &[
+    "capacity",
+    "clear",
+    "contains",
+    "contains_key",
+    "first",
+    "get",
+    "is_empty",
+    "iter",
+    "keys",
+    "last",
+    "len",
+    "reserve",
+    "shrink_to_fit",
+    "values",
+];
+
+/// Collects the uses of the local `binding`.
+struct FindBindingUses<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    binding: HirId,
+    /// The spans of the path expressions referring to `binding`.
+    uses: Vec<Span>,
+    /// The `HirId`s of the method calls with `binding` as their receiver.
+    method_calls: Vec<HirId>,
+}
+
+impl<'tcx> FindBindingUses<'tcx> {
+    fn is_binding(&self, expr: &Expr<'_>) -> bool {
+        matches!(
+            expr.kind,
+            ExprKind::Path(hir::QPath::Resolved(None, path)) if path.res == Res::Local(self.binding)
+        )
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for FindBindingUses<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        if self.is_binding(expr) {
+            self.uses.push(expr.span);
+        }
+        if let ExprKind::MethodCall(_, [receiver, ..], _) = expr.kind
+            && self.is_binding(receiver)
+        {
+            self.method_calls.push(expr.hir_id);
+        }
+        intravisit::walk_expr(self, expr);
+    }
 }
 
 #[derive(Debug)]
@@ -533,6 +1433,11 @@ enum InferSourceKind<'tcx> {
     LetBinding {
         insert_span: Span,
         pattern_name: Option<Ident>,
+        /// The `HirId` of the binding if the pattern is a simple identifier.
+        binding: Option<HirId>,
+        /// The span of the whole `let`, which tells whether a macro introduced the binding even
+        /// if the macro was given its name.
+        let_span: Span,
         ty: Ty<'tcx>,
     },
     ClosureArg {
@@ -544,21 +1449,36 @@ enum InferSourceKind<'tcx> {
         argument_index: usize,
         generics_def_id: DefId,
         def_id: DefId,
+        substs: SubstsRef<'tcx>,
         generic_args: &'tcx [GenericArg<'tcx>],
+        through_self: bool,
     },
     FullyQualifiedMethodCall {
         receiver: &'tcx Expr<'tcx>,
-        /// If the method has other arguments, this is ", " and the start of the first argument,
-        /// while for methods without arguments this is ")" and the end of the method call.
-        successor: (&'static str, BytePos),
+        /// The replacement for the text between the receiver and the other arguments of
+        /// the method. This is ", " if there are other arguments, and ")" otherwise, in
+        /// which case it replaces the rest of the method call.
+        successor: (&'static str, Span),
         substs: SubstsRef<'tcx>,
         def_id: DefId,
+        /// The parameter of the trait needing an annotation when the method has generic
+        /// parameters too, which suggests it could be specified on the method.
+        trait_param: Option<Symbol>,
     },
     ClosureReturn {
         ty: Ty<'tcx>,
         data: &'tcx FnRetTy<'tcx>,
         should_wrap_expr: Option<Span>,
     },
+    /// An argument of a call which can't be annotated in place, which is moved into a new
+    /// binding in front of the statement containing the call.
+    LetIntroduction {
+        /// The start of the statement, or the trailing expression of a block, containing the
+        /// call.
+        insert_span: Span,
+        expr: &'tcx Expr<'tcx>,
+        ty: Ty<'tcx>,
+    },
 }
 
 impl<'tcx> InferSource<'tcx> {
@@ -573,28 +1493,195 @@ fn from_expansion(&self) -> bool {
             InferSourceKind::ClosureReturn { data, should_wrap_expr, .. } => {
                 data.span().from_expansion() || should_wrap_expr.map_or(false, Span::from_expansion)
             }
+            InferSourceKind::LetIntroduction { insert_span, expr, .. } => {
+                insert_span.from_expansion() || expr.span.from_expansion()
+            }
         };
         source_from_expansion || self.span.from_expansion()
     }
 }
 
 impl<'tcx> InferSourceKind<'tcx> {
-    fn ty_msg(&self, infcx: &InferCtxt<'_, 'tcx>) -> String {
-        match *self {
+    /// Sets the `source_kind` and `source_name` arguments of `infer_type_annotations_needed`,
+    /// which mention the type to annotate if anything about it is known.
+    fn set_source_kind_args(&self, infcx: &InferCtxt<'_, 'tcx>, err: &mut Diagnostic) {
+        let (source_kind, source_name) = match *self {
             InferSourceKind::LetBinding { ty, .. }
             | InferSourceKind::ClosureArg { ty, .. }
-            | InferSourceKind::ClosureReturn { ty, .. } => {
+            | InferSourceKind::ClosureReturn { ty, .. }
+            | InferSourceKind::LetIntroduction { ty, .. } => {
                 if ty.is_closure() {
-                    format!(" for the closure `{}`", closure_as_fn_str(infcx, ty))
+                    ("closure", closure_as_fn_str(infcx, ty))
                 } else if !ty.is_ty_infer() {
-                    format!(" for `{}`", ty_to_string(infcx, ty))
+                    ("normal", ty_to_string(infcx, ty))
                 } else {
-                    String::new()
+                    ("other", String::new())
                 }
             }
             // FIXME: We should be able to add some additional info here.
             InferSourceKind::GenericArg { .. }
-            | InferSourceKind::FullyQualifiedMethodCall { .. } => String::new(),
+            | InferSourceKind::FullyQualifiedMethodCall { .. } => ("other", String::new()),
+        };
+        err.set_arg("source_kind", source_kind);
+        err.set_arg("source_name", source_name);
+    }
+}
+
+/// Where a method with a generic return type in `GENERIC_RETURN_METHODS` is defined.
+#[derive(Clone, Copy, PartialEq)]
+enum GenericReturnMethodOwner {
+    Iterator,
+    Str,
+}
+
+/// A method whose return type is generic and commonly needs to be annotated, because all that
+/// is known about it is that it implements `bound`.
+struct GenericReturnMethod {
+    owner: GenericReturnMethodOwner,
+    name: &'static str,
+    bound: &'static str,
+    /// Whether the return type is usually the item type of the iterator.
+    returns_item_ty: bool,
+}
+
+const GENERIC_RETURN_METHODS: &[GenericReturnMethod] = &[
+    GenericReturnMethod {
+        owner: GenericReturnMethodOwner::Iterator,
+        name: "sum",
+        bound: "Sum<Item>",
+        returns_item_ty: true,
+    },
+    GenericReturnMethod {
+        owner: GenericReturnMethodOwner::Iterator,
+        name: "product",
+        bound: "Product<Item>",
+        returns_item_ty: true,
+    },
+    GenericReturnMethod {
+        owner: GenericReturnMethodOwner::Iterator,
+        name: "collect",
+        bound: "FromIterator<Item>",
+        returns_item_ty: false,
+    },
+    GenericReturnMethod {
+        owner: GenericReturnMethodOwner::Str,
+        name: "parse",
+        bound: "FromStr",
+        returns_item_ty: false,
+    },
+];
+
+impl GenericReturnMethod {
+    fn of(tcx: TyCtxt<'_>, def_id: DefId) -> Option<&'static GenericReturnMethod> {
+        if !matches!(tcx.def_kind(def_id), DefKind::AssocFn) {
+            return None;
+        }
+        let iterator = tcx.get_diagnostic_item(sym::Iterator);
+        let owner = if iterator.is_some() && tcx.trait_of_item(def_id) == iterator {
+            GenericReturnMethodOwner::Iterator
+        } else if let Some(impl_def_id) = tcx.impl_of_method(def_id)
+            && tcx.impl_trait_ref(impl_def_id).is_none()
+            && tcx.type_of(impl_def_id).is_str()
+        {
+            GenericReturnMethodOwner::Str
+        } else {
+            return None;
+        };
+        let name = tcx.item_name(def_id);
+        GENERIC_RETURN_METHODS
+            .iter()
+            .find(|method| method.owner == owner && name.as_str() == method.name)
+    }
+
+    /// For methods returning the (dereferenced) item type of the iterator they are called on,
+    /// returns that type if it is a fully known numeric type, as these are what `Sum` and
+    /// `Product` are implemented for.
+    fn prefilled_return_ty<'tcx>(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        substs: SubstsRef<'tcx>,
+    ) -> Option<Ty<'tcx>> {
+        if !self.returns_item_ty {
+            return None;
+        }
+        let item_ty = Self::iterator_item_ty(tcx, substs)?.peel_refs();
+        item_ty.is_numeric().then_some(item_ty)
+    }
+
+    /// For `collect`, returns whether the iterator yields `Result` or `Option` items, which
+    /// can either be gathered into a single `Result` or `Option` or kept one by one.
+    fn aggregated_item(&self, tcx: TyCtxt<'_>, substs: SubstsRef<'_>) -> Option<AggregatedItem> {
+        if self.owner != GenericReturnMethodOwner::Iterator || self.name != "collect" {
+            return None;
+        }
+        let item_ty = Self::iterator_item_ty(tcx, substs)
+            .or_else(|| Self::map_closure_output_ty(tcx, substs))?;
+        let ty::Adt(def, _) = item_ty.kind() else {
+            return None;
+        };
+        if tcx.is_diagnostic_item(sym::Result, def.did()) {
+            Some(AggregatedItem::Result)
+        } else if tcx.is_diagnostic_item(sym::Option, def.did()) {
+            Some(AggregatedItem::Option)
+        } else {
+            None
+        }
+    }
+
+    /// The `Item` type of the iterator the method is called on, if it is fully known.
+    fn iterator_item_ty<'tcx>(tcx: TyCtxt<'tcx>, substs: SubstsRef<'tcx>) -> Option<Ty<'tcx>> {
+        // The regions of the iterator are still inference variables while type checking, but
+        // they don't matter for its item type.
+        let self_ty = tcx.erase_regions(substs.type_at(0));
+        if self_ty.needs_infer() || self_ty.has_param_types_or_consts() {
+            return None;
+        }
+        let iterator = tcx.get_diagnostic_item(sym::Iterator)?;
+        let item = tcx
+            .associated_items(iterator)
+            .in_definition_order()
+            .find(|item| item.kind == ty::AssocKind::Type)?;
+        let item_ty = tcx.mk_projection(item.def_id, tcx.mk_substs_trait(self_ty, &[]));
+        tcx.try_normalize_erasing_regions(ty::ParamEnv::reveal_all(), item_ty).ok()
+    }
+
+    /// The return type of the closure given to `map` when the method is called on the result,
+    /// like the `Result<_, _>` of `iter.map(|s| s.parse())`. Unlike the `Item` type of the whole
+    /// iterator, this is known while the types of the closure are still being inferred.
+    fn map_closure_output_ty<'tcx>(tcx: TyCtxt<'tcx>, substs: SubstsRef<'tcx>) -> Option<Ty<'tcx>> {
+        let ty::Adt(def, map_substs) = substs.type_at(0).kind() else {
+            return None;
+        };
+        if !tcx.is_diagnostic_item(sym::IterMap, def.did()) {
+            return None;
+        }
+        match map_substs.type_at(1).kind() {
+            ty::Closure(_, closure_substs) => {
+                Some(closure_substs.as_closure().sig().output().skip_binder())
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum AggregatedItem {
+    Result,
+    Option,
+}
+
+impl AggregatedItem {
+    fn note(self) -> NoteMessage {
+        match self {
+            AggregatedItem::Result => fluent::infer::collect_result_items_note,
+            AggregatedItem::Option => fluent::infer::collect_option_items_note,
+        }
+    }
+
+    fn turbofishes(self) -> [&'static str; 2] {
+        match self {
+            AggregatedItem::Result => ["::<Result<Vec<_>, _>>", "::<Vec<Result<_, _>>>"],
+            AggregatedItem::Option => ["::<Option<Vec<_>>>", "::<Vec<Option<_>>>"],
         }
     }
 }
@@ -605,6 +1692,9 @@ struct InsertableGenericArgs<'tcx> {
     substs: SubstsRef<'tcx>,
     generics_def_id: DefId,
     def_id: DefId,
+    /// Whether the path is qualified with `Self`, which hides the item the generic
+    /// parameters are declared on.
+    through_self: bool,
 }
 
 /// A visitor which searches for the "best" spot to use in the inference error.
@@ -702,6 +1792,8 @@ fn ty_cost(self, ty: Ty<'tcx>) -> usize {
             InferSourceKind::ClosureReturn { ty, should_wrap_expr, .. } => {
                 30 + ctx.ty_cost(ty) + if should_wrap_expr.is_some() { 10 } else { 0 }
             }
+            // Restructuring the code is only preferable to annotating it where it is.
+            InferSourceKind::LetIntroduction { ty, .. } => 25 + ctx.ty_cost(ty),
         };
 
         let suggestion_may_apply = if source.from_expansion() { 10000 } else { 0 };
@@ -722,6 +1814,50 @@ fn update_infer_source(&mut self, new_source: InferSource<'tcx>) {
         }
     }
 
+    /// Where to introduce a binding for `expr`, an argument of a call, so that it's evaluated
+    /// right before the statement containing it: the start of that statement, or of the
+    /// trailing expression of the block containing it. Returns `None` if moving `expr` there
+    /// could change whether it is evaluated at all, or if the new binding would shadow a local
+    /// used by the statement.
+    fn let_introduction_span(&self, expr: &'tcx Expr<'tcx>) -> Option<Span> {
+        let name = Some(Symbol::intern(LET_INTRODUCTION_NAME));
+        let mut child = expr.hir_id;
+        for (parent_id, parent) in self.infcx.tcx.hir().parent_iter(expr.hir_id) {
+            match parent {
+                hir::Node::Stmt(stmt) => {
+                    let mut finder = FindLocal { name, found: false };
+                    finder.visit_stmt(stmt);
+                    return (!finder.found).then_some(stmt.span);
+                }
+                hir::Node::Block(block) => {
+                    let tail = block.expr.filter(|tail| tail.hir_id == child)?;
+                    return (!mentions_local(tail, name)).then_some(tail.span);
+                }
+                hir::Node::Local(_) => {}
+                hir::Node::Expr(parent_expr) => match parent_expr.kind {
+                    ExprKind::Call(..)
+                    | ExprKind::MethodCall(..)
+                    | ExprKind::Tup(_)
+                    | ExprKind::Array(_)
+                    | ExprKind::Struct(..)
+                    | ExprKind::Field(..)
+                    | ExprKind::Index(..)
+                    | ExprKind::Cast(..)
+                    | ExprKind::Type(..)
+                    | ExprKind::Unary(..)
+                    | ExprKind::AddrOf(..)
+                    | ExprKind::Assign(..)
+                    | ExprKind::AssignOp(..) => {}
+                    ExprKind::Binary(op, ..) if !op.node.is_lazy() => {}
+                    _ => return None,
+                },
+                _ => return None,
+            }
+            child = parent_id;
+        }
+        None
+    }
+
     fn node_substs_opt(&self, hir_id: HirId) -> Option<SubstsRef<'tcx>> {
         let substs = self.typeck_results.node_substs_opt(hir_id);
         self.infcx.resolve_vars_if_possible(substs)
@@ -845,6 +1981,7 @@ fn expr_inferred_subst_iter(
                             substs,
                             generics_def_id: def_id,
                             def_id,
+                            through_self: false,
                         }
                     };
                     return Box::new(insertable.into_iter());
@@ -880,6 +2017,7 @@ fn resolved_path_inferred_subst_iter(
                 substs,
                 generics_def_id,
                 def_id: path.res.def_id(),
+                through_self: false,
             }
         };
 
@@ -899,6 +2037,7 @@ fn resolved_path_inferred_subst_iter(
                     substs,
                     generics_def_id,
                     def_id: res.def_id(),
+                    through_self: false,
                 })
             })
             .chain(last_segment_using_path_data)
@@ -920,6 +2059,18 @@ fn path_inferred_subst_iter(
                     return Box::new(iter::empty());
                 };
 
+                // Both `Self::f` and `<Self>::f` name the function through the self type of the
+                // impl they are in. Its generic arguments are the parameters of the impl, which
+                // are known inside of it, so an unknown parameter of such a path is always one of
+                // the function, and `Self` never has to be replaced by e.g. `Foo::<T>`. The same
+                // goes for `Self { .. }` and `Self(..)`, which never have unknown parameters.
+                let through_self = matches!(
+                    ty.kind,
+                    hir::TyKind::Path(hir::QPath::Resolved(
+                        None,
+                        hir::Path { res: Res::SelfTy { alias_to: Some(_), .. }, .. },
+                    ))
+                );
                 let generics = tcx.generics_of(def_id);
                 let segment: Option<_> = try {
                     if !segment.infer_args || generics.has_impl_trait() {
@@ -927,7 +2078,13 @@ fn path_inferred_subst_iter(
                     }
                     let span = tcx.hir().span(segment.hir_id?);
                     let insert_span = segment.ident.span.shrink_to_hi().with_hi(span.hi());
-                    InsertableGenericArgs { insert_span, substs, generics_def_id: def_id, def_id }
+                    InsertableGenericArgs {
+                        insert_span,
+                        substs,
+                        generics_def_id: def_id,
+                        def_id,
+                        through_self,
+                    }
                 };
 
                 let parent_def_id = generics.parent.unwrap();
@@ -974,6 +2131,69 @@ fn path_inferred_subst_iter(
     }
 }
 
+/// Whether `expr` mentions a local, or only the locals named `name` if it is given.
+fn mentions_local(expr: &Expr<'_>, name: Option<Symbol>) -> bool {
+    let mut finder = FindLocal { name, found: false };
+    finder.visit_expr(expr);
+    finder.found
+}
+
+struct FindLocal {
+    name: Option<Symbol>,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for FindLocal {
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::Path(hir::QPath::Resolved(None, path)) = expr.kind
+            && let Res::Local(_) = path.res
+            && self.name.map_or(true, |name| path.segments[0].ident.name == name)
+        {
+            self.found = true;
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Formats `names` as "`A`, `B` and `C`".
+fn listify(names: impl Iterator<Item = Symbol>) -> String {
+    let names: Vec<_> = names.map(|name| format!("`{}`", name)).collect();
+    match &names[..] {
+        [] => String::new(),
+        [name] => name.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+/// Where to insert the type annotation for a `let` binding or closure parameter with the
+/// pattern `pat`. For a plain binding, this is right after its name no matter which of the
+/// `mut` and `ref` tokens are part of the span of the pattern.
+fn annotation_insert_span(pat: &hir::Pat<'_>) -> Span {
+    match pat.kind {
+        hir::PatKind::Binding(_, _, ident, None) => ident.span.shrink_to_hi(),
+        _ => pat.span.shrink_to_hi(),
+    }
+}
+
+/// The span of just the type of an explicit return type spanning `span`, which may include the
+/// `->` in front of it, so that replacing the type keeps the arrow and spacing the user wrote.
+fn return_ty_span(sm: &SourceMap, span: Span) -> Span {
+    match sm.span_to_snippet(span) {
+        Ok(snippet) if snippet.starts_with("->") => {
+            let ty = snippet["->".len()..].trim_start();
+            span.with_lo(span.hi() - BytePos(ty.len() as u32))
+        }
+        _ => span,
+    }
+}
+
+/// The expansion of the function-like macro, like a `macro_rules!` macro, which wrote out the `let`
+/// spanning `let_span`, if any.
+fn binding_macro_expansion(let_span: Span) -> Option<ExpnData> {
+    let expn_data = let_span.ctxt().outer_expn_data();
+    matches!(expn_data.kind, ExpnKind::Macro(MacroKind::Bang, _)).then_some(expn_data)
+}
+
 impl<'a, 'tcx> Visitor<'tcx> for FindInferSourceVisitor<'a, 'tcx> {
     type NestedFilter = nested_filter::OnlyBodies;
 
@@ -991,8 +2211,10 @@ fn visit_local(&mut self, local: &'tcx Local<'tcx>) {
                         self.update_infer_source(InferSource {
                             span: local.pat.span,
                             kind: InferSourceKind::LetBinding {
-                                insert_span: local.pat.span.shrink_to_hi(),
+                                insert_span: annotation_insert_span(local.pat),
                                 pattern_name: local.pat.simple_ident(),
+                                binding: local.pat.simple_ident().map(|_| local.pat.hir_id),
+                                let_span: local.span,
                                 ty,
                             },
                         })
@@ -1024,7 +2246,7 @@ fn visit_body(&mut self, body: &'tcx Body<'tcx>) {
                 self.update_infer_source(InferSource {
                     span: param.pat.span,
                     kind: InferSourceKind::ClosureArg {
-                        insert_span: param.pat.span.shrink_to_hi(),
+                        insert_span: annotation_insert_span(param.pat),
                         ty: param_ty,
                     },
                 })
@@ -1050,7 +2272,13 @@ fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
 
         for args in self.expr_inferred_subst_iter(expr) {
             debug!(?args);
-            let InsertableGenericArgs { insert_span, substs, generics_def_id, def_id } = args;
+            let InsertableGenericArgs {
+                insert_span,
+                substs,
+                generics_def_id,
+                def_id,
+                through_self,
+            } = args;
             let generics = tcx.generics_of(generics_def_id);
             if let Some(argument_index) = generics
                 .own_substs(substs)
@@ -1072,7 +2300,9 @@ fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
                         argument_index,
                         generics_def_id,
                         def_id,
+                        substs,
                         generic_args,
+                        through_self,
                     },
                 });
             }
@@ -1117,18 +2347,76 @@ fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
             && self.infcx.tcx.trait_of_item(def_id).is_some()
             && !has_impl_trait(def_id)
         {
-            let successor =
-                args.get(1).map_or_else(|| (")", span.hi()), |arg| (", ", arg.span.lo()));
+            let receiver = args.first().unwrap();
+            let successor = match args.get(1) {
+                Some(arg) => (", ", receiver.span.between(arg.span)),
+                None => (")", receiver.span.between(span.shrink_to_hi())),
+            };
+            // A turbofish on the method only specifies its own parameters, so point out that
+            // the parameter is declared on the trait if the method has parameters as well.
+            let generics = tcx.generics_of(def_id);
+            let own_counts = generics.own_counts();
+            let trait_param = if own_counts.types + own_counts.consts > 0
+                && !generics
+                    .own_substs(substs)
+                    .iter()
+                    .any(|&arg| self.generic_arg_contains_target(arg))
+            {
+                generics
+                    .parent
+                    .map(|trait_def_id| tcx.generics_of(trait_def_id))
+                    .and_then(|trait_generics| {
+                        trait_generics.params.iter().find(|param| {
+                            self.generic_arg_contains_target(substs[param.index as usize])
+                        })
+                    })
+                    .map(|param| param.name)
+            } else {
+                None
+            };
             let substs = self.infcx.resolve_vars_if_possible(substs);
             self.update_infer_source(InferSource {
                 span: path.ident.span,
                 kind: InferSourceKind::FullyQualifiedMethodCall {
-                    receiver: args.first().unwrap(),
+                    receiver,
                     successor,
                     substs,
                     def_id,
+                    trait_param,
                 }
             })
         }
+
+        // The arguments passed to `impl Trait` parameters can't be specified using a turbofish,
+        // so we suggest moving the temporaries among them into a binding with a type instead.
+        let (callee, args) = match expr.kind {
+            ExprKind::Call(func, args) => match self.opt_node_type(func.hir_id).map(Ty::kind) {
+                Some(&ty::FnDef(def_id, _)) => (def_id, args),
+                _ => return,
+            },
+            ExprKind::MethodCall(_, args, _) => {
+                match self.typeck_results.type_dependent_def_id(expr.hir_id) {
+                    Some(def_id) => (def_id, &args[1..]),
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+        if !has_impl_trait(callee) {
+            return;
+        }
+        for arg in args {
+            if matches!(arg.kind, ExprKind::Call(..) | ExprKind::MethodCall(..) | ExprKind::Lit(_))
+                && let Some(ty) = self.opt_node_type(arg.hir_id)
+                && self.generic_arg_contains_target(ty.into())
+                && !mentions_local(arg, None)
+                && let Some(insert_span) = self.let_introduction_span(arg)
+            {
+                self.update_infer_source(InferSource {
+                    span: arg.span,
+                    kind: InferSourceKind::LetIntroduction { insert_span, expr: arg, ty },
+                })
+            }
+        }
     }
 }
@@ -1,18 +1,28 @@
 //! Error Reporting for Anonymous Region Lifetime Errors
 //! where both the regions are anonymous.
 
-use crate::infer::error_reporting::nice_region_error::find_anon_type::find_anon_type;
+use crate::infer::error_reporting::nice_region_error::find_anon_type::{
+    find_anon_type, find_component_for_bound_region,
+};
 use crate::infer::error_reporting::nice_region_error::util::AnonymousParamInfo;
 use crate::infer::error_reporting::nice_region_error::NiceRegionError;
 use crate::infer::lexical_region_resolve::RegionResolutionError;
 use crate::infer::SubregionOrigin;
 use crate::infer::TyCtxt;
 
-use rustc_errors::{struct_span_err, Applicability, Diagnostic, ErrorGuaranteed};
+use rustc_errors::{
+    error_code, fluent, merge_touching_suggestion_parts, Applicability, Diagnostic, ErrorGuaranteed,
+    MultiSpan,
+};
 use rustc_hir as hir;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::{self, Visitor};
 use rustc_hir::{GenericParamKind, Ty};
-use rustc_middle::ty::Region;
-use rustc_span::symbol::kw;
+use rustc_middle::middle::resolve_lifetime as rl;
+use rustc_middle::ty::subst::{InternalSubsts, Subst};
+use rustc_middle::ty::{self, DefIdTree, Region};
+use rustc_span::symbol::{kw, Symbol};
+use rustc_span::{BytePos, Span};
 
 impl<'a, 'tcx> NiceRegionError<'a, 'tcx> {
     /// Print the error message for lifetime errors when both the concerned regions are anonymous.
@@ -51,6 +61,7 @@ impl<'a, 'tcx> NiceRegionError<'a, 'tcx> {
     /// ```
     ///
     /// It will later be extended to trait objects.
+    #[allow(rustc::untranslatable_diagnostic)]
     pub(super) fn try_report_anon_anon_conflict(&self) -> Option<ErrorGuaranteed> {
         let (span, sub, sup) = self.regions()?;
 
@@ -113,12 +124,37 @@ pub(super) fn try_report_anon_anon_conflict(&self) -> Option<ErrorGuaranteed> {
             sub_is_ret_type, sup_is_ret_type
         );
 
-        let mut err = struct_span_err!(self.tcx().sess, span, E0623, "lifetime mismatch");
+        // Mirrored errors are only merged when data flows between two parameters.
+        if self.mirror_span.is_some()
+            && (sup_is_ret_type.is_some()
+                || sub_is_ret_type.is_some()
+                || ty_sup.hir_id == ty_sub.hir_id)
+        {
+            return None;
+        }
+
+        let mut err = self.tcx().sess.struct_span_err_with_code(
+            span,
+            fluent::infer::lifetime_mismatch,
+            error_code!(E0623),
+        );
 
         match (sup_is_ret_type, sub_is_ret_type) {
             (ret_capture @ Some(ret_span), _) | (_, ret_capture @ Some(ret_span)) => {
-                let param_span =
-                    if sup_is_ret_type == ret_capture { ty_sub.span } else { ty_sup.span };
+                let (param_span, ret_region, ret_fndecl) = if sup_is_ret_type == ret_capture {
+                    (ty_sub.span, bregion_sup, ty_fndecl_sup)
+                } else {
+                    (ty_sup.span, bregion_sub, ty_fndecl_sub)
+                };
+                // Only label the part of the return type with the lifetime, like the `&str` in
+                // `(&str, usize)` or `Result<&str, Error>`.
+                let ret_span = match ret_fndecl.decl.output {
+                    hir::FnRetTy::Return(ret_ty) => {
+                        find_component_for_bound_region(self.tcx(), ret_ty, &ret_region)
+                            .map_or(ret_span, |component| component.span)
+                    }
+                    hir::FnRetTy::DefaultReturn(_) => ret_span,
+                };
 
                 err.span_label(
                     param_span,
@@ -143,34 +179,104 @@ pub(super) fn try_report_anon_anon_conflict(&self) -> Option<ErrorGuaranteed> {
                         span,
                         format!("...but data{} flows{} here", span_label_var1, span_label_var2),
                     );
+                    if let Some(mirror_span) = self.mirror_span {
+                        let name = |param: &hir::Param<'_>| match param.pat.simple_ident() {
+                            Some(simple_ident) => simple_ident.to_string(),
+                            None => "none".to_owned(),
+                        };
+                        err.set_arg("mirror_from", name(anon_param_sub));
+                        err.set_arg("mirror_into", name(anon_param_sup));
+                        err.span_label(mirror_span, fluent::infer::mirrored_data_flow_label);
+                    }
                 }
             }
         }
 
-        if suggest_adding_lifetime_params(self.tcx(), sub, ty_sup, ty_sub, &mut err) {
-            err.note("each elided lifetime in input position becomes a distinct lifetime");
+        self.label_lifetime_declarations(&mut err, &[(sup, ty_sup.span), (sub, ty_sub.span)]);
+        self.note_object_lifetime_defaults(&mut err, &[ty_sup, ty_sub]);
+
+        if suggest_adding_lifetime_params(self.tcx(), sub, sup, ty_sup, ty_sub, &mut err) {
+            err.explanatory_note(fluent::infer::elided_lifetimes_distinct_note);
         }
 
         let reported = err.emit();
         Some(reported)
     }
+
+    /// Points out the trait objects among `tys` whose lifetime isn't written out and defaults
+    /// to something else than the lifetime of the reference they are behind, because of a
+    /// lifetime bound on their trait. Changing the lifetime of the reference doesn't change the
+    /// one of the trait object then.
+    fn note_object_lifetime_defaults(&self, err: &mut Diagnostic, tys: &[&hir::Ty<'_>]) {
+        let tcx = self.tcx();
+        let mut noted = vec![];
+        for ty in tys {
+            if let hir::TyKind::Rptr(ref_lifetime, hir::MutTy { ty: pointee, .. }) = ty.kind
+                && let hir::TyKind::TraitObject(_, lifetime, _) = pointee.kind
+                && lifetime.name == hir::LifetimeName::ImplicitObjectLifetimeDefault
+                && let Some(default) = tcx.named_region(lifetime.hir_id)
+                && tcx.named_region(ref_lifetime.hir_id) != Some(default)
+                && !noted.contains(&ty.hir_id)
+            {
+                noted.push(ty.hir_id);
+                let default = match default {
+                    rl::Region::Static => kw::StaticLifetime,
+                    rl::Region::EarlyBound(_, def_id)
+                    | rl::Region::LateBound(_, _, def_id)
+                    | rl::Region::Free(_, def_id) => tcx.item_name(def_id),
+                };
+                err.set_arg("object_lifetime", default);
+                err.span_note(pointee.span, fluent::infer::object_lifetime_default_note);
+            }
+        }
+    }
+
+    /// Points at the declaration of each named lifetime in `regions`, unless that declaration
+    /// is on the same line as the type already labeled for it.
+    ///
+    /// Only lifetimes declared on the fn get here, as `find_anon_type` looks for the types
+    /// in the fn signature and so doesn't find the lifetimes of the impl.
+    fn label_lifetime_declarations(&self, err: &mut Diagnostic, regions: &[(Region<'tcx>, Span)]) {
+        let tcx = self.tcx();
+        let sm = tcx.sess.source_map();
+        let mut labeled = vec![];
+        for &(region, ty_span) in regions {
+            // The label is on the declaration of the lifetime, so it doesn't need to name it,
+            // which couldn't be done with arguments shared by the labels of all lifetimes.
+            let param_def_id = match *region {
+                ty::ReEarlyBound(ebr) if ebr.has_name() => ebr.def_id,
+                ty::ReFree(ty::FreeRegion {
+                    bound_region: ty::BoundRegionKind::BrNamed(def_id, name),
+                    ..
+                }) if name != kw::UnderscoreLifetime => def_id,
+                _ => continue,
+            };
+            let decl_span = tcx.def_span(param_def_id);
+            if labeled.contains(&decl_span)
+                || sm.lookup_char_pos(decl_span.lo()).line == sm.lookup_char_pos(ty_span.lo()).line
+            {
+                continue;
+            }
+            labeled.push(decl_span);
+            err.span_label(decl_span, fluent::infer::lifetime_declared_here_label);
+        }
+    }
 }
 
 pub fn suggest_adding_lifetime_params<'tcx>(
     tcx: TyCtxt<'tcx>,
     sub: Region<'tcx>,
+    sup: Region<'tcx>,
     ty_sup: &Ty<'_>,
     ty_sub: &Ty<'_>,
     err: &mut Diagnostic,
 ) -> bool {
-    let (
-        hir::Ty { kind: hir::TyKind::Rptr(lifetime_sub, _), .. },
-        hir::Ty { kind: hir::TyKind::Rptr(lifetime_sup, _), .. },
-    ) = (ty_sub, ty_sup) else {
+    if tcx.sess.opts.unstable_opts.no_suggestions {
         return false;
-    };
+    }
 
-    if !lifetime_sub.name.is_anonymous() || !lifetime_sup.name.is_anonymous() {
+    let (Some(lifetime_sub), Some(lifetime_sup)) =
+        (anonymous_lifetime(ty_sub), anonymous_lifetime(ty_sup)) else {
         return false;
     };
 
@@ -182,6 +288,13 @@ pub fn suggest_adding_lifetime_params<'tcx>(
 
     let node = tcx.hir().get(hir_id);
     let is_impl = matches!(&node, hir::Node::ImplItem(_));
+    let is_provided_method = matches!(
+        node,
+        hir::Node::TraitItem(&hir::TraitItem {
+            kind: hir::TraitItemKind::Fn(_, hir::TraitFn::Provided(_)),
+            ..
+        })
+    );
     let generics = match node {
         hir::Node::Item(&hir::Item { kind: hir::ItemKind::Fn(_, ref generics, ..), .. })
         | hir::Node::TraitItem(&hir::TraitItem { ref generics, .. })
@@ -189,46 +302,518 @@ pub fn suggest_adding_lifetime_params<'tcx>(
         _ => return false,
     };
 
-    let suggestion_param_name = generics
-        .params
-        .iter()
-        .filter(|p| matches!(p.kind, GenericParamKind::Lifetime { .. }))
-        .map(|p| p.name.ident().name)
-        .find(|i| *i != kw::UnderscoreLifetime);
+    // Elided lifetimes inside of `fn(..)` pointer types and `Fn(..)` sugar are bound by
+    // that type, not by the enclosing function, so they need to be handled separately.
+    if let Some(decl) = node.fn_decl() {
+        match (enclosing_fn_sugar(decl, ty_sub.span), enclosing_fn_sugar(decl, ty_sup.span)) {
+            (None, None) => {}
+            (Some(sugar_sub), Some(sugar_sup)) if sugar_sub.span == sugar_sup.span => {
+                return suggest_higher_ranked_binder(
+                    tcx,
+                    anon_reg.def_id,
+                    generics,
+                    sugar_sub,
+                    lifetime_sub,
+                    lifetime_sup,
+                    err,
+                );
+            }
+            (Some(sugar), _) | (_, Some(sugar)) => {
+                err.set_arg("sugar_is_fn_ptr", sugar.is_fn_ptr);
+                err.span_note(sugar.span, fluent::infer::higher_ranked_elided_lifetimes_note);
+                return false;
+            }
+        }
+    }
+
+    // A lifetime of the trait can be used in a provided method without changing its generics.
+    let trait_param_name = if is_provided_method {
+        tcx.hir().get_generics(tcx.local_parent(anon_reg.def_id)).and_then(lifetime_param_name)
+    } else {
+        None
+    };
+    let suggestion_param_name = trait_param_name.or_else(|| lifetime_param_name(generics));
     let introduce_new = suggestion_param_name.is_none();
     let suggestion_param_name =
         suggestion_param_name.map(|n| n.to_string()).unwrap_or_else(|| "'a".to_owned());
 
+    err.set_arg("param_name", suggestion_param_name.as_str());
+    err.set_arg("is_reuse", !introduce_new);
+
+    // Elided lifetimes inside of an argument-position `impl Trait` can only be named once that
+    // `impl Trait` is turned into a named generic parameter.
+    let apits: Vec<_> = generics
+        .params
+        .iter()
+        .filter(|p| {
+            p.is_impl_trait()
+                && (p.span.contains(lifetime_sub.span) || p.span.contains(lifetime_sup.span))
+        })
+        .collect();
+    if !apits.is_empty() {
+        // Adding a generic parameter would change the signature required by the trait.
+        if matches!(node, hir::Node::TraitItem(_))
+            || is_impl && tcx.impl_trait_ref(tcx.local_parent(anon_reg.def_id)).is_some()
+        {
+            return false;
+        }
+        return suggest_apit_to_generic_param(
+            tcx,
+            generics,
+            &apits,
+            [lifetime_sub.span, lifetime_sup.span],
+            &suggestion_param_name,
+            introduce_new,
+            err,
+        );
+    }
+
+    // Tying the lifetimes together in an implementation of a trait method which leaves them
+    // unrelated only leads to an error about the signature not matching the trait.
+    let bound_regions = tcx
+        .is_suitable_region(sup)
+        .filter(|anon_reg_sup| anon_reg_sup.def_id == anon_reg.def_id)
+        .map(|anon_reg_sup| [anon_reg.boundregion, anon_reg_sup.boundregion]);
+    if is_impl
+        && let Some(bound_regions) = bound_regions
+        && let Some((trait_item_def_id, trait_regions)) =
+            trait_method_regions(tcx, anon_reg.def_id, bound_regions)
+        && trait_regions[0] != trait_regions[1]
+    {
+        return note_lifetimes_fixed_by_trait(tcx, trait_item_def_id, trait_regions, err);
+    }
+
     debug!(?lifetime_sup.span);
     debug!(?lifetime_sub.span);
-    let make_suggestion = |span: rustc_span::Span| {
-        if span.is_empty() {
-            (span, format!("{}, ", suggestion_param_name))
-        } else if let Ok("&") = tcx.sess.source_map().span_to_snippet(span).as_deref() {
-            (span.shrink_to_hi(), format!("{} ", suggestion_param_name))
-        } else {
-            (span, suggestion_param_name.clone())
-        }
+    let mut suggestions = vec![
+        name_lifetime_suggestion(tcx, lifetime_sub.span, &suggestion_param_name),
+        name_lifetime_suggestion(tcx, lifetime_sup.span, &suggestion_param_name),
+    ];
+
+    if introduce_new {
+        suggestions.push(insert_generic_params(tcx, generics, &suggestion_param_name));
+    }
+
+    if is_provided_method {
+        let reused = match (trait_param_name, introduce_new) {
+            (Some(_), _) => "trait",
+            (None, false) => "method",
+            (None, true) => "none",
+        };
+        err.set_arg("reused", reused);
+        err.multipart_suggestion(
+            fluent::infer::lifetime_param_provided_method_suggestion,
+            merge_touching_suggestion_parts(suggestions),
+            Applicability::MaybeIncorrect,
+        );
+        note_provided_method_impls(tcx, anon_reg.def_id, err);
+        return true;
+    }
+
+    err.set_arg("is_impl", is_impl);
+    err.multipart_suggestion(
+        fluent::infer::lifetime_param_suggestion,
+        merge_touching_suggestion_parts(suggestions),
+        Applicability::MaybeIncorrect,
+    );
+
+    true
+}
+
+/// The first lifetime parameter of `generics`, which isn't `'_`.
+fn lifetime_param_name(generics: &hir::Generics<'_>) -> Option<Symbol> {
+    generics
+        .params
+        .iter()
+        .filter(|p| matches!(p.kind, GenericParamKind::Lifetime { .. }))
+        .map(|p| p.name.ident().name)
+        .find(|i| *i != kw::UnderscoreLifetime)
+}
+
+/// For the method `def_id` implementing a trait method, the trait method and the regions in
+/// its signature in the places of the late-bound regions `bound_regions` of the method, found
+/// by comparing the signatures after instantiating the trait method's with the impl's
+/// arguments for the trait.
+fn trait_method_regions<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: LocalDefId,
+    bound_regions: [ty::BoundRegionKind; 2],
+) -> Option<(DefId, [Region<'tcx>; 2])> {
+    let trait_item_def_id = tcx.associated_item(def_id.to_def_id()).trait_item_def_id?;
+    let trait_ref = tcx.impl_trait_ref(tcx.local_parent(def_id))?;
+    let trait_substs = InternalSubsts::identity_for_item(tcx, trait_item_def_id);
+    let trait_substs = trait_substs.rebase_onto(tcx, trait_ref.def_id, trait_ref.substs);
+    let trait_sig = tcx.bound_fn_sig(trait_item_def_id).subst(tcx, trait_substs);
+
+    let sig_regions = |sig: ty::PolyFnSig<'tcx>| {
+        let mut regions = vec![];
+        tcx.for_each_free_region(&sig.skip_binder(), |region| regions.push(region));
+        regions
+    };
+    let impl_regions = sig_regions(tcx.fn_sig(def_id.to_def_id()));
+    let trait_regions = sig_regions(trait_sig);
+    // The signatures have a different shape if the impl's is written with normalized types.
+    if impl_regions.len() != trait_regions.len() {
+        return None;
+    }
+    let trait_region = |kind: ty::BoundRegionKind| {
+        let index = impl_regions
+            .iter()
+            .position(|region| matches!(**region, ty::ReLateBound(_, br) if br.kind == kind))?;
+        Some(trait_regions[index])
+    };
+    Some((trait_item_def_id, [trait_region(bound_regions[0])?, trait_region(bound_regions[1])?]))
+}
+
+/// Points out that the lifetimes of an implementation of the trait method `trait_item_def_id`
+/// can't be tied together, as they are the unrelated `trait_regions` in the trait. For a local
+/// trait, suggests tying them together in the trait instead, returning whether it did.
+fn note_lifetimes_fixed_by_trait<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_item_def_id: DefId,
+    trait_regions: [Region<'tcx>; 2],
+    err: &mut Diagnostic,
+) -> bool {
+    err.set_arg("method_name", tcx.item_name(trait_item_def_id));
+    err.set_arg("trait_name", tcx.item_name(tcx.parent(trait_item_def_id)));
+    err.span_note(tcx.def_span(trait_item_def_id), fluent::infer::lifetimes_fixed_by_trait_note);
+
+    let Some(local_def_id) = trait_item_def_id.as_local() else { return false };
+    let node = tcx.hir().get_by_def_id(local_def_id);
+    let (Some(fn_sig), Some(generics)) = (node.fn_sig(), node.generics()) else { return false };
+    let [Some(lifetime_a), Some(lifetime_b)] = trait_regions.map(|region| match *region {
+        ty::ReLateBound(_, br) => fn_sig
+            .decl
+            .inputs
+            .iter()
+            .find_map(|input| find_component_for_bound_region(tcx, input, &br.kind))
+            .and_then(anonymous_lifetime),
+        _ => None,
+    }) else {
+        return false;
     };
-    let mut suggestions =
-        vec![make_suggestion(lifetime_sub.span), make_suggestion(lifetime_sup.span)];
 
+    let param_name = lifetime_param_name(generics);
+    let introduce_new = param_name.is_none();
+    let param_name = param_name.map_or_else(|| "'a".to_owned(), |name| name.to_string());
+    // A new `'a` would shadow the one of the trait.
+    if introduce_new
+        && tcx.hir().get_generics(tcx.local_parent(local_def_id)).map_or(false, |generics| {
+            generics.params.iter().any(|p| p.name.ident().as_str() == param_name)
+        })
+    {
+        return false;
+    }
+
+    let mut suggestions = vec![
+        name_lifetime_suggestion(tcx, lifetime_a.span, &param_name),
+        name_lifetime_suggestion(tcx, lifetime_b.span, &param_name),
+    ];
     if introduce_new {
-        let new_param_suggestion =
-            if let Some(first) = generics.params.iter().find(|p| !p.name.ident().span.is_empty()) {
-                (first.span.shrink_to_lo(), format!("{}, ", suggestion_param_name))
-            } else {
-                (generics.span, format!("<{}>", suggestion_param_name))
-            };
+        suggestions.push(insert_generic_params(tcx, generics, &param_name));
+    }
+    err.set_arg("trait_param_name", param_name.as_str());
+    err.set_arg("trait_is_reuse", !introduce_new);
+    err.multipart_suggestion(
+        fluent::infer::lifetime_param_trait_suggestion,
+        merge_touching_suggestion_parts(suggestions),
+        Applicability::MaybeIncorrect,
+    );
+    true
+}
 
-        suggestions.push(new_param_suggestion);
+/// Points out that changing the signature of the provided method `def_id` changes it for the
+/// implementations of its trait, and which local ones there are and override it, if there are few.
+fn note_provided_method_impls(tcx: TyCtxt<'_>, def_id: LocalDefId, err: &mut Diagnostic) {
+    err.note(fluent::infer::provided_method_signature_note);
+    let trait_def_id = tcx.local_parent(def_id).to_def_id();
+    let impls: Vec<_> =
+        tcx.all_impls(trait_def_id).filter(|impl_def_id| impl_def_id.is_local()).collect();
+    if !(1..=3).contains(&impls.len()) {
+        return;
+    }
+    let mut spans =
+        MultiSpan::from_spans(impls.iter().map(|&impl_def_id| tcx.def_span(impl_def_id)).collect());
+    for impl_def_id in impls {
+        if let Some(&item_def_id) =
+            tcx.impl_item_implementor_ids(impl_def_id).get(&def_id.to_def_id())
+        {
+            let label = fluent::infer::provided_method_override_label.as_message();
+            spans.push_span_label(tcx.def_span(item_def_id), label);
+        }
     }
+    err.span_note(spans, fluent::infer::provided_method_impls_note);
+}
 
-    let mut sugg = String::from("consider introducing a named lifetime parameter");
-    if is_impl {
-        sugg.push_str(" and update trait if needed");
+/// Suggests replacing each of the argument-position `impl Trait`s in `apits` with a new named
+/// generic parameter, so that the elided lifetimes at `lifetime_spans` can be named `param_name`.
+fn suggest_apit_to_generic_param(
+    tcx: TyCtxt<'_>,
+    generics: &hir::Generics<'_>,
+    apits: &[&hir::GenericParam<'_>],
+    lifetime_spans: [Span; 2],
+    param_name: &str,
+    introduce_new: bool,
+    err: &mut Diagnostic,
+) -> bool {
+    let sm = tcx.sess.source_map();
+    // Whether a lifetime is inside of an `impl Trait` is decided by its span, as the suggestion
+    // naming the one of a `&impl Trait` is an insertion at the start of the `impl Trait`.
+    let lifetime_suggestions =
+        lifetime_spans.map(|span| (span, name_lifetime_suggestion(tcx, span, param_name)));
+    let mut used_names: Vec<_> =
+        generics.params.iter().map(|p| p.name.ident().to_string()).collect();
+
+    let mut new_params = vec![];
+    if introduce_new {
+        new_params.push(param_name.to_owned());
     }
-    err.multipart_suggestion(sugg, suggestions, Applicability::MaybeIncorrect);
+    let mut suggestions: Vec<_> = lifetime_suggestions
+        .iter()
+        .filter(|(span, _)| !apits.iter().any(|apit| apit.span.contains(*span)))
+        .map(|(_, suggestion)| suggestion.clone())
+        .collect();
+    for apit in apits {
+        let Ok(mut snippet) = sm.span_to_snippet(apit.span) else {
+            return false;
+        };
+        // Name the lifetimes in the bounds, starting from the back so that the
+        // offsets of the earlier ones stay valid.
+        let mut inner: Vec<_> = lifetime_suggestions
+            .iter()
+            .filter(|(span, _)| apit.span.contains(*span))
+            .map(|(_, suggestion)| suggestion)
+            .collect();
+        inner.sort_by_key(|(span, _)| std::cmp::Reverse(span.lo()));
+        inner.dedup_by_key(|(span, _)| *span);
+        for (span, replacement) in inner {
+            let lo = (span.lo() - apit.span.lo()).0 as usize;
+            let hi = (span.hi() - apit.span.lo()).0 as usize;
+            snippet.replace_range(lo..hi, replacement);
+        }
+        let Some(bounds) = snippet.strip_prefix("impl ") else {
+            return false;
+        };
+
+        // Prefer the initial of the (first) trait, e.g. `I` for `impl Iterator`.
+        let initial = bounds.chars().next().filter(|c| c.is_ascii_uppercase());
+        let Some(name) = initial
+            .into_iter()
+            .chain('T'..='Z')
+            .chain('A'..='S')
+            .map(String::from)
+            .find(|name| !used_names.contains(name))
+        else {
+            return false;
+        };
+        new_params.push(format!("{}: {}", name, bounds.trim()));
+        suggestions.push((apit.span, name.clone()));
+        used_names.push(name);
+    }
+
+    match written_params(generics).last() {
+        Some(last) => {
+            if introduce_new {
+                let lifetime = new_params.remove(0);
+                suggestions.push(insert_generic_params(tcx, generics, &lifetime));
+            }
+            suggestions.push((last.span.shrink_to_hi(), format!(", {}", new_params.join(", "))));
+        }
+        None => suggestions.push(insert_generic_params(tcx, generics, &new_params.join(", "))),
+    }
+
+    err.multipart_suggestion(
+        fluent::infer::lifetime_param_apit_suggestion,
+        merge_touching_suggestion_parts(suggestions),
+        Applicability::MaybeIncorrect,
+    );
+    err.note(fluent::infer::lifetime_param_apit_note);
+
+    true
+}
+
+/// The generic parameters written in the parameter list of `generics`, unlike the ones for
+/// elided lifetimes and argument-position `impl Trait`, and ones from the input of a macro
+/// expanding to the item.
+fn written_params<'hir>(
+    generics: &'hir hir::Generics<'hir>,
+) -> impl Iterator<Item = &'hir hir::GenericParam<'hir>> {
+    generics.params.iter().filter(|p| {
+        !p.is_impl_trait() && !p.name.ident().span.is_empty() && generics.span.contains(p.span)
+    })
+}
+
+/// Builds the insertion of `params` in front of the generic parameters of `generics`. Without
+/// written parameters, they go into the angle brackets, or new ones right after the name of the
+/// item, even if the span of the generics reaches over a `where` clause.
+fn insert_generic_params(
+    tcx: TyCtxt<'_>,
+    generics: &hir::Generics<'_>,
+    params: &str,
+) -> (Span, String) {
+    if let Some(first) = written_params(generics).next() {
+        return (first.span.shrink_to_lo(), format!("{}, ", params));
+    }
+    let span = generics.span;
+    match tcx.sess.source_map().span_to_snippet(span) {
+        Ok(snippet) if snippet.trim_start_matches('<').trim_start().starts_with('>') => {
+            (span.with_lo(span.lo() + BytePos(1)).shrink_to_lo(), params.to_owned())
+        }
+        Ok(snippet) if snippet.starts_with('<') => {
+            (span.with_lo(span.lo() + BytePos(1)).shrink_to_lo(), format!("{}, ", params))
+        }
+        _ => (span.shrink_to_lo(), format!("<{}>", params)),
+    }
+}
+
+/// The anonymous lifetime of a reference type, or the only anonymous lifetime argument of a
+/// path type like `Ref<'_, T>`, which is either written as `'_` or fully elided.
+fn anonymous_lifetime<'hir>(ty: &'hir hir::Ty<'hir>) -> Option<&'hir hir::Lifetime> {
+    let lifetime = match ty.kind {
+        hir::TyKind::Rptr(ref lifetime, _) => lifetime,
+        hir::TyKind::Path(hir::QPath::Resolved(None, path)) => {
+            let mut lifetimes =
+                path.segments.last()?.args().args.iter().filter_map(|arg| match arg {
+                    hir::GenericArg::Lifetime(lifetime) if lifetime.name.is_anonymous() => {
+                        Some(lifetime)
+                    }
+                    _ => None,
+                });
+            match (lifetimes.next(), lifetimes.next()) {
+                (Some(lifetime), None) => lifetime,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    lifetime.name.is_anonymous().then_some(lifetime)
+}
+
+/// Builds the replacement naming the (elided) lifetime at `span` as `name`. A written `'_` is
+/// replaced by the name. The span of a fully elided lifetime is the `&` of a reference, and for
+/// a path, it is an empty span at the start of its generic arguments, the `<` of empty generic
+/// arguments, or the name of the path if it has no generic arguments.
+pub(super) fn name_lifetime_suggestion(tcx: TyCtxt<'_>, span: Span, name: &str) -> (Span, String) {
+    if span.is_empty() {
+        return (span, format!("{}, ", name));
+    }
+    match tcx.sess.source_map().span_to_snippet(span).as_deref() {
+        Ok("'_") => (span, name.to_owned()),
+        Ok("&") => (span.shrink_to_hi(), format!("{} ", name)),
+        Ok("<") => (span.shrink_to_hi(), name.to_owned()),
+        Ok(snippet) if snippet.starts_with(|c: char| c == '_' || c.is_alphabetic()) => {
+            (span.shrink_to_hi(), format!("<{}>", name))
+        }
+        _ => (span, name.to_owned()),
+    }
+}
+
+/// A `fn(..)` pointer type or a parenthesized `Fn(..)` trait bound. Elided lifetimes inside
+/// of these are higher-ranked.
+#[derive(Clone, Copy, Debug)]
+struct FnSugar {
+    /// The span of the whole function pointer type or trait path.
+    span: Span,
+    is_fn_ptr: bool,
+    /// Where to insert a new lifetime into an existing `for<..>` binder, if there is one.
+    existing_binder: Option<Span>,
+}
+
+/// Finds the innermost `fn(..)` pointer type or `Fn(..)` trait bound in `decl` whose
+/// span contains `span`.
+fn enclosing_fn_sugar(decl: &hir::FnDecl<'_>, span: Span) -> Option<FnSugar> {
+    struct FnSugarVisitor {
+        span: Span,
+        found: Option<FnSugar>,
+    }
+
+    impl<'v> Visitor<'v> for FnSugarVisitor {
+        fn visit_ty(&mut self, ty: &'v hir::Ty<'v>) {
+            if let hir::TyKind::BareFn(bare_fn) = ty.kind && ty.span.contains(self.span) {
+                self.found = Some(FnSugar {
+                    span: ty.span,
+                    is_fn_ptr: true,
+                    existing_binder: bare_fn.generic_params.first().map(|p| p.span.shrink_to_lo()),
+                });
+            }
+            intravisit::walk_ty(self, ty);
+        }
+
+        fn visit_poly_trait_ref(
+            &mut self,
+            poly_trait_ref: &'v hir::PolyTraitRef<'v>,
+            modifier: hir::TraitBoundModifier,
+        ) {
+            let path = poly_trait_ref.trait_ref.path;
+            let is_fn_sugar = path
+                .segments
+                .last()
+                .and_then(|segment| segment.args)
+                .map_or(false, |args| args.parenthesized);
+            if is_fn_sugar && path.span.contains(self.span) {
+                self.found = Some(FnSugar {
+                    span: path.span,
+                    is_fn_ptr: false,
+                    existing_binder: poly_trait_ref
+                        .bound_generic_params
+                        .first()
+                        .map(|p| p.span.shrink_to_lo()),
+                });
+            }
+            intravisit::walk_poly_trait_ref(self, poly_trait_ref, modifier);
+        }
+    }
+
+    let mut visitor = FnSugarVisitor { span, found: None };
+    for ty in decl.inputs {
+        visitor.visit_ty(ty);
+    }
+    if let hir::FnRetTy::Return(ty) = decl.output {
+        visitor.visit_ty(ty);
+    }
+    visitor.found
+}
+
+/// Both references live in the same `fn(..)` pointer type or `Fn(..)` trait bound, so
+/// suggest naming their lifetimes with a `for<'a>` binder on that type instead of with a
+/// parameter of the enclosing function.
+fn suggest_higher_ranked_binder(
+    tcx: TyCtxt<'_>,
+    def_id: LocalDefId,
+    generics: &hir::Generics<'_>,
+    sugar: FnSugar,
+    lifetime_sub: &hir::Lifetime,
+    lifetime_sup: &hir::Lifetime,
+    err: &mut Diagnostic,
+) -> bool {
+    // The binder shadows the lifetimes of the enclosing function and of its impl or trait,
+    // so pick a fresh name.
+    let parent_generics = tcx.hir().get_generics(tcx.local_parent(def_id));
+    let Some(name) = ('a'..='z').map(|c| format!("'{}", c)).find(|name| {
+        !generics
+            .params
+            .iter()
+            .chain(parent_generics.into_iter().flat_map(|g| g.params))
+            .any(|p| p.name.ident().as_str() == name.as_str())
+    }) else {
+        return false;
+    };
+
+    let binder = match sugar.existing_binder {
+        Some(span) => (span, format!("{}, ", name)),
+        None => (sugar.span.shrink_to_lo(), format!("for<{}> ", name)),
+    };
+    let suggestions = vec![
+        binder,
+        name_lifetime_suggestion(tcx, lifetime_sub.span, &name),
+        name_lifetime_suggestion(tcx, lifetime_sup.span, &name),
+    ];
+    err.set_arg("sugar_is_fn_ptr", sugar.is_fn_ptr);
+    err.multipart_suggestion(
+        fluent::infer::higher_ranked_lifetime_suggestion,
+        merge_touching_suggestion_parts(suggestions),
+        Applicability::MaybeIncorrect,
+    );
 
     true
 }
@@ -27,19 +27,44 @@ pub fn find_anon_type<'tcx>(
 ) -> Option<(&'tcx hir::Ty<'tcx>, &'tcx hir::FnSig<'tcx>)> {
     let anon_reg = tcx.is_suitable_region(region)?;
     let hir_id = tcx.hir().local_def_id_to_hir_id(anon_reg.def_id);
-    let fn_sig = tcx.hir().get(hir_id).fn_sig()?;
+    let node = tcx.hir().get(hir_id);
+    let fn_sig = node.fn_sig()?;
 
     fn_sig
         .decl
         .inputs
         .iter()
         .find_map(|arg| find_component_for_bound_region(tcx, arg, br))
+        .or_else(|| {
+            // Lifetimes elided in an argument-position `impl Trait` show up in the bounds
+            // of its synthetic type parameter instead of in the argument type.
+            node.generics()?.predicates.iter().find_map(|predicate| match predicate {
+                hir::WherePredicate::BoundPredicate(predicate)
+                    if predicate.origin == hir::PredicateOrigin::ImplTrait =>
+                {
+                    predicate.bounds.iter().find_map(|bound| {
+                        let mut nested_visitor = FindNestedTypeVisitor {
+                            tcx,
+                            bound_region: *br,
+                            found_type: None,
+                            current_index: ty::INNERMOST,
+                        };
+                        nested_visitor.visit_param_bound(bound);
+                        nested_visitor.found_type
+                    })
+                }
+                _ => None,
+            })
+        })
         .map(|ty| (ty, fn_sig))
 }
 
 // This method creates a FindNestedTypeVisitor which returns the type corresponding
 // to the anonymous region.
-fn find_component_for_bound_region<'tcx>(
+/// Returns the part of the type `arg` which mentions the region `br`, like the `&u8` in
+/// `Vec<&u8>` or the `Ref<'_>` in `(Ref<'_>, u32)`. Only lifetimes which are arguments of a
+/// path itself count for it, so this looks through wrappers like the `Pin` in `Pin<&mut Self>`.
+pub(super) fn find_component_for_bound_region<'tcx>(
     tcx: TyCtxt<'tcx>,
     arg: &'tcx hir::Ty<'tcx>,
     br: &ty::BoundRegionKind,
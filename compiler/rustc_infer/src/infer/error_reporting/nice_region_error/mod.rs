@@ -11,6 +11,7 @@
 mod named_anon_conflict;
 mod placeholder_error;
 mod static_impl_trait;
+mod static_return;
 mod trait_impl_difference;
 mod util;
 
@@ -23,17 +24,33 @@ impl<'cx, 'tcx> InferCtxt<'cx, 'tcx> {
     pub fn try_report_nice_region_error(&self, error: &RegionResolutionError<'tcx>) -> bool {
         NiceRegionError::new(self, error.clone()).try_report().is_some()
     }
+
+    /// Reports `error` together with the error at `mirror_span`, which requires the
+    /// opposite relation between the same two anonymous regions. Returns `false` if the
+    /// two errors can't be reported as one.
+    pub fn try_report_mirrored_region_errors(
+        &self,
+        error: &RegionResolutionError<'tcx>,
+        mirror_span: Span,
+    ) -> bool {
+        let mut nice = NiceRegionError::new(self, error.clone());
+        nice.mirror_span = Some(mirror_span);
+        nice.try_report_anon_anon_conflict().is_some()
+    }
 }
 
 pub struct NiceRegionError<'cx, 'tcx> {
     infcx: &'cx InferCtxt<'cx, 'tcx>,
     error: Option<RegionResolutionError<'tcx>>,
     regions: Option<(Span, ty::Region<'tcx>, ty::Region<'tcx>)>,
+    /// The span of another error requiring the opposite relation between the two
+    /// regions, which is reported as part of this one.
+    mirror_span: Option<Span>,
 }
 
 impl<'cx, 'tcx> NiceRegionError<'cx, 'tcx> {
     pub fn new(infcx: &'cx InferCtxt<'cx, 'tcx>, error: RegionResolutionError<'tcx>) -> Self {
-        Self { infcx, error: Some(error), regions: None }
+        Self { infcx, error: Some(error), regions: None, mirror_span: None }
     }
 
     pub fn new_from_span(
@@ -42,7 +59,7 @@ pub fn new_from_span(
         sub: ty::Region<'tcx>,
         sup: ty::Region<'tcx>,
     ) -> Self {
-        Self { infcx, error: None, regions: Some((span, sub, sup)) }
+        Self { infcx, error: None, regions: Some((span, sub, sup)), mirror_span: None }
     }
 
     fn tcx(&self) -> TyCtxt<'tcx> {
@@ -9,6 +9,7 @@
 impl<'a, 'tcx> NiceRegionError<'a, 'tcx> {
     /// When given a `ConcreteFailure` for a function with parameters containing a named region and
     /// an anonymous region, emit an descriptive diagnostic error.
+    #[allow(rustc::untranslatable_diagnostic)]
     pub(super) fn try_report_named_anon_conflict(
         &self,
     ) -> Option<DiagnosticBuilder<'tcx, ErrorGuaranteed>> {
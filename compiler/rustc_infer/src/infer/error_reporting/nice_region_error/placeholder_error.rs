@@ -194,6 +194,7 @@ fn try_report_trait_placeholder_mismatch(
     //    = note: `T` must implement `...` for any two lifetimes `'1` and `'2`.
     //    = note: However, the type `T` only implements `...` for some specific lifetime `'2`.
     #[instrument(level = "debug", skip(self))]
+    #[allow(rustc::untranslatable_diagnostic)]
     fn report_trait_placeholder_mismatch(
         &self,
         vid: Option<Region<'tcx>>,
@@ -306,6 +307,7 @@ fn report_trait_placeholder_mismatch(
     /// them needs to be mentioned the closest to the placeholders.
     /// This makes the error messages read better, however at the cost of some complexity
     /// due to the number of combinations we have to deal with.
+    #[allow(rustc::untranslatable_diagnostic)]
     fn explain_actual_impl_that_was_found(
         &self,
         err: &mut Diagnostic,
@@ -20,6 +20,7 @@
 impl<'a, 'tcx> NiceRegionError<'a, 'tcx> {
     /// Print the error message for lifetime errors when the return type is a static `impl Trait`,
     /// `dyn Trait` or if a method call on a trait object introduces a static requirement.
+    #[allow(rustc::untranslatable_diagnostic)]
     pub(super) fn try_report_static_impl_trait(&self) -> Option<ErrorGuaranteed> {
         debug!("try_report_static_impl_trait(error={:?})", self.error);
         let tcx = self.tcx();
@@ -268,7 +269,6 @@ pub(super) fn try_report_static_impl_trait(&self) -> Option<ErrorGuaranteed> {
             lifetime_name,
             Some(arg),
             captures,
-            Some((param.param_ty_span, param.param_ty.to_string())),
         );
 
         let reported = err.emit();
@@ -283,7 +283,6 @@ pub fn suggest_new_region_bound(
     lifetime_name: String,
     arg: Option<String>,
     captures: String,
-    param: Option<(Span, String)>,
 ) {
     debug!("try_report_static_impl_trait: fn_return={:?}", fn_returns);
     // FIXME: account for the need of parens in `&(dyn Trait + '_)`
@@ -292,7 +291,8 @@ pub fn suggest_new_region_bound(
     let explicit = format!("you can add an explicit `{}` lifetime bound", lifetime_name);
     let explicit_static =
         arg.map(|arg| format!("explicit `'static` bound to the lifetime of {}", arg));
-    let add_static_bound = "alternatively, add an explicit `'static` bound to this reference";
+    // Requiring the argument to be `'static` instead is only right if the data it refers to is,
+    // which can't be known here, so only the bound on the return type is suggested.
     let plus_lt = format!(" + {}", lifetime_name);
     for fn_return in fn_returns {
         if fn_return.span.desugaring_kind().is_some() {
@@ -327,14 +327,6 @@ pub fn suggest_new_region_bound(
                             Applicability::MaybeIncorrect,
                         );
                     }
-                    if let Some((param_span, param_ty)) = param.clone() {
-                        err.span_suggestion_verbose(
-                            param_span,
-                            add_static_bound,
-                            param_ty,
-                            Applicability::MaybeIncorrect,
-                        );
-                    }
                 } else if opaque
                     .bounds
                     .iter()
@@ -390,14 +382,6 @@ pub fn suggest_new_region_bound(
                             Applicability::MaybeIncorrect,
                         );
                     }
-                    if let Some((param_span, param_ty)) = param.clone() {
-                        err.span_suggestion_verbose(
-                            param_span,
-                            add_static_bound,
-                            param_ty,
-                            Applicability::MaybeIncorrect,
-                        );
-                    }
                 }
                 _ => {}
             },
@@ -504,6 +488,7 @@ fn find_impl_on_dyn_trait(
         self.suggest_constrain_dyn_trait_in_impl(err, &v.0, ident, self_ty)
     }
 
+    #[allow(rustc::untranslatable_diagnostic)]
     fn suggest_constrain_dyn_trait_in_impl(
         &self,
         err: &mut Diagnostic,
@@ -0,0 +1,95 @@
+//! Checking whether the values a function returns are `'static`, so that a `'static` lifetime
+//! is only suggested for its return type when that is right.
+
+use rustc_ast::LitKind;
+use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::symbol::sym;
+
+/// Why a returned value is `'static`, from the least to the most certain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum StaticReturn {
+    /// The result of `Box::leak`.
+    Leaked,
+    /// A `static` or `const` item, or a reference to one or to a literal.
+    Constant,
+    /// A string or byte string literal.
+    Literal,
+}
+
+/// Returns why all the values returned by the body of `fn_def_id` are `'static`, if they are.
+///
+/// This only looks at the returned expressions themselves, so it misses values that are
+/// `'static` because of what they are computed from.
+pub(super) fn static_return_value(tcx: TyCtxt<'_>, fn_def_id: LocalDefId) -> Option<StaticReturn> {
+    let body = tcx.hir().body(tcx.hir().maybe_body_owned_by(fn_def_id)?);
+    let hir::ExprKind::Block(block, _) = body.value.kind else {
+        return None;
+    };
+    let mut finder = ReturnFinder(vec![]);
+    finder.visit_block(block);
+    finder.0.extend(block.expr);
+    finder.0.iter().map(|expr| static_value(tcx, expr)).min().flatten()
+}
+
+fn static_value(tcx: TyCtxt<'_>, expr: &hir::Expr<'_>) -> Option<StaticReturn> {
+    match expr.kind {
+        hir::ExprKind::Block(block, _) => static_value(tcx, block.expr?),
+        hir::ExprKind::Lit(ref lit) => match lit.node {
+            LitKind::Str(..) | LitKind::ByteStr(..) => Some(StaticReturn::Literal),
+            _ => None,
+        },
+        // Borrows of literals and constants are promoted to `'static`.
+        hir::ExprKind::AddrOf(hir::BorrowKind::Ref, hir::Mutability::Not, inner)
+            if matches!(inner.kind, hir::ExprKind::Lit(_)) || is_constant(inner) =>
+        {
+            Some(StaticReturn::Constant)
+        }
+        hir::ExprKind::Path(_) if is_constant(expr) => Some(StaticReturn::Constant),
+        hir::ExprKind::Call(callee, [_]) if is_box_leak(tcx, callee) => Some(StaticReturn::Leaked),
+        _ => None,
+    }
+}
+
+fn is_constant(expr: &hir::Expr<'_>) -> bool {
+    matches!(
+        expr.kind,
+        hir::ExprKind::Path(hir::QPath::Resolved(
+            None,
+            hir::Path {
+                res: Res::Def(DefKind::Const | DefKind::AssocConst | DefKind::Static(_), _),
+                ..
+            },
+        ))
+    )
+}
+
+/// Whether `callee` is a path to `Box::leak`.
+fn is_box_leak(tcx: TyCtxt<'_>, callee: &hir::Expr<'_>) -> bool {
+    let hir::ExprKind::Path(hir::QPath::TypeRelative(qself, segment)) = callee.kind else {
+        return false;
+    };
+    segment.ident.name == sym::leak
+        && matches!(
+            qself.kind,
+            hir::TyKind::Path(hir::QPath::Resolved(
+                None,
+                hir::Path { res: Res::Def(DefKind::Struct, def_id), .. },
+            )) if Some(*def_id) == tcx.lang_items().owned_box()
+        )
+}
+
+/// Collects the values of the `return` expressions of a body, outside of closures in it.
+struct ReturnFinder<'hir>(Vec<&'hir hir::Expr<'hir>>);
+
+impl<'hir> Visitor<'hir> for ReturnFinder<'hir> {
+    fn visit_expr(&mut self, expr: &'hir hir::Expr<'hir>) {
+        if let hir::ExprKind::Ret(Some(value)) = expr.kind {
+            self.0.push(value);
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
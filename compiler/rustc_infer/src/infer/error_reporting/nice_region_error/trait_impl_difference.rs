@@ -1,13 +1,17 @@
 //! Error Reporting for `impl` items that do not match the obligations from their `trait`.
 
+use crate::infer::error_reporting::nice_region_error::different_lifetimes::name_lifetime_suggestion;
+use crate::infer::error_reporting::nice_region_error::static_return::{
+    static_return_value, StaticReturn,
+};
 use crate::infer::error_reporting::nice_region_error::NiceRegionError;
 use crate::infer::lexical_region_resolve::RegionResolutionError;
 use crate::infer::Subtype;
 use crate::traits::ObligationCauseCode::CompareImplItemObligation;
-use rustc_errors::{ErrorGuaranteed, MultiSpan};
+use rustc_errors::{fluent, Applicability, Diagnostic, ErrorGuaranteed, MultiSpan};
 use rustc_hir as hir;
 use rustc_hir::def::Res;
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::intravisit::Visitor;
 use rustc_middle::hir::nested_filter;
 use rustc_middle::ty::print::RegionHighlightMode;
@@ -33,22 +37,30 @@ pub(super) fn try_report_impl_not_conforming_to_trait(&self) -> Option<ErrorGuar
             && let (Subtype(sup_trace), Subtype(sub_trace)) = (&sup_origin, &sub_origin)
             && let sub_expected_found @ Some((sub_expected, sub_found)) = sub_trace.values.ty()
             && let sup_expected_found @ Some(_) = sup_trace.values.ty()
-            && let CompareImplItemObligation { trait_item_def_id, .. } = sub_trace.cause.code()
+            && let CompareImplItemObligation { impl_item_def_id, trait_item_def_id, .. } =
+                sub_trace.cause.code()
             && sup_expected_found == sub_expected_found
         {
-            let guar =
-                self.emit_err(var_origin.span(), sub_expected, sub_found, *trait_item_def_id);
+            let guar = self.emit_err(
+                var_origin.span(),
+                sub_expected,
+                sub_found,
+                *trait_item_def_id,
+                *impl_item_def_id,
+            );
             return Some(guar);
         }
         None
     }
 
+    #[allow(rustc::untranslatable_diagnostic)]
     fn emit_err(
         &self,
         sp: Span,
         expected: Ty<'tcx>,
         found: Ty<'tcx>,
         trait_def_id: DefId,
+        impl_def_id: LocalDefId,
     ) -> ErrorGuaranteed {
         let trait_sp = self.tcx().def_span(trait_def_id);
         let mut err = self
@@ -129,8 +141,53 @@ fn visit_region(&mut self, r: ty::Region<'tcx>) -> ControlFlow<Self::BreakTy> {
                  argument, the other inputs and its output",
             );
         }
+        self.suggest_static_return(&mut err, expected, impl_def_id);
         err.emit()
     }
+
+    /// Suggests writing out the `'static` lifetime of a reference returned by the `impl` if the
+    /// `trait` requires it, the `impl` elides it and the `impl` only returns `'static` data.
+    fn suggest_static_return(&self, err: &mut Diagnostic, expected: Ty<'tcx>, impl_fn: LocalDefId) {
+        let tcx = self.tcx();
+        if tcx.sess.opts.unstable_opts.no_suggestions {
+            return;
+        }
+        let ty::FnPtr(expected_sig) = expected.kind() else {
+            return;
+        };
+        let ty::Ref(expected_region, ..) = *expected_sig.output().skip_binder().kind() else {
+            return;
+        };
+        if !expected_region.is_static() {
+            return;
+        }
+        let hir_id = tcx.hir().local_def_id_to_hir_id(impl_fn);
+        let Some(&hir::FnRetTy::Return(ret_ty)) =
+            tcx.hir().fn_decl_by_hir_id(hir_id).map(|decl| &decl.output) else {
+            return;
+        };
+        let hir::TyKind::Rptr(lifetime, _) = ret_ty.kind else {
+            return;
+        };
+        if !lifetime.name.is_anonymous() {
+            return;
+        }
+        let Some(returned) = static_return_value(tcx, impl_fn) else {
+            return;
+        };
+        let applicability = if returned == StaticReturn::Literal {
+            Applicability::MachineApplicable
+        } else {
+            Applicability::MaybeIncorrect
+        };
+        let (span, suggestion) = name_lifetime_suggestion(tcx, lifetime.span, "'static");
+        err.span_suggestion_verbose(
+            span,
+            fluent::infer::static_return_suggestion,
+            suggestion,
+            applicability,
+        );
+    }
 }
 
 struct TypeParamSpanVisitor<'tcx> {
@@ -1,31 +1,21 @@
 use crate::infer::error_reporting::{note_and_explain_region, ObligationCauseExt};
-use crate::infer::{self, InferCtxt, SubregionOrigin};
-use rustc_errors::{struct_span_err, Diagnostic, DiagnosticBuilder, ErrorGuaranteed};
+use crate::infer::{self, InferCtxt, SubregionOrigin, TypeTrace};
+use rustc_errors::{
+    fluent, struct_span_err, Diagnostic, DiagnosticBuilder, DiagnosticMessage, ErrorGuaranteed,
+    IntoDiagnosticArg,
+};
 use rustc_middle::traits::ObligationCauseCode;
 use rustc_middle::ty::error::TypeError;
 use rustc_middle::ty::{self, Region};
 
 impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
+    #[allow(rustc::untranslatable_diagnostic)]
     pub(super) fn note_region_origin(&self, err: &mut Diagnostic, origin: &SubregionOrigin<'tcx>) {
-        let mut label_or_note = |span, msg: &str| {
-            let sub_count = err.children.iter().filter(|d| d.span.is_dummy()).count();
-            let expanded_sub_count = err.children.iter().filter(|d| !d.span.is_dummy()).count();
-            let span_is_primary = err.span.primary_spans().iter().all(|&sp| sp == span);
-            if span_is_primary && sub_count == 0 && expanded_sub_count == 0 {
-                err.span_label(span, msg);
-            } else if span_is_primary && expanded_sub_count == 0 {
-                err.note(msg);
-            } else {
-                err.span_note(span, msg);
-            }
-        };
         match *origin {
             infer::Subtype(ref trace) => {
                 if let Some((expected, found)) = self.values_str(trace.values) {
-                    label_or_note(
-                        trace.cause.span,
-                        &format!("...so that the {}", trace.cause.as_requirement_str()),
-                    );
+                    let msg = self.requirement_msg(fluent::infer::subtype, trace);
+                    err.label_or_note(trace.cause.span, msg);
 
                     err.note_expected_found(&"", expected, &"", found);
                 } else {
@@ -33,44 +23,42 @@ pub(super) fn note_region_origin(&self, err: &mut Diagnostic, origin: &Subregion
                     // handling of region checking when type errors are present is
                     // *terrible*.
 
-                    label_or_note(
-                        trace.cause.span,
-                        &format!("...so that {}", trace.cause.as_requirement_str()),
-                    );
+                    let msg = self.requirement_msg(fluent::infer::subtype_2, trace);
+                    err.label_or_note(trace.cause.span, msg);
                 }
             }
             infer::Reborrow(span) => {
-                label_or_note(span, "...so that reference does not outlive borrowed content");
+                err.label_or_note(span, "...so that reference does not outlive borrowed content");
             }
             infer::ReborrowUpvar(span, ref upvar_id) => {
                 let var_name = self.tcx.hir().name(upvar_id.var_path.hir_id);
-                label_or_note(span, &format!("...so that closure can access `{}`", var_name));
+                err.label_or_note(span, format!("...so that closure can access `{}`", var_name));
             }
             infer::RelateObjectBound(span) => {
-                label_or_note(span, "...so that it can be closed over into an object");
+                err.label_or_note(span, "...so that it can be closed over into an object");
             }
             infer::DataBorrowed(ty, span) => {
-                label_or_note(
+                err.label_or_note(
                     span,
-                    &format!(
+                    format!(
                         "...so that the type `{}` is not borrowed for too long",
                         self.ty_to_string(ty)
                     ),
                 );
             }
             infer::ReferenceOutlivesReferent(ty, span) => {
-                label_or_note(
+                err.label_or_note(
                     span,
-                    &format!(
+                    format!(
                         "...so that the reference type `{}` does not outlive the data it points at",
                         self.ty_to_string(ty)
                     ),
                 );
             }
             infer::RelateParamBound(span, t, opt_span) => {
-                label_or_note(
+                err.label_or_note(
                     span,
-                    &format!(
+                    format!(
                         "...so that the type `{}` will meet its required lifetime bounds{}",
                         self.ty_to_string(t),
                         if opt_span.is_some() { "..." } else { "" },
@@ -81,13 +69,13 @@ pub(super) fn note_region_origin(&self, err: &mut Diagnostic, origin: &Subregion
                 }
             }
             infer::RelateRegionParamBound(span) => {
-                label_or_note(
+                err.label_or_note(
                     span,
                     "...so that the declared lifetime parameter bounds are satisfied",
                 );
             }
             infer::CompareImplItemObligation { span, .. } => {
-                label_or_note(
+                err.label_or_note(
                     span,
                     "...so that the definition in impl matches the definition from the trait",
                 );
@@ -98,6 +86,21 @@ pub(super) fn note_region_origin(&self, err: &mut Diagnostic, origin: &Subregion
         }
     }
 
+    /// Translates `message`, which mentions the requirement of `trace`, right away, as this is
+    /// done for both origins of a conflict between regions, with their own requirements.
+    pub(super) fn requirement_msg(
+        &self,
+        message: DiagnosticMessage,
+        trace: &TypeTrace<'tcx>,
+    ) -> String {
+        let requirement = trace.cause.as_requirement_kind().into_diagnostic_arg();
+        self.tcx
+            .sess
+            .diagnostic()
+            .eagerly_translate_to_string(message, [("requirement".into(), requirement)].into_iter())
+    }
+
+    #[allow(rustc::untranslatable_diagnostic)]
     pub(super) fn report_concrete_failure(
         &self,
         origin: SubregionOrigin<'tcx>,
@@ -377,6 +380,7 @@ pub(super) fn report_concrete_failure(
         }
     }
 
+    #[allow(rustc::untranslatable_diagnostic)]
     pub(super) fn report_placeholder_failure(
         &self,
         placeholder_origin: SubregionOrigin<'tcx>,
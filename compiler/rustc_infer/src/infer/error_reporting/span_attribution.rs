@@ -0,0 +1,123 @@
+//! Mapping spans from inside closures, `async` blocks and other desugarings back to what the
+//! user wrote, for diagnostics which are computed on the lowered code.
+
+use rustc_hir as hir;
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_hir::lang_items::LangItem;
+use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::{BytePos, DesugaringKind, ExpnKind, Span};
+
+/// Where a span from the lowered code of a body comes from in the source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpanAttribution {
+    /// The span outside of all desugarings, e.g. the whole `expr.await` for a span of its
+    /// expansion. Spans from macro expansions are kept as they are.
+    pub original: Span,
+    /// The innermost desugaring the span came from, if any.
+    pub desugaring_kind: Option<DesugaringKind>,
+    /// The header of the innermost closure or `async` block containing `original`, like
+    /// `move |x|` or `async move`.
+    pub enclosing_construct_span: Option<Span>,
+}
+
+/// Returns where `span`, a span from the body of `body_owner` or of a closure or `async`
+/// block in it, comes from in the source.
+pub fn user_facing_span(tcx: TyCtxt<'_>, span: Span, body_owner: LocalDefId) -> SpanAttribution {
+    let (original, desugaring_kind) = peel_desugarings(span);
+    let typeck_root = tcx.typeck_root_def_id(body_owner.to_def_id()).expect_local();
+    let enclosing_construct_span = tcx.hir().maybe_body_owned_by(typeck_root).and_then(|body_id| {
+        let mut finder = ConstructFinder { tcx, constructs: vec![] };
+        finder.visit_body(tcx.hir().body(body_id));
+        innermost_construct(&finder.constructs, original)
+    });
+    SpanAttribution { original, desugaring_kind, enclosing_construct_span }
+}
+
+/// Walks out of the desugarings `span` is from, returning the span they were expanded from and
+/// the kind of the innermost one.
+fn peel_desugarings(mut span: Span) -> (Span, Option<DesugaringKind>) {
+    let mut innermost = None;
+    loop {
+        let expn_data = span.ctxt().outer_expn_data();
+        let ExpnKind::Desugaring(kind) = expn_data.kind else {
+            return (span, innermost);
+        };
+        innermost.get_or_insert(kind);
+        span = expn_data.call_site;
+    }
+}
+
+/// A closure or `async` block.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Construct {
+    /// The span of its body.
+    pub(super) body: Span,
+    /// The span of its header, i.e. its parameters or the `async` keyword.
+    pub(super) header: Span,
+}
+
+/// Returns the header of the innermost construct whose body contains `span`.
+pub(super) fn innermost_construct(constructs: &[Construct], span: Span) -> Option<Span> {
+    constructs
+        .iter()
+        .filter(|construct| construct.body.contains(span))
+        .min_by_key(|construct| construct.body.hi() - construct.body.lo())
+        .map(|construct| construct.header)
+}
+
+/// Returns the span to report an error which failed at `failure_span` at, given the header of
+/// the innermost construct around where it comes from. That header is only used if it is
+/// nested in the span, as the span is already more precise otherwise.
+pub(super) fn attribute_to_header(failure_span: Span, header: Option<Span>) -> Span {
+    match header {
+        Some(header) if failure_span.contains(header) && failure_span.lo() < header.lo() => header,
+        _ => failure_span,
+    }
+}
+
+struct ConstructFinder<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    constructs: Vec<Construct>,
+}
+
+impl<'tcx> Visitor<'tcx> for ConstructFinder<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        match expr.kind {
+            // `async` blocks are lowered to a call of `from_generator` with a generator which
+            // only has the span of the block, so the `async` keyword is only part of the span of
+            // the call. The body of an `async fn` has the span of the body for both, so it has
+            // no header.
+            hir::ExprKind::Call(
+                hir::Expr {
+                    kind: hir::ExprKind::Path(hir::QPath::LangItem(LangItem::FromGenerator, ..)),
+                    ..
+                },
+                [generator],
+            ) => {
+                let header = expr.span.until(generator.span);
+                let snippet = self.tcx.sess.source_map().span_to_snippet(header);
+                let trimmed = snippet.as_deref().map_or(0, |snippet| snippet.trim_end().len());
+                let header = header.with_hi(header.lo() + BytePos(trimmed as u32));
+                if !header.is_empty() {
+                    self.constructs.push(Construct { body: generator.span, header });
+                }
+            }
+            hir::ExprKind::Closure(&hir::Closure { body, fn_decl_span, .. }) => {
+                let body = self.tcx.hir().body(body);
+                if !matches!(body.generator_kind, Some(hir::GeneratorKind::Async(_))) {
+                    self.constructs.push(Construct { body: body.value.span, header: fn_decl_span });
+                }
+            }
+            _ => {}
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
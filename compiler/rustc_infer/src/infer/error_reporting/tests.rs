@@ -0,0 +1,63 @@
+use super::span_attribution::{attribute_to_header, innermost_construct, Construct};
+use super::REQUIREMENT_KINDS;
+use rustc_span::{BytePos, Span};
+
+const INFER_FTL: &str = include_str!("../../../../rustc_error_messages/locales/en-US/infer.ftl");
+
+/// Returns the keys of the variants of the select expression in the message `id`.
+fn select_variants(id: &str) -> Vec<&'static str> {
+    let start = INFER_FTL
+        .find(&format!("\n{} = ", id))
+        .unwrap_or_else(|| panic!("no message `{}` in `infer.ftl`", id));
+    INFER_FTL[start + 1..]
+        .lines()
+        .skip(1)
+        .take_while(|line| line.starts_with(' '))
+        .filter_map(|line| {
+            let line = line.trim_start().trim_start_matches('*');
+            let key = line.strip_prefix('[')?;
+            Some(&key[..key.find(']')?])
+        })
+        .collect()
+}
+
+#[test]
+fn requirement_kinds_have_wording() {
+    let mut expected = REQUIREMENT_KINDS.to_vec();
+    expected.sort_unstable();
+    let mut variants = select_variants("infer_requirement");
+    variants.sort_unstable();
+    assert_eq!(variants, expected, "select arms of `infer_requirement` don't match the kinds");
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+#[test]
+fn innermost_construct_is_picked() {
+    // `async { async { x } }`, with the closure `|| y` next to the inner block.
+    let outer = Construct { body: span(6, 40), header: span(0, 5) };
+    let inner = Construct { body: span(14, 20), header: span(8, 13) };
+    let sibling = Construct { body: span(25, 30), header: span(22, 24) };
+    let constructs = [outer, inner, sibling];
+
+    assert_eq!(innermost_construct(&constructs, span(16, 17)), Some(inner.header));
+    assert_eq!(innermost_construct(&constructs, span(26, 27)), Some(sibling.header));
+    assert_eq!(innermost_construct(&constructs, span(21, 22)), Some(outer.header));
+    // Spans of the headers themselves aren't inside of the constructs' bodies.
+    assert_eq!(innermost_construct(&constructs, span(8, 13)), Some(outer.header));
+    assert_eq!(innermost_construct(&constructs, span(41, 42)), None);
+}
+
+#[test]
+fn only_nested_headers_are_attributed_to() {
+    // `async { async { x } }` failing at the outer block, whose `async` is at `0..5`.
+    let failure_span = span(0, 30);
+    assert_eq!(attribute_to_header(failure_span, Some(span(8, 13))), span(8, 13));
+    // The header of the block the error is reported for doesn't tell any more than its span.
+    assert_eq!(attribute_to_header(failure_span, Some(span(0, 5))), failure_span);
+    // Neither do headers of constructs which aren't part of the span.
+    assert_eq!(attribute_to_header(span(8, 20), Some(span(0, 5))), span(8, 20));
+    assert_eq!(attribute_to_header(failure_span, None), failure_span);
+}
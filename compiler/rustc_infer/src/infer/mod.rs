@@ -10,11 +10,11 @@
 
 use crate::traits::{self, ObligationCause, PredicateObligations, TraitEngine, TraitEngineExt};
 
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexMap};
 use rustc_data_structures::sync::Lrc;
 use rustc_data_structures::undo_log::Rollback;
 use rustc_data_structures::unify as ut;
-use rustc_errors::{DiagnosticBuilder, ErrorGuaranteed};
+use rustc_errors::{DiagnosticBuilder, DiagnosticId, ErrorGuaranteed};
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_middle::infer::canonical::{Canonical, CanonicalVarValues};
 use rustc_middle::infer::unify_key::{ConstVarValue, ConstVariableValue};
@@ -38,6 +38,7 @@
 use std::fmt;
 
 use self::combine::CombineFields;
+use self::error_reporting::InferenceErrorBudget;
 use self::free_regions::RegionRelations;
 use self::lexical_region_resolve::LexicalRegionResolutions;
 use self::outlives::env::OutlivesEnvironment;
@@ -302,6 +303,21 @@ pub struct InferCtxt<'a, 'tcx> {
 
     pub reported_closure_mismatch: RefCell<FxHashSet<(Span, Option<Span>)>>,
 
+    /// The spans of the `expr?`s reported as unable to convert their residual into the `()`
+    /// returned by `main` or a test function. Ambiguous obligations caused by them are fallout
+    /// of those errors, and aren't reported.
+    pub question_mark_errors: RefCell<Vec<Span>>,
+
+    /// The normalization cycles found so far, each as the projections starting with the one
+    /// which had to be normalized again while normalizing the others. The projection cache
+    /// only remembers that a projection is part of a cycle, but errors about the ambiguity
+    /// this leads to also point out the cycle itself.
+    pub normalization_cycles: RefCell<Vec<Vec<ty::ProjectionTy<'tcx>>>>,
+
+    /// The type annotation errors emitted for each function and error code, to summarize
+    /// them once there are too many, see `charge_inference_error_budget`.
+    inference_error_budgets: RefCell<FxIndexMap<(LocalDefId, DiagnosticId), InferenceErrorBudget>>,
+
     /// When an error occurs, we want to avoid reporting "derived"
     /// errors that are due to this original failure. Normally, we
     /// handle this with the `err_count_on_creation` count, which
@@ -624,6 +640,9 @@ pub fn enter<R>(&mut self, f: impl for<'a> FnOnce(InferCtxt<'a, 'tcx>) -> R) ->
             evaluation_cache: Default::default(),
             reported_trait_errors: Default::default(),
             reported_closure_mismatch: Default::default(),
+            question_mark_errors: Default::default(),
+            normalization_cycles: Default::default(),
+            inference_error_budgets: Default::default(),
             tainted_by_errors_flag: Cell::new(false),
             err_count_on_creation: tcx.sess.err_count(),
             in_snapshot: Cell::new(false),
@@ -88,6 +88,7 @@ pub fn replace_opaque_types_with_inference_vars<T: TypeFoldable<'tcx>>(
         InferOk { value, obligations }
     }
 
+    #[allow(rustc::untranslatable_diagnostic)]
     pub fn handle_opaque_type(
         &self,
         a: Ty<'tcx>,
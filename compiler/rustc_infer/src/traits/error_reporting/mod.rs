@@ -2,15 +2,16 @@
 
 use crate::infer::InferCtxt;
 use rustc_data_structures::fx::FxHashSet;
-use rustc_errors::{struct_span_err, DiagnosticBuilder, ErrorGuaranteed, MultiSpan};
+use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder, ErrorGuaranteed, MultiSpan};
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_middle::ty::TyCtxt;
-use rustc_span::Span;
+use rustc_span::{BytePos, Span};
 use std::fmt;
 use std::iter;
 
 impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
+    #[allow(rustc::untranslatable_diagnostic)]
     pub fn report_extra_impl_obligation(
         &self,
         error_span: Span,
@@ -32,10 +33,36 @@ pub fn report_extra_impl_obligation(
 
         err.span_label(error_span, format!("impl has extra requirement {}", requirement));
 
+        if let Some(generics) = self.tcx.hir().get_generics(impl_item_def_id)
+            && let Some(mut removal_span) = generics.span_for_where_clause_bound_removal(error_span)
+        {
+            if removal_span == generics.where_clause_span {
+                // Also remove the whitespace before `where`, unless the clause is on its own line.
+                let sm = self.tcx.sess.source_map();
+                if let Ok(prev) = sm.span_to_prev_source(removal_span) {
+                    let trimmed = prev.trim_end_matches([' ', '\t']);
+                    if !trimmed.ends_with('\n') {
+                        let len = (prev.len() - trimmed.len()) as u32;
+                        removal_span = removal_span.with_lo(removal_span.lo() - BytePos(len));
+                    }
+                }
+            }
+            err.span_suggestion_verbose(
+                removal_span,
+                format!(
+                    "consider removing the extra requirement, as `{}` in the trait doesn't have it",
+                    self.tcx.def_path_str(trait_item_def_id)
+                ),
+                "",
+                Applicability::MaybeIncorrect,
+            );
+        }
+
         err
     }
 }
 
+#[allow(rustc::untranslatable_diagnostic)]
 pub fn report_object_safety_error<'tcx>(
     tcx: TyCtxt<'tcx>,
     span: Span,
@@ -418,7 +418,14 @@ fn check_expr(&mut self, cx: &LateContext<'_>, expr: &Expr<'_>) {
             debug!(?ty);
             if let Some(adt_def) = ty.ty_adt_def() &&
                 let Some(name) =  cx.tcx.get_diagnostic_name(adt_def.did()) &&
-                matches!(name, sym::DiagnosticMessage | sym::SubdiagnosticMessage)
+                matches!(
+                    name,
+                    sym::DiagnosticMessage
+                        | sym::SubdiagnosticMessage
+                        | sym::SuggestionMessage
+                        | sym::LabelMessage
+                        | sym::NoteMessage
+                )
             {
                 found_diagnostic_message = true;
                 break;
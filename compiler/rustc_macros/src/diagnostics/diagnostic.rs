@@ -24,6 +24,7 @@ pub(crate) fn new(diag: syn::Ident, sess: syn::Ident, structure: Structure<'a>)
                 kind: None,
                 code: None,
                 slug: None,
+                no_dedup: None,
             },
             sess,
             structure,
@@ -115,6 +116,7 @@ pub(crate) fn into_tokens(self) -> TokenStream {
             gen impl<'__session_diagnostic_sess> rustc_session::SessionDiagnostic<'__session_diagnostic_sess, #param_ty>
                     for @Self
             {
+                #[track_caller]
                 fn into_diagnostic(
                     self,
                     #sess: &'__session_diagnostic_sess rustc_session::parse::ParseSess
@@ -142,6 +144,7 @@ pub(crate) fn new(diag: syn::Ident, structure: Structure<'a>) -> Self {
                 kind: None,
                 code: None,
                 slug: None,
+                no_dedup: None,
             },
             structure,
         }
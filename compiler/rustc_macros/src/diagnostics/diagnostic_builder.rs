@@ -5,8 +5,9 @@
     DiagnosticDeriveError,
 };
 use crate::diagnostics::utils::{
-    report_error_if_not_applied_to_span, report_type_error, type_is_unit, type_matches_path,
-    Applicability, FieldInfo, FieldInnerTy, HasFieldMap, SetOnce,
+    report_error_if_not_applied_to_span, report_type_error, suggestion_span, type_is_unit,
+    type_matches_path, Applicability, FieldInfo, FieldInnerTy, HasFieldMap, SetOnce,
+    SuggestionPosition,
 };
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
@@ -18,6 +19,11 @@
 };
 use synstructure::{BindingInfo, Structure};
 
+/// Whether `field` is a `#[subdiagnostic]`.
+fn is_subdiagnostic(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| attr.path.segments.last().unwrap().ident == "subdiagnostic")
+}
+
 /// What kind of diagnostic is being derived - an error, a warning or a lint?
 #[derive(Copy, Clone)]
 pub(crate) enum DiagnosticDeriveKind {
@@ -59,6 +65,9 @@ pub(crate) struct DiagnosticDeriveBuilder {
     /// Error codes are a optional part of the struct attribute - this is only set to detect
     /// multiple specifications.
     pub code: Option<(String, proc_macro::Span)>,
+    /// `no_dedup` is an optional part of the struct attribute - this is only set to detect
+    /// multiple specifications.
+    pub no_dedup: Option<((), proc_macro::Span)>,
 }
 
 impl HasFieldMap for DiagnosticDeriveBuilder {
@@ -108,11 +117,65 @@ pub fn body<'s>(&mut self, structure: &mut Structure<'s>) -> (TokenStream, Token
         // argument to the diagnostic so that it can be referred to by Fluent messages.
         let args = structure
             .filter(|field_binding| needs_moved.contains(&field_binding.binding))
-            .each(|field_binding| self.generate_field_attrs_code(field_binding));
+            .each_variant(|variant| {
+                let (subdiagnostics, others): (Vec<_>, Vec<_>) =
+                    variant.bindings().iter().partition(|binding| is_subdiagnostic(binding.ast()));
+                let others: TokenStream = others
+                    .into_iter()
+                    .map(|binding| self.generate_field_attrs_code(binding))
+                    .collect();
+                let subdiagnostics = self.generate_subdiagnostics_code(&subdiagnostics);
+                let diag = &self.diag;
+                // Labels are shown in source order, whichever fields they come from.
+                quote! {
+                    #others
+                    #subdiagnostics
+                    #diag.span.sort_span_labels();
+                }
+            });
 
         (attrs, args)
     }
 
+    /// Generates the calls adding the `#[subdiagnostic]` fields to the diagnostic, ordered by
+    /// the `AddSubdiagnostic::PRIORITY` of their types and then by the order of the fields, so
+    /// that e.g. moving a field with a multipart suggestion before one with a single-span
+    /// suggestion can't reorder the output.
+    fn generate_subdiagnostics_code(&self, bindings: &[&BindingInfo<'_>]) -> TokenStream {
+        if bindings.is_empty() {
+            return TokenStream::new();
+        }
+
+        let diag = &self.diag;
+        let (slots, calls): (Vec<_>, Vec<_>) = bindings
+            .iter()
+            .enumerate()
+            .map(|(i, binding)| {
+                let slot = format_ident!("__subdiagnostic_{}", i);
+                let field = binding.ast();
+                let inner_ty = FieldInnerTy::from_type(&field.ty);
+                let ty = inner_ty.inner_type().unwrap_or(&field.ty);
+                let call = inner_ty.with(binding, quote! { #diag.subdiagnostic(#binding); });
+                let slot_init = quote! { let mut #slot = Some(#binding); };
+                let call = quote! {
+                    if <#ty as rustc_errors::AddSubdiagnostic>::PRIORITY == __priority {
+                        if let Some(#binding) = #slot.take() {
+                            #call
+                        }
+                    }
+                };
+                (slot_init, call)
+            })
+            .unzip();
+
+        quote! {
+            #(#slots)*
+            for __priority in rustc_errors::SubdiagnosticPriority::ALL {
+                #(#calls)*
+            }
+        }
+    }
+
     /// Returns `true` if `field` should generate a `set_arg` call rather than any other diagnostic
     /// call (like `span_label`).
     fn should_generate_set_arg(&self, field: &Field) -> bool {
@@ -126,12 +189,6 @@ fn needs_move(&self, field: &Field) -> bool {
         let is_multispan = type_matches_path(&field.ty, &["rustc_errors", "MultiSpan"]);
         // FIXME(davidtwco): better support for one field needing to be in the by-move and
         // by-ref branches.
-        let is_subdiagnostic = field
-            .attrs
-            .iter()
-            .map(|attr| attr.path.segments.last().unwrap().ident.to_string())
-            .any(|attr| attr == "subdiagnostic");
-
         // `set_arg` calls take their argument by-move..
         generates_set_arg
             // If this is a `MultiSpan` field then it needs to be moved to be used by any
@@ -139,7 +196,7 @@ fn needs_move(&self, field: &Field) -> bool {
             || is_multispan
             // If this a `#[subdiagnostic]` then it needs to be moved as the other diagnostic is
             // unlikely to be `Copy`..
-            || is_subdiagnostic
+            || is_subdiagnostic(field)
     }
 
     /// Establishes state in the `DiagnosticDeriveBuilder` resulting from the struct
@@ -222,7 +279,7 @@ fn generate_structure_code_for_attr(
             };
         }
 
-        // Remaining attributes are optional, only `code = ".."` at the moment.
+        // Remaining attributes are optional, `code = ".."` and `no_dedup`.
         let mut tokens = Vec::new();
         for nested_attr in nested_iter {
             let meta = match nested_attr {
@@ -232,6 +289,13 @@ fn generate_structure_code_for_attr(
 
             let path = meta.path();
             let nested_name = path.segments.last().unwrap().ident.to_string();
+            if matches!(meta, Meta::Path(_)) && nested_name == "no_dedup" {
+                self.no_dedup.set_once(((), path.span().unwrap()));
+                tokens.push(quote! {
+                    #diag.set_no_dedup();
+                });
+                continue;
+            }
             // Struct attributes are only allowed to be applied once, and the diagnostic
             // changes will be set in the initialisation code.
             if let Meta::NameValue(MetaNameValue { lit: syn::Lit::Str(s), .. }) = &meta {
@@ -245,7 +309,10 @@ fn generate_structure_code_for_attr(
                         });
                     }
                     _ => invalid_nested_attr(attr, &nested_attr)
-                        .help("only `code` is a valid nested attributes following the slug")
+                        .help(
+                            "only `code` and `no_dedup` are valid nested attributes following \
+                             the slug",
+                        )
                         .emit(),
                 }
             } else {
@@ -271,6 +338,19 @@ fn generate_field_attrs_code(&mut self, binding_info: &BindingInfo<'_>) -> Token
             };
         }
 
+        // Two labels on the same field are shown on the same span, in the order of the
+        // attributes, which is rarely what was intended.
+        let mut labels =
+            field.attrs.iter().filter(|attr| attr.path.segments.last().unwrap().ident == "label");
+        if let (Some(_), Some(second)) = (labels.next(), labels.next()) {
+            span_err(second.span().unwrap(), "`#[label]` is applied to this field more than once")
+                .help(
+                    "combine the labels into one message, or put the other label on a separate \
+                     field with `#[label(.., overlaps_primary)]`",
+                )
+                .emit();
+        }
+
         let needs_move = self.needs_move(&field);
         let inner_ty = FieldInnerTy::from_type(&field.ty);
 
@@ -415,12 +495,31 @@ fn generate_inner_field_code_list(
             None => throw_invalid_attr!(attr, &meta),
         };
 
-        // None of these attributes should have anything following the slug.
+        // None of these attributes should have anything following the slug, except for labels
+        // which can be marked as `overlaps_primary`.
+        let mut overlaps_primary = false;
+        match nested_iter.next() {
+            Some(NestedMeta::Meta(Meta::Path(path)))
+                if name == "label" && path.is_ident("overlaps_primary") =>
+            {
+                overlaps_primary = true;
+            }
+            Some(_) => throw_invalid_attr!(attr, &meta),
+            None => {}
+        }
         if nested_iter.next().is_some() {
             throw_invalid_attr!(attr, &meta);
         }
 
         match name {
+            // A label which may share its span with the primary span or another label is
+            // resolved once the diagnostic is complete, so that it is merged into a note rather
+            // than shown twice on that span, no matter the order of the fields.
+            "label" if overlaps_primary => {
+                report_error_if_not_applied_to_span(attr, &info)?;
+                let diag = &self.diag;
+                Ok(quote! { #diag.label_or_note(#binding, rustc_errors::fluent::#msg); })
+            }
             "label" => {
                 report_error_if_not_applied_to_span(attr, &info)?;
                 Ok(self.add_spanned_subdiagnostic(binding, ident, msg))
@@ -455,6 +554,7 @@ fn generate_inner_field_code_suggestion(
 
         let mut msg = None;
         let mut code = None;
+        let mut position: Option<(SuggestionPosition, proc_macro::Span)> = None;
 
         let mut nested_iter = nested.into_iter().peekable();
         if let Some(nested_attr) = nested_iter.peek() {
@@ -504,14 +604,28 @@ fn generate_inner_field_code_suggestion(
                                 },
                             }
                         }
+                        "position" => match SuggestionPosition::from_str(&s.value()) {
+                            Ok(v) => position.set_once((v, span)),
+                            Err(()) => {
+                                span_err(span, "invalid position, expected `before` or `after`")
+                                    .emit();
+                            }
+                        },
                         _ => throw_invalid_nested_attr!(attr, &nested_attr, |diag| {
                             diag.help(
-                                "only `message`, `code` and `applicability` are valid field \
-                                 attributes",
+                                "only `message`, `code`, `applicability` and `position` are \
+                                 valid field attributes",
                             )
                         }),
                     }
                 }
+                Meta::List(list) if nested_name == "code" => {
+                    let span = meta.span().unwrap();
+                    if code.is_some() {
+                        span_err(span, "`code` specified multiple times").emit();
+                    }
+                    code = Some(self.build_suggestion_code_lines(list)?.to_code(&span_field));
+                }
                 _ => throw_invalid_nested_attr!(attr, &nested_attr, |diag| {
                     if matches!(meta, Meta::Path(_)) {
                         diag.help("a diagnostic slug must be the first argument to the attribute")
@@ -531,8 +645,9 @@ fn generate_inner_field_code_suggestion(
         let msg = msg.unwrap_or_else(|| parse_quote! { _subdiag::suggestion });
         let msg = quote! { rustc_errors::fluent::#msg };
         let code = code.unwrap_or_else(|| quote! { String::new() });
+        let span = suggestion_span(&span_field, position.value());
 
-        Ok(quote! { #diag.#method(#span_field, #msg, #code, #applicability); })
+        Ok(quote! { #diag.#method(#span, #msg, #code, #applicability); })
     }
 
     /// Adds a spanned subdiagnostic by generating a `diag.span_$kind` call with the current slug
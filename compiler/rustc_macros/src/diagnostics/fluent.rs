@@ -4,7 +4,7 @@
 };
 use fluent_bundle::{FluentBundle, FluentError, FluentResource};
 use fluent_syntax::{
-    ast::{Attribute, Entry, Identifier, Message},
+    ast::{Attribute, Comment, Entry, Expression, Identifier, Message, Pattern, PatternElement},
     parser::ParserError,
 };
 use proc_macro::{Diagnostic, Level, Span};
@@ -20,11 +20,13 @@
     parse::{Parse, ParseStream},
     parse_macro_input,
     punctuated::Punctuated,
+    spanned::Spanned,
     token, Ident, LitStr, Result,
 };
 use unic_langid::langid;
 
 struct Resource {
+    attrs: Vec<syn::Attribute>,
     ident: Ident,
     #[allow(dead_code)]
     fat_arrow_token: token::FatArrow,
@@ -34,6 +36,7 @@ struct Resource {
 impl Parse for Resource {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
         Ok(Resource {
+            attrs: input.call(syn::Attribute::parse_outer)?,
             ident: input.parse()?,
             fat_arrow_token: input.parse()?,
             resource: input.parse()?,
@@ -81,6 +84,58 @@ fn invocation_relative_path_to_absolute(span: Span, path: &str) -> PathBuf {
     }
 }
 
+/// Attribute which a resource is given in the invocation for its suggestion messages to be
+/// checked not to spell out the code being suggested, which translations couldn't move around
+/// relative to the rest of the sentence. The emitter already shows the suggested code, and names
+/// which a message does need can be passed as Fluent arguments instead.
+const CODE_FREE_SUGGESTIONS_ATTR: &str = "code_free_suggestions";
+
+/// Start of the line of a message's comment which declares the kind of subdiagnostic the message
+/// is for, e.g. `# kind: label`.
+const KIND_MARKER: &str = "kind:";
+
+/// Messages which declare a kind of subdiagnostic are generated with the type of that kind, which
+/// only the methods of `Diagnostic` adding that kind take, so that e.g. a suggestion can't end up
+/// as a label. Other messages keep being `DiagnosticMessage`s, which all methods take.
+const SUBDIAGNOSTIC_KINDS: &[(&str, &str)] = &[
+    ("suggestion", "SuggestionMessage"),
+    ("label", "LabelMessage"),
+    // Also for helps and warnings, which are added by the same methods.
+    ("note", "NoteMessage"),
+];
+
+/// Returns the kind of subdiagnostic declared in `comment`, the comment of a message, if any.
+fn declared_kind<'a>(comment: Option<&Comment<&'a str>>) -> Option<&'a str> {
+    comment?.content.iter().find_map(|line| Some(line.strip_prefix(KIND_MARKER)?.trim()))
+}
+
+/// Longest backtick-quoted code allowed in a checked suggestion message, which leaves room for
+/// keywords and short names like `impl Trait`.
+const MAX_SUGGESTION_CODE_LEN: usize = 20;
+
+/// Returns the first backtick-quoted code in `pattern`, or in any of the variants of its
+/// selectors, which is longer than `MAX_SUGGESTION_CODE_LEN`. Placeables inside the code don't
+/// count towards its length.
+fn long_inline_code(pattern: &Pattern<&str>) -> Option<String> {
+    let mut text = String::new();
+    for element in &pattern.elements {
+        match element {
+            PatternElement::TextElement { value } => text.push_str(value),
+            PatternElement::Placeable { expression: Expression::Select { variants, .. } } => {
+                if let Some(code) = variants.iter().find_map(|v| long_inline_code(&v.value)) {
+                    return Some(code);
+                }
+            }
+            PatternElement::Placeable { expression: Expression::Inline(_) } => {}
+        }
+    }
+    text.split('`')
+        .skip(1)
+        .step_by(2)
+        .find(|code| code.chars().count() > MAX_SUGGESTION_CODE_LEN)
+        .map(|code| code.to_string())
+}
+
 /// See [rustc_macros::fluent_messages].
 pub(crate) fn fluent_messages(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let resources = parse_macro_input!(input as Resources);
@@ -96,6 +151,9 @@ pub(crate) fn fluent_messages(input: proc_macro::TokenStream) -> proc_macro::Tok
 
     let mut includes = TokenStream::new();
     let mut generated = TokenStream::new();
+    // Names of the messages with a `.teach` attribute, which is emitted as an extra note
+    // with `-Zteach`.
+    let mut teachable = Vec::new();
     for res in resources.0 {
         let ident_span = res.ident.span().unwrap();
         let path_span = res.resource.span().unwrap();
@@ -183,12 +241,70 @@ pub(crate) fn fluent_messages(input: proc_macro::TokenStream) -> proc_macro::Tok
             }
         };
 
+        let mut check_suggestions = false;
+        for attr in &res.attrs {
+            if attr.path.is_ident(CODE_FREE_SUGGESTIONS_ATTR) && attr.tokens.is_empty() {
+                check_suggestions = true;
+            } else {
+                Diagnostic::spanned(
+                    attr.span().unwrap(),
+                    Level::Error,
+                    "unsupported attribute on a Fluent resource",
+                )
+                .help(format!("the only supported attribute is `#[{CODE_FREE_SUGGESTIONS_ATTR}]`"))
+                .emit();
+            }
+        }
+        // `FluentResource` drops comments, so parse the resource again to see their kinds.
+        let entries = match fluent_syntax::parser::parse(resource.source()) {
+            Ok(ast) | Err((ast, _)) => ast.body,
+        };
         let mut constants = TokenStream::new();
-        for entry in resource.entries() {
+        for entry in &entries {
             let span = res.ident.span();
-            if let Entry::Message(Message { id: Identifier { name }, attributes, .. }) = entry {
+            if let Entry::Message(Message { id: Identifier { name }, value, attributes, comment }) =
+                entry
+            {
                 let _ = previous_defns.entry(name.to_string()).or_insert(ident_span);
 
+                let kind = declared_kind(comment.as_ref()).and_then(|kind| {
+                    let ty = SUBDIAGNOSTIC_KINDS.iter().find(|&&(k, _)| k == kind).map(|&(_, t)| t);
+                    if ty.is_none() {
+                        Diagnostic::spanned(
+                            ident_span,
+                            Level::Error,
+                            format!("message `{name}` declares the unknown kind `{kind}`"),
+                        )
+                        .help("the kinds are `suggestion`, `label` and `note`")
+                        .emit();
+                    }
+                    Some((kind, ty?))
+                });
+
+                if check_suggestions {
+                    let suggestions = value
+                        .iter()
+                        .filter(|_| matches!(kind, Some(("suggestion", _))))
+                        .map(|value| (name.to_string(), value))
+                        .chain(
+                            attributes
+                                .iter()
+                                .filter(|attr| attr.id.name.ends_with("suggestion"))
+                                .map(|attr| (format!("{name}.{}", attr.id.name), &attr.value)),
+                        );
+                    for (message, value) in suggestions {
+                        if let Some(code) = long_inline_code(value) {
+                            Diagnostic::spanned(
+                                ident_span,
+                                Level::Error,
+                                format!("suggestion `{message}` contains the code `{code}`"),
+                            )
+                            .help("leave the code to the suggestion, or pass it as an argument")
+                            .emit();
+                        }
+                    }
+                }
+
                 if name.contains('-') {
                     Diagnostic::spanned(
                         ident_span,
@@ -210,14 +326,28 @@ pub(crate) fn fluent_messages(input: proc_macro::TokenStream) -> proc_macro::Tok
                     &name.replace('-', "_").replace(&format!("{}_", res.ident), ""),
                     span,
                 );
-                constants.extend(quote! {
-                    pub const #snake_name: crate::DiagnosticMessage =
-                        crate::DiagnosticMessage::FluentIdentifier(
-                            std::borrow::Cow::Borrowed(#name),
-                            None
-                        );
+                let message = quote! {
+                    crate::DiagnosticMessage::FluentIdentifier(
+                        std::borrow::Cow::Borrowed(#name),
+                        None
+                    )
+                };
+                constants.extend(match kind {
+                    Some((_, ty)) => {
+                        let kind = Ident::new(ty, span);
+                        quote! {
+                            pub const #snake_name: crate::#kind = crate::#kind::new(#message);
+                        }
+                    }
+                    None => quote! {
+                        pub const #snake_name: crate::DiagnosticMessage = #message;
+                    },
                 });
 
+                if attributes.iter().any(|attr| attr.id.name == "teach") {
+                    teachable.push(name.to_string());
+                }
+
                 for Attribute { id: Identifier { name: attr_name }, .. } in attributes {
                     let snake_name = Ident::new(&attr_name.replace('-', "_"), span);
                     if !previous_attrs.insert(snake_name.clone()) {
@@ -279,6 +409,10 @@ pub mod fluent_generated {
                 #includes
             ];
 
+            pub static TEACHABLE_MESSAGES: &'static [&'static str] = &[
+                #(#teachable),*
+            ];
+
             #generated
 
             pub mod _subdiag {
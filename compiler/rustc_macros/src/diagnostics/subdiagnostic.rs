@@ -5,7 +5,8 @@
 };
 use crate::diagnostics::utils::{
     report_error_if_not_applied_to_applicability, report_error_if_not_applied_to_span,
-    Applicability, FieldInfo, FieldInnerTy, HasFieldMap, SetOnce,
+    suggestion_span, Applicability, FieldInfo, FieldInnerTy, HasFieldMap, SetOnce, SuggestionCode,
+    SuggestionPosition,
 };
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
@@ -43,6 +44,20 @@ enum SubdiagnosticKind {
     Suggestion(SubdiagnosticSuggestionKind),
 }
 
+/// Names of the variants of `rustc_errors::SubdiagnosticPriority`, in order.
+const PRIORITIES: [&str; 4] = ["Label", "Note", "Suggestion", "MultipartSuggestion"];
+
+impl SubdiagnosticKind {
+    /// Index into `PRIORITIES` of the priority of subdiagnostics of this kind.
+    fn priority(self) -> usize {
+        match self {
+            SubdiagnosticKind::Label => 0,
+            SubdiagnosticKind::Note | SubdiagnosticKind::Help | SubdiagnosticKind::Warn => 1,
+            SubdiagnosticKind::Suggestion(_) => 2,
+        }
+    }
+}
+
 impl FromStr for SubdiagnosticKind {
     type Err = ();
 
@@ -132,6 +147,7 @@ pub(crate) fn into_tokens(self) -> TokenStream {
             }
 
             structure.bind_with(|_| synstructure::BindStyle::Move);
+            let mut kinds = vec![];
             let variants_ = structure.each_variant(|variant| {
                 // Build the mapping of field names to fields. This allows attributes to peek
                 // values from other fields.
@@ -151,25 +167,36 @@ pub(crate) fn into_tokens(self) -> TokenStream {
                     kind: None,
                     slug: None,
                     code: None,
+                    position: None,
                     span_field: None,
                     applicability: None,
                 };
-                builder.into_tokens().unwrap_or_else(|v| v.to_compile_error())
+                let tokens = builder.into_tokens().unwrap_or_else(|v| v.to_compile_error());
+                kinds.extend(builder.kind.map(|(kind, _)| kind));
+                tokens
             });
 
+            // An enum is ordered as its latest-placed variant, so that none of its variants
+            // can come before subdiagnostics that are meant to precede it.
+            let priority = kinds.into_iter().map(SubdiagnosticKind::priority).max();
+            let priority = format_ident!("{}", priority.map_or("Note", |p| PRIORITIES[p]));
+
             quote! {
-                match self {
-                    #variants_
+                const PRIORITY: rustc_errors::SubdiagnosticPriority =
+                    rustc_errors::SubdiagnosticPriority::#priority;
+
+                fn add_to_diagnostic(self, #diag: &mut rustc_errors::Diagnostic) {
+                    use rustc_errors::{Applicability, IntoDiagnosticArg};
+                    match self {
+                        #variants_
+                    }
                 }
             }
         };
 
         let ret = structure.gen_impl(quote! {
             gen impl rustc_errors::AddSubdiagnostic for @Self {
-                fn add_to_diagnostic(self, #diag: &mut rustc_errors::Diagnostic) {
-                    use rustc_errors::{Applicability, IntoDiagnosticArg};
-                    #implementation
-                }
+                #implementation
             }
         });
         ret
@@ -200,8 +227,11 @@ struct SessionSubdiagnosticDeriveBuilder<'a> {
     /// `#[kind(slug)]` attribute on the type or variant.
     slug: Option<(Path, proc_macro::Span)>,
     /// If a suggestion, the code to suggest as a replacement - from the `#[kind(code = "...")]`
-    /// attribute on the type or variant.
-    code: Option<(TokenStream, proc_macro::Span)>,
+    /// or `#[kind(code("...", "..."))]` attribute on the type or variant.
+    code: Option<(SuggestionCode, proc_macro::Span)>,
+    /// If a suggestion, whether the code is inserted before or after the `#[primary_span]`
+    /// instead of replacing it - from the `#[kind(position = "...")]` attribute.
+    position: Option<(SuggestionPosition, proc_macro::Span)>,
 
     /// Identifier for the binding to the `#[primary_span]` field.
     span_field: Option<(proc_macro2::Ident, proc_macro::Span)>,
@@ -233,10 +263,10 @@ fn identify_kind(&mut self) -> Result<(), DiagnosticDeriveError> {
                             NestedMeta::Meta(Meta::Path(path)) => {
                                 self.slug.set_once((path.clone(), span));
                             }
-                            NestedMeta::Meta(meta @ Meta::NameValue(_))
+                            NestedMeta::Meta(meta @ (Meta::NameValue(_) | Meta::List(_)))
                                 if matches!(
                                     meta.path().segments.last().unwrap().ident.to_string().as_str(),
-                                    "code" | "applicability"
+                                    "code" | "applicability" | "position"
                                 ) =>
                             {
                                 // don't error for valid follow-up attributes
@@ -267,7 +297,10 @@ fn identify_kind(&mut self) -> Result<(), DiagnosticDeriveError> {
                                 match nested_name {
                                     "code" => {
                                         let formatted_str = self.build_format(&s.value(), s.span());
-                                        self.code.set_once((formatted_str, span));
+                                        self.code.set_once((
+                                            SuggestionCode::Single(formatted_str),
+                                            span,
+                                        ));
                                     }
                                     "applicability" => {
                                         let value = match Applicability::from_str(&s.value()) {
@@ -279,14 +312,26 @@ fn identify_kind(&mut self) -> Result<(), DiagnosticDeriveError> {
                                         };
                                         self.applicability.set_once((quote! { #value }, span));
                                     }
+                                    "position" => match SuggestionPosition::from_str(&s.value()) {
+                                        Ok(position) => self.position.set_once((position, span)),
+                                        Err(()) => span_err(
+                                            span,
+                                            "invalid position, expected `before` or `after`",
+                                        )
+                                        .emit(),
+                                    },
                                     _ => throw_invalid_nested_attr!(attr, &nested_attr, |diag| {
                                         diag.help(
-                                            "only `code` and `applicability` are valid nested \
-                                             attributes",
+                                            "only `code`, `applicability` and `position` are \
+                                             valid nested attributes",
                                         )
                                     }),
                                 }
                             }
+                            Meta::List(list) if nested_name == "code" => {
+                                let code = self.build_suggestion_code_lines(list)?;
+                                self.code.set_once((code, span));
+                            }
                             _ => throw_invalid_nested_attr!(attr, &nested_attr, |diag| {
                                 if matches!(meta, Meta::Path(_)) {
                                     diag.help(
@@ -334,6 +379,20 @@ fn identify_kind(&mut self) -> Result<(), DiagnosticDeriveError> {
                 );
             }
 
+            if matches!(
+                kind,
+                SubdiagnosticKind::Label | SubdiagnosticKind::Help | SubdiagnosticKind::Note
+            ) && self.position.is_some()
+            {
+                throw_span_err!(
+                    span,
+                    &format!(
+                        "`position` is not a valid nested attribute of a `{}` attribute",
+                        name
+                    )
+                );
+            }
+
             if self.slug.is_none() {
                 throw_span_err!(
                     span,
@@ -439,16 +498,19 @@ fn into_tokens(&mut self) -> Result<TokenStream, DiagnosticDeriveError> {
             .as_ref()
             .map(|(slug, _)| slug.clone())
             .unwrap_or_else(|| parse_quote! { you::need::to::specify::a::slug });
+        let span_field = self.span_field.as_ref().map(|(span, _)| span);
         let code = match self.code.as_ref() {
-            Some((code, _)) => Some(quote! { #code }),
+            // Missing `#[primary_span]` errors are reported below.
+            Some((code, _)) => Some(match span_field {
+                Some(span) => code.to_code(span),
+                None => code.to_code(quote! { rustc_span::DUMMY_SP }),
+            }),
             None if is_suggestion => {
                 span_err(self.span, "suggestion without `code = \"...\"`").emit();
                 Some(quote! { /* macro error */ "..." })
             }
             None => None,
         };
-
-        let span_field = self.span_field.as_ref().map(|(span, _)| span);
         let applicability = match self.applicability.clone() {
             Some((applicability, _)) => Some(applicability),
             None if is_suggestion => {
@@ -463,7 +525,16 @@ fn into_tokens(&mut self) -> Result<TokenStream, DiagnosticDeriveError> {
         let message = quote! { rustc_errors::fluent::#slug };
         let call = if matches!(kind, SubdiagnosticKind::Suggestion(..)) {
             if let Some(span) = span_field {
-                quote! { #diag.#name(#span, #message, #code, #applicability); }
+                let span = suggestion_span(span, self.position.map(|(position, _)| position));
+                let ident = &self.variant.ast().ident;
+                let tag = match self.variant.prefix {
+                    Some(prefix) => format!("{}::{}", prefix, ident),
+                    None => ident.to_string(),
+                };
+                quote! {
+                    #diag.#name(#span, #message, #code, #applicability);
+                    #diag.tag_last_suggestion(#tag);
+                }
             } else {
                 span_err(self.span, "suggestion without `#[primary_span]` field").emit();
                 quote! { unreachable!(); }
@@ -4,7 +4,7 @@
 use quote::{format_ident, quote, ToTokens};
 use std::collections::{BTreeSet, HashMap};
 use std::str::FromStr;
-use syn::{spanned::Spanned, Attribute, Meta, Type, TypeTuple};
+use syn::{spanned::Spanned, Attribute, Meta, MetaList, NestedMeta, Type, TypeTuple};
 use synstructure::{BindingInfo, Structure};
 
 /// Checks whether the type name of `ty` matches `name`.
@@ -196,6 +196,62 @@ fn value(self) -> Option<T> {
     }
 }
 
+/// Code to suggest, from the `code` nested attribute of a suggestion attribute.
+pub(crate) enum SuggestionCode {
+    /// `code = "..."` (or `code = r"..."`).
+    Single(TokenStream),
+    /// `code("...", "...")`, lines which are joined with newlines and indented like the line of
+    /// the suggestion's span, see `rustc_errors::multiline_suggestion_code`.
+    Lines(Vec<TokenStream>),
+}
+
+impl SuggestionCode {
+    /// Generates the expression for the suggested code of a suggestion at `span_field`.
+    pub(crate) fn to_code(&self, span_field: impl ToTokens) -> TokenStream {
+        match self {
+            SuggestionCode::Single(code) => code.clone(),
+            SuggestionCode::Lines(lines) => quote! {
+                rustc_errors::multiline_suggestion_code(#span_field, &[#(#lines),*])
+            },
+        }
+    }
+}
+
+/// Where a suggestion inserts its code relative to the span it is given, from the
+/// `position = "..."` nested attribute. Without it, the code replaces the span.
+#[derive(Clone, Copy)]
+pub(crate) enum SuggestionPosition {
+    /// `position = "before"`
+    Before,
+    /// `position = "after"`
+    After,
+}
+
+impl FromStr for SuggestionPosition {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "before" => Ok(SuggestionPosition::Before),
+            "after" => Ok(SuggestionPosition::After),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Generates the expression for the span a suggestion at `span_field` applies to, which is
+/// an empty span next to it if the suggestion has a `position`.
+pub(crate) fn suggestion_span(
+    span_field: impl ToTokens,
+    position: Option<SuggestionPosition>,
+) -> TokenStream {
+    match position {
+        None => quote! { #span_field },
+        Some(SuggestionPosition::Before) => quote! { #span_field.shrink_to_lo() },
+        Some(SuggestionPosition::After) => quote! { #span_field.shrink_to_hi() },
+    }
+}
+
 pub(crate) trait HasFieldMap {
     /// Returns the binding for the field with the given name, if it exists on the type.
     fn get_field_binding(&self, field: &String) -> Option<&TokenStream>;
@@ -294,6 +350,26 @@ fn build_format(&self, input: &str, span: proc_macro2::Span) -> TokenStream {
             format!(#input #(,#args)*)
         }
     }
+
+    /// Builds the code of a `code("...", "...")` nested attribute, see `SuggestionCode::Lines`.
+    fn build_suggestion_code_lines(
+        &self,
+        list: &MetaList,
+    ) -> Result<SuggestionCode, DiagnosticDeriveError> {
+        let mut lines = vec![];
+        for nested in &list.nested {
+            match nested {
+                NestedMeta::Lit(syn::Lit::Str(s)) => {
+                    lines.push(self.build_format(&s.value(), s.span()));
+                }
+                _ => throw_span_err!(
+                    nested.span().unwrap(),
+                    "each line of `code(...)` must be a string literal"
+                ),
+            }
+        }
+        Ok(SuggestionCode::Lines(lines))
+    }
 }
 
 /// `Applicability` of a suggestion - mirrors `rustc_errors::Applicability` - and used to represent
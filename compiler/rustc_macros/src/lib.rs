@@ -106,6 +106,17 @@ pub fn newtype_index(input: TokenStream) -> TokenStream {
 /// );
 /// err.emit();
 /// ```
+///
+/// A resource given the `#[code_free_suggestions]` attribute in the invocation has its
+/// suggestion messages checked not to quote long pieces of code, which the emitter already shows.
+///
+/// A message whose comment has a `kind: suggestion`, `kind: label` or `kind: note` line is
+/// generated as a `SuggestionMessage`, `LabelMessage` or `NoteMessage` instead:
+///
+/// ```fluent
+/// # kind: label
+/// typeck_field_previous_use_label = first use of `{$field_name}`
+/// ```
 #[proc_macro]
 pub fn fluent_messages(input: TokenStream) -> TokenStream {
     diagnostics::fluent_messages(input)
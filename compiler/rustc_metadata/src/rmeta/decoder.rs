@@ -28,7 +28,7 @@
 use rustc_serialize::opaque::MemDecoder;
 use rustc_serialize::{Decodable, Decoder};
 use rustc_session::cstore::{
-    CrateSource, ExternCrate, ForeignModule, LinkagePreference, NativeLib,
+    CfgStrippedImpl, CrateSource, ExternCrate, ForeignModule, LinkagePreference, NativeLib,
 };
 use rustc_session::Session;
 use rustc_span::hygiene::{ExpnIndex, MacroKind};
@@ -941,6 +941,10 @@ fn get_expn_that_defined(self, id: DefIndex, sess: &Session) -> ExpnId {
         self.root.tables.expn_that_defined.get(self, id).unwrap().decode((self, sess))
     }
 
+    fn get_cfg_stripped_impls(self, tcx: TyCtxt<'tcx>) -> &'tcx [CfgStrippedImpl] {
+        tcx.arena.alloc_from_iter(self.root.cfg_stripped_impls.decode(self))
+    }
+
     fn get_debugger_visualizers(self) -> Vec<rustc_span::DebuggerVisualizerFile> {
         self.root.debugger_visualizers.decode(self).collect::<Vec<_>>()
     }
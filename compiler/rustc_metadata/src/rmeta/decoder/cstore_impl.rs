@@ -306,6 +306,7 @@ fn into_args(self) -> (DefId, SimplifiedType) {
 
     used_crate_source => { Lrc::clone(&cdata.source) }
     debugger_visualizers => { cdata.get_debugger_visualizers() }
+    cfg_stripped_impls => { cdata.get_cfg_stripped_impls(tcx) }
 
     exported_symbols => {
         let syms = cdata.exported_symbols(tcx);
@@ -361,6 +362,10 @@ pub(in crate::rmeta) fn provide(providers: &mut Providers) {
             assert_eq!(cnum, LOCAL_CRATE);
             native_libs::collect(tcx)
         },
+        cfg_stripped_impls: |tcx, cnum| {
+            assert_eq!(cnum, LOCAL_CRATE);
+            tcx.arena.alloc_from_iter(tcx.sess.parse_sess.cfg_stripped_impls.lock().iter().copied())
+        },
         foreign_modules: |tcx, cnum| {
             assert_eq!(cnum, LOCAL_CRATE);
             foreign_modules::collect(tcx).into_iter().map(|m| (m.def_id, m)).collect()
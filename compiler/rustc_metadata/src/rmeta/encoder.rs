@@ -30,7 +30,7 @@
 use rustc_middle::ty::{self, SymbolName, Ty, TyCtxt};
 use rustc_serialize::{opaque, Decodable, Decoder, Encodable, Encoder};
 use rustc_session::config::CrateType;
-use rustc_session::cstore::{ForeignModule, LinkagePreference, NativeLib};
+use rustc_session::cstore::{CfgStrippedImpl, ForeignModule, LinkagePreference, NativeLib};
 use rustc_span::hygiene::{ExpnIndex, HygieneEncodeContext, MacroKind};
 use rustc_span::symbol::{sym, Symbol};
 use rustc_span::{
@@ -606,6 +606,10 @@ fn encode_crate_root(&mut self) -> LazyValue<CrateRoot> {
         let incoherent_impls = self.encode_incoherent_impls();
         let incoherent_impls_bytes = self.position() - i;
 
+        i = self.position();
+        let cfg_stripped_impls = self.encode_cfg_stripped_impls();
+        let cfg_stripped_impls_bytes = self.position() - i;
+
         // Encode MIR.
         i = self.position();
         self.encode_mir();
@@ -725,6 +729,7 @@ fn encode_crate_root(&mut self) -> LazyValue<CrateRoot> {
             traits,
             impls,
             incoherent_impls,
+            cfg_stripped_impls,
             exported_symbols,
             interpret_alloc_index,
             tables,
@@ -749,6 +754,7 @@ fn encode_crate_root(&mut self) -> LazyValue<CrateRoot> {
             + traits_bytes
             + impls_bytes
             + incoherent_impls_bytes
+            + cfg_stripped_impls_bytes
             + mir_bytes
             + item_bytes
             + interpret_alloc_index_bytes
@@ -801,6 +807,7 @@ fn encode_crate_root(&mut self) -> LazyValue<CrateRoot> {
             p("traits", traits_bytes);
             p("impls", impls_bytes);
             p("incoherent_impls", incoherent_impls_bytes);
+            p("cfg_stripped_impls", cfg_stripped_impls_bytes);
             p("mir", mir_bytes);
             p("item", item_bytes);
             p("interpret_alloc_index", interpret_alloc_index_bytes);
@@ -1768,6 +1775,11 @@ fn encode_debugger_visualizers(&mut self) -> LazyArray<DebuggerVisualizerFile> {
         self.lazy_array(self.tcx.debugger_visualizers(LOCAL_CRATE).iter())
     }
 
+    fn encode_cfg_stripped_impls(&mut self) -> LazyArray<CfgStrippedImpl> {
+        empty_proc_macro!(self);
+        self.lazy_array(self.tcx.cfg_stripped_impls(LOCAL_CRATE).iter())
+    }
+
     fn encode_crate_deps(&mut self) -> LazyArray<CrateDep> {
         empty_proc_macro!(self);
 
@@ -1702,6 +1702,13 @@
         desc { "looking up the debugger visualizers for this crate" }
         separate_provide_extern
     }
+    /// Returns the trait impls of this crate which were removed because one of its features
+    /// wasn't enabled. These are only recorded with `-Zmetadata-include-cfg-impls`.
+    query cfg_stripped_impls(_: CrateNum) -> &'tcx [rustc_session::cstore::CfgStrippedImpl] {
+        eval_always
+        desc { "looking up the trait impls removed by disabled features of a crate" }
+        separate_provide_extern
+    }
     query postorder_cnums(_: ()) -> &'tcx [CrateNum] {
         eval_always
         desc { "generating a postorder list of CrateNums" }
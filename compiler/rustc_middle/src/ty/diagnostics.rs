@@ -7,7 +7,7 @@
     PolyTraitPredicate, Ty, TyCtxt, TypeSuperVisitable, TypeVisitor,
 };
 
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{Applicability, Diagnostic, DiagnosticArgValue, IntoDiagnosticArg};
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
@@ -97,6 +97,27 @@ fn is_suggestable(self, tcx: TyCtxt<'tcx>, infer_suggestable: bool) -> bool {
     }
 }
 
+pub trait IsNameableFrom<'tcx> {
+    /// Returns the first item referred to by `self` which can't be named in `module`, as there
+    /// is no path to it which is visible from there, or as it is a closure or generator.
+    ///
+    /// This doesn't check whether `self` can be suggested at all, for which there is
+    /// `IsSuggestable::is_suggestable`.
+    fn find_unnameable_item(self, tcx: TyCtxt<'tcx>, module: DefId) -> Option<DefId>;
+}
+
+impl<'tcx, T> IsNameableFrom<'tcx> for T
+where
+    T: TypeVisitable<'tcx>,
+{
+    fn find_unnameable_item(self, tcx: TyCtxt<'tcx>, module: DefId) -> Option<DefId> {
+        match self.visit_with(&mut IsNameableVisitor { tcx, module }) {
+            ControlFlow::Break(def_id) => Some(def_id),
+            ControlFlow::Continue(()) => None,
+        }
+    }
+}
+
 pub fn suggest_arbitrary_trait_bound<'tcx>(
     tcx: TyCtxt<'tcx>,
     generics: &hir::Generics<'_>,
@@ -499,3 +520,85 @@ fn visit_const(&mut self, c: Const<'tcx>) -> ControlFlow<Self::BreakTy> {
         c.super_visit_with(self)
     }
 }
+
+struct IsNameableVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    module: DefId,
+}
+
+impl<'tcx> IsNameableVisitor<'tcx> {
+    /// Checks that the item `def_id` can be named from `self.module`, where it is defined or
+    /// through a re-export.
+    fn check_path(&self, def_id: DefId) -> ControlFlow<DefId> {
+        if self.is_nameable(def_id, &mut FxHashSet::default()) {
+            ControlFlow::CONTINUE
+        } else {
+            ControlFlow::Break(def_id)
+        }
+    }
+
+    /// Items from other crates can be named if there is a public path to them, which is what
+    /// `visible_parent_map` is made of. Local items have to be visible from `self.module`, and so
+    /// does a module they're defined in or re-exported from. `seen` keeps modules re-exporting
+    /// each other from being gone through over and over.
+    fn is_nameable(&self, def_id: DefId, seen: &mut FxHashSet<DefId>) -> bool {
+        let tcx = self.tcx;
+        if def_id.is_crate_root() {
+            return true;
+        }
+        if !def_id.is_local() {
+            return tcx.visible_parent_map(()).contains_key(&def_id);
+        }
+        if !seen.insert(def_id) {
+            return false;
+        }
+        let nameable_where_defined = tcx.visibility(def_id).is_accessible_from(self.module, tcx)
+            && match tcx.opt_parent(def_id) {
+                Some(parent) if tcx.def_kind(parent) == hir::def::DefKind::Mod => {
+                    self.is_nameable(parent, seen)
+                }
+                _ => true,
+            };
+        if nameable_where_defined {
+            return true;
+        }
+        let mut reexporting_modules = vec![];
+        tcx.hir().for_each_module(|module| {
+            let reexports = tcx.module_reexports(module).unwrap_or_default();
+            if reexports.iter().any(|reexport| {
+                reexport.res.opt_def_id() == Some(def_id)
+                    && reexport.vis.is_accessible_from(self.module, tcx)
+            }) {
+                reexporting_modules.push(module.to_def_id());
+            }
+        });
+        reexporting_modules.into_iter().any(|module| self.is_nameable(module, seen))
+    }
+}
+
+impl<'tcx> TypeVisitor<'tcx> for IsNameableVisitor<'tcx> {
+    type BreakTy = DefId;
+
+    fn visit_ty(&mut self, t: Ty<'tcx>) -> ControlFlow<Self::BreakTy> {
+        match *t.kind() {
+            Closure(def_id, _) | Generator(def_id, ..) => return ControlFlow::Break(def_id),
+            Adt(def, _) => self.check_path(def.did())?,
+            Foreign(def_id) => self.check_path(def_id)?,
+            Projection(data) => self.check_path(data.trait_def_id(self.tcx))?,
+            Dynamic(preds, _) => {
+                for def_id in preds.iter().filter_map(|pred| match pred.skip_binder() {
+                    ExistentialPredicate::Trait(trait_ref) => Some(trait_ref.def_id),
+                    ExistentialPredicate::Projection(proj) => {
+                        Some(self.tcx.parent(proj.item_def_id))
+                    }
+                    ExistentialPredicate::AutoTrait(def_id) => Some(def_id),
+                }) {
+                    self.check_path(def_id)?;
+                }
+            }
+            _ => {}
+        }
+
+        t.super_visit_with(self)
+    }
+}
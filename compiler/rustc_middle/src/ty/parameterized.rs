@@ -76,6 +76,7 @@ impl $crate::ty::ParameterizedOverTcx for $ty {
     rustc_hir::def_id::DefIndex,
     rustc_hir::definitions::DefKey,
     rustc_index::bit_set::FiniteBitSet<u32>,
+    rustc_session::cstore::CfgStrippedImpl,
     rustc_session::cstore::ForeignModule,
     rustc_session::cstore::LinkagePreference,
     rustc_session::cstore::NativeLib,
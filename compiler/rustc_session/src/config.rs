@@ -742,6 +742,7 @@ fn default() -> Options {
             json_artifact_notifications: false,
             json_unused_externs: JsonUnusedExterns::No,
             json_future_incompat: false,
+            json_compilation_summary: false,
             pretty: None,
             working_dir: RealFileName::LocalPath(std::env::current_dir().unwrap()),
         }
@@ -783,6 +784,8 @@ pub fn get_symbol_mangling_version(&self) -> SymbolManglingVersion {
 }
 
 impl UnstableOptions {
+    // JUSTIFICATION: the handler does the same as `Session::teach` for fluent diagnostics
+    #[allow(rustc::bad_opt_access)]
     pub fn diagnostic_handler_flags(&self, can_emit_warnings: bool) -> HandlerFlags {
         HandlerFlags {
             can_emit_warnings,
@@ -791,6 +794,12 @@ pub fn diagnostic_handler_flags(&self, can_emit_warnings: bool) -> HandlerFlags
             report_delayed_bugs: self.report_delayed_bugs,
             macro_backtrace: self.macro_backtrace,
             deduplicate_diagnostics: self.deduplicate_diagnostics,
+            max_explanatory_notes: self.max_explanatory_notes,
+            teach: self.teach,
+            emit_diagnostic_digests: self.emit_diagnostic_digests,
+            no_suggestions: self.no_suggestions,
+            track_diagnostics: self.track_diagnostics,
+            ui_testing: self.ui_testing,
         }
     }
 }
@@ -1490,6 +1499,7 @@ pub struct JsonConfig {
     pub json_artifact_notifications: bool,
     pub json_unused_externs: JsonUnusedExterns,
     pub json_future_incompat: bool,
+    pub json_compilation_summary: bool,
 }
 
 /// Report unused externs in event stream
@@ -1530,6 +1540,7 @@ pub fn parse_json(matches: &getopts::Matches) -> JsonConfig {
     let mut json_artifact_notifications = false;
     let mut json_unused_externs = JsonUnusedExterns::No;
     let mut json_future_incompat = false;
+    let mut json_compilation_summary = false;
     for option in matches.opt_strs("json") {
         // For now conservatively forbid `--color` with `--json` since `--json`
         // won't actually be emitting any colors and anything colorized is
@@ -1549,6 +1560,7 @@ pub fn parse_json(matches: &getopts::Matches) -> JsonConfig {
                 "unused-externs" => json_unused_externs = JsonUnusedExterns::Loud,
                 "unused-externs-silent" => json_unused_externs = JsonUnusedExterns::Silent,
                 "future-incompat" => json_future_incompat = true,
+                "compilation-summary" => json_compilation_summary = true,
                 s => early_error(
                     ErrorOutputType::default(),
                     &format!("unknown `--json` option `{s}`"),
@@ -1562,6 +1574,7 @@ pub fn parse_json(matches: &getopts::Matches) -> JsonConfig {
         json_artifact_notifications,
         json_unused_externs,
         json_future_incompat,
+        json_compilation_summary,
     }
 }
 
@@ -2209,6 +2222,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         json_artifact_notifications,
         json_unused_externs,
         json_future_incompat,
+        json_compilation_summary,
     } = parse_json(matches);
 
     let error_format = parse_error_format(matches, color, json_rendered);
@@ -2504,6 +2518,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         json_artifact_notifications,
         json_unused_externs,
         json_future_incompat,
+        json_compilation_summary,
         pretty,
         working_dir,
     }
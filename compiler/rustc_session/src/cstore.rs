@@ -112,6 +112,21 @@ pub struct ForeignModule {
     pub def_id: DefId,
 }
 
+/// A trait impl that was removed from a crate because a feature of it wasn't enabled, recorded
+/// with `-Zmetadata-include-cfg-impls` so that dependents can point out the missing feature.
+/// Stripped items are never resolved, so the trait and self type are the paths they were written
+/// with, without generic arguments and without leading segments like `crate` or `super`, e.g.
+/// `std::fmt::Display`.
+#[derive(Copy, Clone, Encodable, Decodable, HashStable_Generic, Debug)]
+pub struct CfgStrippedImpl {
+    /// The path to the trait.
+    pub trait_path: Symbol,
+    /// The path to the self type.
+    pub self_ty_path: Symbol,
+    /// The feature the impl needs.
+    pub feature: Symbol,
+}
+
 #[derive(Copy, Clone, Debug, HashStable_Generic)]
 pub struct ExternCrate {
     pub src: ExternCrateSource,
@@ -206,6 +206,9 @@ pub struct Options {
         /// `true` if we're emitting a JSON job containing a future-incompat report for lints
         json_future_incompat: bool [TRACKED],
 
+        /// `true` if we're emitting a JSON blob summarizing the diagnostics at the very end
+        json_compilation_summary: bool [UNTRACKED],
+
         pretty: Option<PpMode> [UNTRACKED],
 
         /// The (potentially remapped) working directory
@@ -1276,6 +1279,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
     dump_dep_graph: bool = (false, parse_bool, [UNTRACKED],
         "dump the dependency graph to $RUST_DEP_GRAPH (default: /tmp/dep_graph.gv) \
         (default: no)"),
+    dump_diagnostics: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write every emitted diagnostic to this file, as one JSON object per line with the \
+        Fluent identifiers and arguments of its messages (default: no)"),
     dump_drop_tracking_cfg: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "dump drop-tracking control-flow graph as a `.dot` file (default: no)"),
     dump_mir: Option<String> = (None, parse_opt_string, [UNTRACKED],
@@ -1304,10 +1310,15 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         an additional `.html` file showing the computed coverage spans."),
     dwarf_version: Option<u32> = (None, parse_opt_number, [TRACKED],
         "version of DWARF debug information to emit (default: 2 or 4, depending on platform)"),
+    emit_diagnostic_digests: bool = (false, parse_bool, [UNTRACKED],
+        "emit the Fluent identifier and sorted argument names of each translatable diagnostic, \
+        for use by compiletest (default: no)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
     emit_thin_lto: bool = (true, parse_bool, [TRACKED],
         "emit the bc module with thin LTO info (default: yes)"),
+    explain_inference: bool = (false, parse_bool, [UNTRACKED],
+        "point at all uses of a binding whose type could not be inferred (default: no)"),
     export_executable_symbols: bool = (false, parse_bool, [TRACKED],
         "export symbols from executables, as if they were dynamic libraries"),
     extra_const_ub_checks: bool = (false, parse_bool, [TRACKED],
@@ -1345,6 +1356,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "hash spans relative to their parent item for incr. comp. (default: no)"),
     incremental_verify_ich: bool = (false, parse_bool, [UNTRACKED],
         "verify incr. comp. hashes of green query instances (default: no)"),
+    inference_error_budget: usize = (5, parse_number, [UNTRACKED],
+        "the number of type annotation errors with the same error code reported for a function \
+        before the remaining ones are summarized (default: 5, 0 reports all of them)"),
     inline_mir: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "enable MIR inlining (default: no)"),
     inline_mir_threshold: Option<usize> = (None, parse_opt_number, [TRACKED],
@@ -1385,12 +1399,17 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "list the symbols defined by a library crate (default: no)"),
     macro_backtrace: bool = (false, parse_bool, [UNTRACKED],
         "show macro backtraces (default: no)"),
+    max_explanatory_notes: Option<usize> = (None, parse_opt_number, [UNTRACKED],
+        "emit each explanatory note at most this many times per compilation (default: unlimited)"),
     merge_functions: Option<MergeFunctions> = (None, parse_merge_functions, [TRACKED],
         "control the operation of the MergeFunctions LLVM pass, taking \
         the same values as the target option of the same name"),
     #[rustc_lint_opt_deny_field_access("use `Session::meta_stats` instead of this field")]
     meta_stats: bool = (false, parse_bool, [UNTRACKED],
         "gather metadata statistics (default: no)"),
+    metadata_include_cfg_impls: bool = (false, parse_bool, [TRACKED],
+        "record trait impls removed by a disabled feature in the crate metadata, so that \
+        dependents can suggest enabling it (default: no)"),
     mir_emit_retag: bool = (false, parse_bool, [TRACKED],
         "emit Retagging MIR statements, interpreted e.g., by miri; implies -Zmir-opt-level=0 \
         (default: no)"),
@@ -1431,6 +1450,8 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "do not use unique names for text and data sections when -Z function-sections is used"),
     no_profiler_runtime: bool = (false, parse_no_flag, [TRACKED],
         "prevent automatic injection of the profiler_builtins crate"),
+    no_suggestions: bool = (false, parse_no_flag, [UNTRACKED],
+        "do not attach suggestions to diagnostics, and skip computing them where expensive"),
     normalize_docs: bool = (false, parse_bool, [TRACKED],
         "normalize associated items in rustdoc when generating documentation"),
     oom: OomStrategy = (OomStrategy::Abort, parse_oom_strategy, [TRACKED],
@@ -1608,6 +1629,9 @@ pub(crate) fn parse_proc_macro_execution_strategy(
         "choose the TLS model to use (`rustc --print tls-models` for details)"),
     trace_macros: bool = (false, parse_bool, [UNTRACKED],
         "for every macro invocation, print its name and arguments (default: no)"),
+    track_diagnostics: bool = (false, parse_bool, [UNTRACKED],
+        "note where in the compiler each diagnostic was created and emitted, and which \
+        diagnostic struct it was created from (default: no)"),
     translate_remapped_path_to_local_path: bool = (true, parse_bool, [TRACKED],
         "translate remapped paths into local paths when possible (default: yes)"),
     trap_unreachable: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1617,7 +1641,8 @@ pub(crate) fn parse_proc_macro_execution_strategy(
     trim_diagnostic_paths: bool = (true, parse_bool, [UNTRACKED],
         "in diagnostics, use heuristics to shorten paths referring to items"),
     ui_testing: bool = (false, parse_bool, [UNTRACKED],
-        "emit compiler diagnostics in a form suitable for UI testing (default: no)"),
+        "emit compiler diagnostics in a form suitable for UI testing, with `-Zunstable-options` \
+        also showing which code produced each suggestion (default: no)"),
     uninit_const_chunk_threshold: usize = (16, parse_number, [TRACKED],
         "allow generating const initializers with mixed init/uninit chunks, \
         and set the maximum number of chunks for which this is allowed (default: 16)"),
@@ -2,6 +2,7 @@
 //! It also serves as an input to the parser itself.
 
 use crate::config::CheckCfg;
+use crate::cstore::CfgStrippedImpl;
 use crate::lint::{
     builtin::UNSTABLE_SYNTAX_PRE_EXPANSION, BufferedEarlyLint, BuiltinLintDiagnostics, Lint, LintId,
 };
@@ -11,8 +12,8 @@
 use rustc_data_structures::sync::{Lock, Lrc};
 use rustc_errors::{emitter::SilentEmitter, ColorConfig, Handler};
 use rustc_errors::{
-    error_code, fallback_fluent_bundle, Applicability, Diagnostic, DiagnosticBuilder, DiagnosticId,
-    DiagnosticMessage, ErrorGuaranteed, MultiSpan, StashKey,
+    error_code, fallback_fluent_bundle, Applicability, Diagnostic, DiagnosticArgValue,
+    DiagnosticBuilder, DiagnosticId, DiagnosticMessage, ErrorGuaranteed, MultiSpan, StashKey,
 };
 use rustc_feature::{find_feature_issue, GateIssue, UnstableFeatures};
 use rustc_span::edition::Edition;
@@ -218,6 +219,9 @@ pub struct ParseSess {
     /// Spans passed to `proc_macro::quote_span`. Each span has a numerical
     /// identifier represented by its position in the vector.
     pub proc_macro_quoted_spans: Lock<Vec<Span>>,
+    /// Trait impls removed by `#[cfg(feature = "..")]`, only collected with
+    /// `-Zmetadata-include-cfg-impls`.
+    pub cfg_stripped_impls: Lock<Vec<CfgStrippedImpl>>,
 }
 
 impl ParseSess {
@@ -256,6 +260,7 @@ pub fn with_span_handler(handler: Handler, source_map: Lrc<SourceMap>) -> Self {
             type_ascription_path_suggestions: Default::default(),
             assume_incomplete_release: false,
             proc_macro_quoted_spans: Default::default(),
+            cfg_stripped_impls: Default::default(),
         }
     }
 
@@ -338,17 +343,43 @@ pub fn proc_macro_quoted_spans(&self) -> Vec<Span> {
         self.proc_macro_quoted_spans.lock().clone()
     }
 
+    #[track_caller]
     pub fn create_err<'a>(
         &'a self,
         err: impl SessionDiagnostic<'a>,
     ) -> DiagnosticBuilder<'a, ErrorGuaranteed> {
-        err.into_diagnostic(self)
+        fn type_name_of<T>(_: &T) -> &'static str {
+            std::any::type_name::<T>()
+        }
+
+        let created_from = type_name_of(&err);
+        let mut err = err.into_diagnostic(self);
+        if self.span_diagnostic.track_diagnostics() {
+            // The backtrace of an ICE only shows the type of the diagnostic struct, so show the
+            // arguments it was turned into as well.
+            let args: Vec<_> = err
+                .args()
+                .iter()
+                .map(|(name, value)| match value {
+                    DiagnosticArgValue::Str(s) => format!("{} = {:?}", name, s),
+                    DiagnosticArgValue::Number(n) => format!("{} = {}", name, n),
+                })
+                .collect();
+            err.note(&format!(
+                "-Ztrack-diagnostics: created from `{}` {{ {} }}",
+                created_from,
+                args.join(", ")
+            ));
+        }
+        err
     }
 
+    #[track_caller]
     pub fn emit_err<'a>(&'a self, err: impl SessionDiagnostic<'a>) -> ErrorGuaranteed {
         self.create_err(err).emit()
     }
 
+    #[track_caller]
     pub fn create_warning<'a>(
         &'a self,
         warning: impl SessionDiagnostic<'a, ()>,
@@ -356,11 +387,13 @@ pub fn create_warning<'a>(
         warning.into_diagnostic(self)
     }
 
+    #[track_caller]
     pub fn emit_warning<'a>(&'a self, warning: impl SessionDiagnostic<'a, ()>) {
         self.create_warning(warning).emit()
     }
 
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_err(
         &self,
         msg: impl Into<DiagnosticMessage>,
@@ -369,6 +402,7 @@ pub fn struct_err(
     }
 
     #[rustc_lint_diagnostics]
+    #[track_caller]
     pub fn struct_warn(&self, msg: impl Into<DiagnosticMessage>) -> DiagnosticBuilder<'_, ()> {
         self.span_diagnostic.struct_warn(msg)
     }
@@ -988,6 +988,14 @@ fn drop(&mut self) {
     f()
 }
 
+/// Returns the whitespace at the start of the line that `sp` starts on, using the `SourceMap`
+/// provided to `with_source_map`. Returns `None` if there is no such `SourceMap`.
+pub fn indentation_before(sp: Span) -> Option<String> {
+    with_session_globals(|session_globals| {
+        session_globals.source_map.borrow().as_ref()?.indentation_before(sp)
+    })
+}
+
 impl fmt::Debug for Span {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         with_session_globals(|session_globals| {
@@ -14,7 +14,7 @@
 
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::stable_hasher::StableHasher;
-use rustc_data_structures::sync::{AtomicU32, Lrc, MappedReadGuard, ReadGuard, RwLock};
+use rustc_data_structures::sync::{AtomicU32, Lock, Lrc, MappedReadGuard, ReadGuard, RwLock};
 use std::hash::Hash;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
@@ -178,6 +178,10 @@ pub struct SourceMap {
 
     /// The algorithm used for hashing the contents of each source file.
     hash_kind: SourceFileHashAlgorithm,
+
+    /// The `include!` invocations which files were included by, keyed by the start of the
+    /// included file. Only the first invocation of each file is kept.
+    include_sites: Lock<FxHashMap<BytePos, Span>>,
 }
 
 impl SourceMap {
@@ -200,6 +204,7 @@ pub fn with_file_loader_and_hash_kind(
             file_loader,
             path_mapping,
             hash_kind,
+            include_sites: Default::default(),
         }
     }
 
@@ -423,6 +428,21 @@ pub fn lookup_source_file(&self, pos: BytePos) -> Lrc<SourceFile> {
         (*self.files.borrow().source_files)[idx].clone()
     }
 
+    /// Records that the code of `file` was included by the `include!` invocation at `site`.
+    pub fn save_include_site(&self, file: &SourceFile, site: Span) {
+        self.include_sites.lock().entry(file.start_pos).or_insert(site);
+    }
+
+    /// Returns the `include!` invocation which included the code at `span`, if it is in a file
+    /// included that way.
+    pub fn include_site(&self, span: Span) -> Option<Span> {
+        if span.is_dummy() {
+            return None;
+        }
+        let file = self.lookup_source_file(span.lo());
+        self.include_sites.lock().get(&file.start_pos).copied()
+    }
+
     /// Looks up source information about a `BytePos`.
     pub fn lookup_char_pos(&self, pos: BytePos) -> Loc {
         let sf = self.lookup_source_file(pos);
@@ -216,7 +216,9 @@
         IrTyKind,
         Is,
         ItemContext,
+        IterMap,
         Iterator,
+        LabelMessage,
         Layout,
         Left,
         LinkedList,
@@ -234,6 +236,7 @@
         NonZeroU64,
         NonZeroU8,
         None,
+        NoteMessage,
         Ok,
         Option,
         Ord,
@@ -279,6 +282,7 @@
         StructuralEq,
         StructuralPartialEq,
         SubdiagnosticMessage,
+        SuggestionMessage,
         Sync,
         Target,
         ToOwned,
@@ -481,6 +485,7 @@
         cmse_nonsecure_entry,
         coerce_unsized,
         cold,
+        collect,
         column,
         column_macro,
         compare_and_swap,
@@ -852,6 +857,7 @@
         lateout,
         lazy_normalization_consts,
         le,
+        leak,
         len,
         let_chains,
         let_else,
@@ -1451,8 +1457,10 @@
         test_unstable_lint,
         then_with,
         thread,
+        thread_builder_spawn,
         thread_local,
         thread_local_macro,
+        thread_spawn,
         thumb2,
         thumb_mode: "thumb-mode",
         tmm_reg,
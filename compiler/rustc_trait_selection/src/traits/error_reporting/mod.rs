@@ -2,16 +2,18 @@
 pub mod suggestions;
 
 use super::{
-    EvaluationResult, FulfillmentContext, FulfillmentError, FulfillmentErrorCode,
+    EvaluationResult, FulfillmentContext, FulfillmentError, FulfillmentErrorCode, ImplSource,
     MismatchedProjectionTypes, Obligation, ObligationCause, ObligationCauseCode,
     OnUnimplementedDirective, OnUnimplementedNote, OutputTypeParameterMismatch, Overflow,
     PredicateObligation, SelectionContext, SelectionError, TraitNotObjectSafe,
 };
 
-use crate::infer::error_reporting::{TyCategory, TypeAnnotationNeeded as ErrorCode};
+use crate::infer::error_reporting::{
+    CannotSatisfyPredicate, TyCategory, TypeAnnotationNeeded as ErrorCode,
+};
 use crate::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use crate::infer::{self, InferCtxt, TyCtxtInferExt};
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{
     pluralize, struct_span_err, Applicability, Diagnostic, DiagnosticBuilder, ErrorGuaranteed,
     MultiSpan, Style,
@@ -28,11 +30,13 @@
 use rustc_middle::ty::abstract_const::NotConstEvaluatable;
 use rustc_middle::ty::error::ExpectedFound;
 use rustc_middle::ty::fold::{TypeFolder, TypeSuperFoldable};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::subst::Subst;
 use rustc_middle::ty::{
     self, SubtypePredicate, ToPolyTraitRef, ToPredicate, TraitRef, Ty, TyCtxt, TypeFoldable,
     TypeVisitable,
 };
-use rustc_span::symbol::{kw, sym};
+use rustc_span::symbol::{kw, sym, Symbol};
 use rustc_span::{ExpnKind, Span, DUMMY_SP};
 use std::fmt;
 use std::iter;
@@ -201,11 +205,46 @@ struct ErrorDescriptor<'tcx> {
             }
         }
 
+        // If no type could satisfy an ambiguous bound on an inference variable, that is what's
+        // reported, and the other ambiguities about the variable, which would ask for a type
+        // annotation, are left out.
+        let unsatisfiable_vars: FxHashSet<_> = iter::zip(errors, &is_suppressed)
+            .filter(|&(error, &suppressed)| {
+                !suppressed && matches!(error.code, FulfillmentErrorCode::CodeAmbiguity)
+            })
+            .filter_map(|(error, _)| self.unsatisfiable_self_ty_var(&error.obligation))
+            .collect();
+        if !unsatisfiable_vars.is_empty() {
+            for (error, suppressed) in iter::zip(errors, &mut is_suppressed) {
+                if matches!(error.code, FulfillmentErrorCode::CodeAmbiguity)
+                    && let Some(vid) = self.ambiguous_self_ty_var(error.obligation.predicate)
+                    && unsatisfiable_vars.contains(&vid)
+                    && self.unsatisfiable_self_ty_var(&error.obligation).is_none()
+                {
+                    *suppressed = true;
+                }
+            }
+        }
+
+        // The errors come in the order the obligations were processed in, which has
+        // nothing to do with the source, so the "type annotations needed" errors are
+        // emitted in source order once all of them are known.
+        let mut ambiguity_errors = vec![];
         for (error, suppressed) in iter::zip(errors, is_suppressed) {
             if !suppressed {
-                self.report_fulfillment_error(error, body_id, fallback_has_occurred);
+                self.report_fulfillment_error(
+                    error,
+                    body_id,
+                    fallback_has_occurred,
+                    &mut ambiguity_errors,
+                );
             }
         }
+        ambiguity_errors.sort_by_key(|diag| diag.sort_span);
+        for mut diag in ambiguity_errors {
+            self.charge_inference_error_budget(&mut diag);
+            self.tcx.sess.diagnostic().emit_diagnostic(&mut diag);
+        }
 
         self.tcx.sess.delay_span_bug(DUMMY_SP, "expected fullfillment errors")
     }
@@ -531,6 +570,14 @@ fn report_selection_error(
                             err.span_label(enclosing_scope_span, s);
                         }
 
+                        if self.tcx.is_diagnostic_item(sym::FromResidual, trait_ref.def_id()) {
+                            self.suggest_returning_result_from_entry_fn(
+                                &mut err,
+                                &obligation,
+                                trait_predicate,
+                            );
+                        }
+
                         self.suggest_floating_point_literal(&obligation, &mut err, &trait_ref);
                         self.suggest_dereferencing_index(&obligation, &mut err, trait_predicate);
                         let mut suggested =
@@ -545,6 +592,7 @@ fn report_selection_error(
                             trait_predicate,
                         );
                         self.note_version_mismatch(&mut err, &trait_ref);
+                        self.suggest_enabling_feature_for_cfg_stripped_impl(&mut err, trait_ref);
                         self.suggest_remove_await(&obligation, &mut err);
                         self.suggest_derive(&obligation, &mut err, trait_predicate);
 
@@ -1331,6 +1379,7 @@ fn report_fulfillment_error(
         error: &FulfillmentError<'tcx>,
         body_id: Option<hir::BodyId>,
         fallback_has_occurred: bool,
+        ambiguity_errors: &mut Vec<Diagnostic>,
     );
 
     fn report_projection_error(
@@ -1383,6 +1432,15 @@ fn note_version_mismatch(
         trait_ref: &ty::PolyTraitRef<'tcx>,
     ) -> bool;
 
+    /// If a dependency would implement the unsatisfied trait `trait_ref` if one of its features
+    /// was enabled, a help message about that feature is added to `err`. This only knows about
+    /// dependencies built with `-Zmetadata-include-cfg-impls`.
+    fn suggest_enabling_feature_for_cfg_stripped_impl(
+        &self,
+        err: &mut Diagnostic,
+        trait_ref: ty::PolyTraitRef<'tcx>,
+    );
+
     /// Creates a `PredicateObligation` with `new_self_ty` replacing the existing type in the
     /// `trait_ref`.
     ///
@@ -1393,10 +1451,13 @@ fn mk_trait_obligation_with_new_self_ty(
         trait_ref_and_ty: ty::Binder<'tcx, (ty::TraitPredicate<'tcx>, Ty<'tcx>)>,
     ) -> PredicateObligation<'tcx>;
 
+    /// Buffers the error for an ambiguous `obligation` in `ambiguity_errors`, unless it is
+    /// likely fallout from earlier errors.
     fn maybe_report_ambiguity(
         &self,
         obligation: &PredicateObligation<'tcx>,
         body_id: Option<hir::BodyId>,
+        ambiguity_errors: &mut Vec<Diagnostic>,
     );
 
     fn predicate_can_apply(
@@ -1405,6 +1466,29 @@ fn predicate_can_apply(
         pred: ty::PolyTraitRef<'tcx>,
     ) -> bool;
 
+    /// The root of the inference variable which is the self type of the trait predicate
+    /// `predicate`, if it is one.
+    fn ambiguous_self_ty_var(&self, predicate: ty::Predicate<'tcx>) -> Option<ty::TyVid>;
+
+    /// The root of the inference variable which is the self type of the ambiguous trait
+    /// obligation `obligation`, if no type could satisfy the obligation.
+    ///
+    /// Selection doesn't look at any candidates for a self type which is an inference variable,
+    /// so such obligations are ambiguous even if none of the impls of the trait nor the where
+    /// clauses in scope apply to the rest of the trait reference. As only the trait references
+    /// of the impls are unified with it, an impl with where clauses that can't hold still counts
+    /// as applying.
+    fn unsatisfiable_self_ty_var(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+    ) -> Option<ty::TyVid>;
+
+    fn report_unsatisfiable_ambiguity(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::PolyTraitRef<'tcx>,
+    ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed>;
+
     fn note_obligation_cause(&self, err: &mut Diagnostic, obligation: &PredicateObligation<'tcx>);
 
     fn suggest_unsized_bound_if_applicable(
@@ -1420,6 +1504,19 @@ fn annotate_source_of_ambiguity(
         predicate: ty::Predicate<'tcx>,
     );
 
+    fn note_normalization_cycle(
+        &self,
+        err: &mut Diagnostic,
+        param_env: ty::ParamEnv<'tcx>,
+        projection_ty: ty::ProjectionTy<'tcx>,
+    );
+
+    fn normalization_source_span(
+        &self,
+        param_env: ty::ParamEnv<'tcx>,
+        projection_ty: ty::ProjectionTy<'tcx>,
+    ) -> Option<Span>;
+
     fn maybe_suggest_unsized_generics(&self, err: &mut Diagnostic, span: Span, node: Node<'hir>);
 
     fn maybe_indirection_for_unsized(
@@ -1434,6 +1531,8 @@ fn is_recursive_obligation(
         obligated_types: &mut Vec<Ty<'tcx>>,
         cause_code: &ObligationCauseCode<'tcx>,
     ) -> bool;
+
+    fn is_fallout_of_question_mark_error(&self, obligation: &PredicateObligation<'tcx>) -> bool;
 }
 
 impl<'a, 'tcx> InferCtxtPrivExt<'a, 'tcx> for InferCtxt<'a, 'tcx> {
@@ -1481,6 +1580,7 @@ fn report_fulfillment_error(
         error: &FulfillmentError<'tcx>,
         body_id: Option<hir::BodyId>,
         fallback_has_occurred: bool,
+        ambiguity_errors: &mut Vec<Diagnostic>,
     ) {
         match error.code {
             FulfillmentErrorCode::CodeSelectionError(ref selection_error) => {
@@ -1495,7 +1595,7 @@ fn report_fulfillment_error(
                 self.report_projection_error(&error.obligation, e);
             }
             FulfillmentErrorCode::CodeAmbiguity => {
-                self.maybe_report_ambiguity(&error.obligation, body_id);
+                self.maybe_report_ambiguity(&error.obligation, body_id, ambiguity_errors);
             }
             FulfillmentErrorCode::CodeSubtypeError(ref expected_found, ref err) => {
                 self.report_mismatched_types(
@@ -1968,6 +2068,50 @@ fn note_version_mismatch(
         suggested
     }
 
+    fn suggest_enabling_feature_for_cfg_stripped_impl(
+        &self,
+        err: &mut Diagnostic,
+        trait_ref: ty::PolyTraitRef<'tcx>,
+    ) {
+        let trait_def_id = trait_ref.def_id();
+        let self_ty = self.resolve_vars_if_possible(trait_ref.skip_binder().self_ty());
+        // The stripped impls only know the paths they were written with, so the self type has to
+        // be a nominal type.
+        let self_ty_def_id = match *self_ty.kind() {
+            ty::Adt(def, _) => def.did(),
+            ty::Foreign(def_id) => def_id,
+            _ => return,
+        };
+        // Whether an item written as `written_path` in a stripped impl can be `def_id`. Paths
+        // imported with a `use` are only compared by the segments they were written with.
+        let may_name = |written_path: Symbol, def_id: DefId| {
+            let def_path = with_no_trimmed_paths!(self.tcx.def_path_str(def_id));
+            let def_path: Vec<_> = def_path.split("::").collect();
+            let written_path: Vec<_> = written_path.as_str().split("::").collect();
+            def_path.ends_with(&written_path)
+        };
+        for &cnum in self.tcx.crates(()) {
+            // Coherence requires the trait or the self type of an impl to be from its crate.
+            if trait_def_id.krate != cnum && self_ty_def_id.krate != cnum {
+                continue;
+            }
+            for stripped_impl in self.tcx.cfg_stripped_impls(cnum) {
+                if may_name(stripped_impl.trait_path, trait_def_id)
+                    && may_name(stripped_impl.self_ty_path, self_ty_def_id)
+                {
+                    err.help(&format!(
+                        "an implementation of `{}` for `{}` exists in crate `{}`, but requires \
+                         enabling its `{}` feature",
+                        self.tcx.item_name(trait_def_id),
+                        self.tcx.item_name(self_ty_def_id),
+                        self.tcx.crate_name(cnum),
+                        stripped_impl.feature,
+                    ));
+                }
+            }
+        }
+    }
+
     fn mk_trait_obligation_with_new_self_ty(
         &self,
         param_env: ty::ParamEnv<'tcx>,
@@ -1989,6 +2133,7 @@ fn maybe_report_ambiguity(
         &self,
         obligation: &PredicateObligation<'tcx>,
         body_id: Option<hir::BodyId>,
+        ambiguity_errors: &mut Vec<Diagnostic>,
     ) {
         // Unable to successfully determine, probably means
         // insufficient type information, but could mean
@@ -2003,8 +2148,22 @@ fn maybe_report_ambiguity(
         // Ambiguity errors are often caused as fallout from earlier errors.
         // We ignore them if this `infcx` is tainted in some cases below.
 
+        if self.is_fallout_of_question_mark_error(obligation) {
+            self.set_tainted_by_errors();
+            return;
+        }
+
         let bound_predicate = predicate.kind();
         let mut err = match bound_predicate.skip_binder() {
+            ty::PredicateKind::Trait(data)
+                if !self.is_tainted_by_errors()
+                    && self.unsatisfiable_self_ty_var(obligation).is_some() =>
+            {
+                self.report_unsatisfiable_ambiguity(
+                    obligation,
+                    bound_predicate.rebind(data.trait_ref),
+                )
+            }
             ty::PredicateKind::Trait(data) => {
                 let trait_ref = bound_predicate.rebind(data.trait_ref);
                 debug!(?trait_ref);
@@ -2037,7 +2196,10 @@ fn maybe_report_ambiguity(
                             ErrorCode::E0282,
                             false,
                         )
-                        .emit();
+                        .buffer(ambiguity_errors);
+                        // The buffered error hasn't been emitted yet, but later ambiguities
+                        // should still be considered fallout from it.
+                        self.set_tainted_by_errors();
                     }
                     return;
                 }
@@ -2091,6 +2253,8 @@ fn maybe_report_ambiguity(
                     }
                 }
 
+                self.suggest_enabling_feature_for_cfg_stripped_impl(&mut err, trait_ref);
+
                 if let ObligationCauseCode::ItemObligation(def_id) = *obligation.cause.code() {
                     self.suggest_fully_qualified_path(&mut err, def_id, span, trait_ref.def_id());
                 } else if let (
@@ -2149,6 +2313,25 @@ fn maybe_report_ambiguity(
                     }
                 }
 
+                // Selection doesn't look at impls at all when the self type is an inference
+                // variable, so check ourselves whether the trait has a single, non-generic impl.
+                let mut impls = self.tcx.all_impls(trait_ref.def_id());
+                let single_candidate = match (impls.next(), impls.next()) {
+                    (Some(impl_def_id), None) => self
+                        .tcx
+                        .impl_trait_ref(impl_def_id)
+                        .map(|impl_trait_ref| impl_trait_ref.self_ty())
+                        .filter(|self_ty| !self_ty.needs_subst()),
+                    _ => None,
+                };
+                // This comes after the turbofish suggestion above, which clears the suggestions.
+                self.note_combinator_closure_param(
+                    &mut err,
+                    body_id,
+                    trait_ref.self_ty().skip_binder(),
+                    single_candidate,
+                );
+
                 if let (Some(body_id), Some(ty::subst::GenericArgKind::Type(_))) =
                     (body_id, subst.map(|subst| subst.unpack()))
                 {
@@ -2279,16 +2462,16 @@ fn visit_expr(&mut self, ex: &'v hir::Expr<'v>) {
                     .iter()
                     .chain(Some(data.term.into_arg()))
                     .find(|g| g.has_infer_types_or_consts());
-                if let Some(subst) = subst {
-                    let mut err = self.emit_inference_failure_err(
+                let mut err = if let Some(subst) = subst {
+                    let mut failure = self.build_inference_failure(
                         body_id,
                         span,
                         subst,
                         ErrorCode::E0284,
                         true,
                     );
-                    err.note(&format!("cannot satisfy `{}`", predicate));
-                    err
+                    failure.subdiagnostic(CannotSatisfyPredicate { predicate });
+                    failure.into_diagnostic()
                 } else {
                     // If we can't find a substitution, just print a generic error
                     let mut err = struct_span_err!(
@@ -2300,7 +2483,9 @@ fn visit_expr(&mut self, ex: &'v hir::Expr<'v>) {
                     );
                     err.span_label(span, &format!("cannot satisfy `{}`", predicate));
                     err
-                }
+                };
+                self.note_normalization_cycle(&mut err, obligation.param_env, data.projection_ty);
+                err
             }
 
             ty::PredicateKind::ConstEvaluatable(data) => {
@@ -2346,7 +2531,8 @@ fn visit_expr(&mut self, ex: &'v hir::Expr<'v>) {
             }
         };
         self.note_obligation_cause(&mut err, obligation);
-        err.emit();
+        err.buffer(ambiguity_errors);
+        self.set_tainted_by_errors();
     }
 
     fn annotate_source_of_ambiguity(
@@ -2355,20 +2541,8 @@ fn annotate_source_of_ambiguity(
         impls: &[DefId],
         predicate: ty::Predicate<'tcx>,
     ) {
-        let mut spans = vec![];
-        let mut crates = vec![];
-        let mut post = vec![];
-        for def_id in impls {
-            match self.tcx.span_of_impl(*def_id) {
-                Ok(span) => spans.push(span),
-                Err(name) => {
-                    crates.push(name);
-                    if let Some(header) = to_pretty_impl_header(self.tcx, *def_id) {
-                        post.push(header);
-                    }
-                }
-            }
-        }
+        let AmbiguityCandidates { spans, crates, headers: mut post, records } =
+            AmbiguityCandidates::new(self.tcx, impls);
         let msg = format!("multiple `impl`s satisfying `{}` found", predicate);
         let mut crate_names: Vec<_> = crates.iter().map(|n| format!("`{}`", n)).collect();
         crate_names.sort();
@@ -2390,6 +2564,7 @@ fn annotate_source_of_ambiguity(
             err.downgrade_to_delayed_bug();
             return;
         }
+        err.metadata.impl_candidates = records;
         let post = if post.len() > 4 {
             format!(
                 ":\n{}\nand {} more",
@@ -2442,6 +2617,99 @@ fn annotate_source_of_ambiguity(
         }
     }
 
+    fn ambiguous_self_ty_var(&self, predicate: ty::Predicate<'tcx>) -> Option<ty::TyVid> {
+        let ty::PredicateKind::Trait(data) = predicate.kind().skip_binder() else {
+            return None;
+        };
+        match *self.shallow_resolve(data.self_ty()).kind() {
+            ty::Infer(ty::TyVar(vid)) => Some(self.root_var(vid)),
+            _ => None,
+        }
+    }
+
+    fn unsatisfiable_self_ty_var(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+    ) -> Option<ty::TyVid> {
+        let tcx = self.tcx;
+        let predicate = self.resolve_vars_if_possible(obligation.predicate);
+        let vid = self.ambiguous_self_ty_var(predicate)?;
+        let trait_pred = predicate.to_opt_poly_trait_pred()?;
+        let trait_ref = trait_pred.to_poly_trait_ref().no_bound_vars()?;
+        let def_id = trait_ref.def_id;
+        if predicate.references_error()
+            || trait_pred.skip_binder().polarity != ty::ImplPolarity::Positive
+            // Auto traits and the traits with builtin impls are implemented without an `impl`.
+            // Trait objects also are, but aren't considered, as they're rarely what was meant,
+            // see `report_unsatisfiable_ambiguity`.
+            || tcx.trait_is_auto(def_id)
+            || tcx.lang_items().items().contains(&Some(def_id))
+            // Uses of the items of the trait itself get E0790, which says how to name an impl.
+            || matches!(
+                *obligation.cause.code(),
+                ObligationCauseCode::ItemObligation(item_def_id)
+                | ObligationCauseCode::BindingObligation(item_def_id, _)
+                    if tcx.trait_of_item(item_def_id) == Some(def_id)
+            )
+            || obligation
+                .param_env
+                .caller_bounds()
+                .iter()
+                .filter_map(|bound| bound.to_opt_poly_trait_pred())
+                .any(|bound| bound.def_id() == def_id)
+        {
+            return None;
+        }
+
+        let may_apply = |impl_def_id| {
+            self.probe(|_| {
+                let impl_substs = self.fresh_substs_for_item(obligation.cause.span, impl_def_id);
+                let impl_trait_ref = tcx.bound_impl_trait_ref(impl_def_id).unwrap();
+                let impl_trait_ref = impl_trait_ref.subst(tcx, impl_substs);
+                self.can_eq(obligation.param_env, trait_ref, impl_trait_ref).is_ok()
+            })
+        };
+        let mut impls = tcx
+            .all_impls(def_id)
+            .filter(|&impl_def_id| tcx.impl_polarity(impl_def_id) != ty::ImplPolarity::Negative);
+        if impls.any(may_apply) { None } else { Some(vid) }
+    }
+
+    fn report_unsatisfiable_ambiguity(
+        &self,
+        obligation: &PredicateObligation<'tcx>,
+        trait_ref: ty::PolyTraitRef<'tcx>,
+    ) -> DiagnosticBuilder<'tcx, ErrorGuaranteed> {
+        let predicate = self.resolve_vars_if_possible(obligation.predicate);
+        let span = obligation.cause.span;
+        let trait_path = trait_ref.print_only_trait_path();
+        let mut err = struct_span_err!(
+            self.tcx.sess,
+            span,
+            E0277,
+            "no `impl` can satisfy the trait bound `{}`",
+            predicate,
+        );
+        err.span_label(span, format!("no `impl` of `{}` applies to any type", trait_path));
+        // The trait objects of object safe traits implement them without an `impl`, but are
+        // rarely what was meant, and can't be used where the type has to be `Sized`.
+        if self.tcx.is_object_safe(trait_ref.def_id()) {
+            err.note(&format!(
+                "no `impl` or bound in scope applies to `{}` for any type, so only trait objects \
+                 like `dyn {}` implement it, which can't be used if the type has to be `Sized`",
+                trait_path, trait_path,
+            ));
+        } else {
+            err.note(&format!(
+                "no `impl` or bound in scope applies to `{}` for any type, so a type annotation \
+                 can't fix this",
+                trait_path,
+            ));
+        }
+        self.report_similar_impl_candidates(vec![], trait_ref, obligation.cause.body_id, &mut err);
+        err
+    }
+
     /// Returns `true` if the trait predicate may apply for *some* assignment
     /// to the type parameters.
     fn predicate_can_apply(
@@ -2540,6 +2808,70 @@ fn suggest_unsized_bound_if_applicable(
         self.maybe_suggest_unsized_generics(err, span, node);
     }
 
+    /// Points out the normalization cycle `projection_ty` is part of, if any, as the
+    /// ambiguity is only fallout from it then.
+    fn note_normalization_cycle(
+        &self,
+        err: &mut Diagnostic,
+        param_env: ty::ParamEnv<'tcx>,
+        projection_ty: ty::ProjectionTy<'tcx>,
+    ) {
+        let projection_ty = self.resolve_vars_if_possible(projection_ty);
+        // Selecting the impls below may find more cycles, so don't hold on to the borrow.
+        let Some((cycle, start)) = self.normalization_cycles.borrow().iter().find_map(|cycle| {
+            let start = cycle.iter().position(|p| *p == projection_ty)?;
+            Some((cycle.clone(), start))
+        }) else {
+            return;
+        };
+        let path = |p: ty::ProjectionTy<'tcx>| self.tcx.mk_projection(p.item_def_id, p.substs);
+        for step in 0..cycle.len() {
+            let from = cycle[(start + step) % cycle.len()];
+            let to = cycle[(start + step + 1) % cycle.len()];
+            let msg = format!(
+                "{}. normalizing `{}` requires normalizing `{}`",
+                step + 1,
+                path(from),
+                path(to),
+            );
+            match self.normalization_source_span(param_env, from) {
+                Some(span) => err.span_note(span, &msg),
+                None => err.note(&msg),
+            };
+        }
+        err.note(&format!(
+            "...which again requires normalizing `{}`, completing the cycle",
+            path(projection_ty),
+        ));
+    }
+
+    /// Returns the span of the associated type definition `projection_ty` is normalized
+    /// with, if it is in the local crate.
+    fn normalization_source_span(
+        &self,
+        param_env: ty::ParamEnv<'tcx>,
+        projection_ty: ty::ProjectionTy<'tcx>,
+    ) -> Option<Span> {
+        let trait_ref = projection_ty.trait_ref(self.tcx);
+        let obligation = Obligation::new(
+            ObligationCause::dummy(),
+            param_env,
+            ty::Binder::dummy(trait_ref).to_poly_trait_predicate(),
+        );
+        let impl_def_id = self.probe(|_| match SelectionContext::new(self).select(&obligation) {
+            Ok(Some(ImplSource::UserDefined(data))) => Some(data.impl_def_id),
+            _ => None,
+        })?;
+        // Without a definition in the impl, the default from the trait is used.
+        let def_id = self
+            .tcx
+            .associated_items(impl_def_id)
+            .in_definition_order()
+            .find(|item| item.trait_item_def_id == Some(projection_ty.item_def_id))
+            .map_or(projection_ty.item_def_id, |item| item.def_id);
+        def_id.as_local().map(|def_id| self.tcx.def_span(def_id))
+    }
+
     #[instrument(level = "debug", skip_all)]
     fn maybe_suggest_unsized_generics<'hir>(
         &self,
@@ -2656,6 +2988,16 @@ fn is_recursive_obligation(
         }
         false
     }
+
+    /// Whether `obligation` is caused by a `?` in `main` or a test function which was reported
+    /// as unable to convert its residual into `()`, or by the expression the `?` is applied to,
+    /// see `InferCtxt::question_mark_errors`. Types like the `F` of `s.parse::<F>()?` are
+    /// usually inferred from the return type the residual is converted into, so in a function
+    /// returning `()`, that error is why they are ambiguous.
+    fn is_fallout_of_question_mark_error(&self, obligation: &PredicateObligation<'tcx>) -> bool {
+        let span = obligation.cause.span;
+        self.question_mark_errors.borrow().iter().any(|question_mark| question_mark.contains(span))
+    }
 }
 
 /// Look for type `param` in an ADT being used only through a reference to confirm that suggesting
@@ -2821,3 +3163,42 @@ fn visit_ty(&mut self, ty: Ty<'tcx>) -> ControlFlow<Self::BreakTy> {
         }
     }
 }
+
+/// The impls which could have been used for an ambiguous obligation, as described by both the
+/// notes of the error and its JSON metadata.
+struct AmbiguityCandidates {
+    /// The spans of the local impls.
+    spans: Vec<Span>,
+    /// The crates defining the other impls, once for each impl.
+    crates: Vec<Symbol>,
+    /// The headers of the impls from other crates.
+    headers: Vec<String>,
+    records: Vec<rustc_errors::ImplCandidate>,
+}
+
+impl AmbiguityCandidates {
+    fn new(tcx: TyCtxt<'_>, impls: &[DefId]) -> Self {
+        let mut candidates =
+            AmbiguityCandidates { spans: vec![], crates: vec![], headers: vec![], records: vec![] };
+        // Only impls of a trait can be candidates, and `to_pretty_impl_header` only fails for
+        // the others, so all three lists describe the same impls.
+        for &def_id in impls {
+            let Some(trait_ref) = tcx.impl_trait_ref(def_id) else { continue };
+            match tcx.span_of_impl(def_id) {
+                Ok(span) => candidates.spans.push(span),
+                Err(name) => {
+                    let Some(header) = to_pretty_impl_header(tcx, def_id) else { continue };
+                    candidates.crates.push(name);
+                    candidates.headers.push(header);
+                }
+            }
+            candidates.records.push(rustc_errors::ImplCandidate {
+                krate: tcx.crate_name(def_id.krate).to_string(),
+                self_ty: trait_ref.self_ty().to_string(),
+                trait_args: trait_ref.substs[1..].iter().map(|arg| arg.to_string()).collect(),
+                is_blanket: matches!(trait_ref.self_ty().kind(), ty::Param(_)),
+            });
+        }
+        candidates
+    }
+}
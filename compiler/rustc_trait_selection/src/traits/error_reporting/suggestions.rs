@@ -16,7 +16,7 @@
 };
 use rustc_hir as hir;
 use rustc_hir::def::DefKind;
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_hir::intravisit::Visitor;
 use rustc_hir::lang_items::LangItem;
 use rustc_hir::{AsyncGeneratorKind, GeneratorKind, Node};
@@ -308,6 +308,15 @@ fn suggest_await_before_try(
         span: Span,
     );
 
+    /// Suggest returning a `Result` from `main` or a test function which uses `?` on a `Result`
+    /// while returning `()`. Also remembers the `?`, see `InferCtxt::question_mark_errors`.
+    fn suggest_returning_result_from_entry_fn(
+        &self,
+        err: &mut Diagnostic,
+        obligation: &PredicateObligation<'tcx>,
+        trait_pred: ty::PolyTraitPredicate<'tcx>,
+    );
+
     fn suggest_floating_point_literal(
         &self,
         obligation: &PredicateObligation<'tcx>,
@@ -2843,6 +2852,68 @@ fn suggest_await_before_try(
         }
     }
 
+    fn suggest_returning_result_from_entry_fn(
+        &self,
+        err: &mut Diagnostic,
+        obligation: &PredicateObligation<'tcx>,
+        trait_pred: ty::PolyTraitPredicate<'tcx>,
+    ) {
+        if !obligation.cause.span.is_desugaring(DesugaringKind::QuestionMark) {
+            return;
+        }
+        let hir = self.tcx.hir();
+        let body_owner = hir.opt_local_def_id(obligation.cause.body_id).unwrap_or_else(|| {
+            hir.body_owner_def_id(hir::BodyId { hir_id: obligation.cause.body_id })
+        });
+        if !is_entry_or_test_fn(self.tcx, body_owner) {
+            return;
+        }
+        let Some(body_id) = hir.maybe_body_owned_by(body_owner) else {
+            return;
+        };
+        let body = hir.body(body_id);
+
+        let mut finder = QuestionMarkFinder { span: obligation.cause.span, question_mark: None };
+        finder.visit_body(body);
+        self.question_mark_errors
+            .borrow_mut()
+            .push(finder.question_mark.unwrap_or(obligation.cause.span));
+        let residual = trait_pred.skip_binder().trait_ref.substs.type_at(1);
+
+        let hir_id = hir.local_def_id_to_hir_id(body_owner);
+        let Some(decl) = hir.fn_decl_by_hir_id(hir_id) else {
+            return;
+        };
+        let hir::FnRetTy::DefaultReturn(ret_span) = decl.output else {
+            return;
+        };
+        let hir::ExprKind::Block(block, _) = body.value.kind else {
+            return;
+        };
+        let ty::Adt(residual_def, _) = residual.kind() else {
+            return;
+        };
+        if !self.tcx.is_diagnostic_item(sym::Result, residual_def.did()) {
+            return;
+        }
+        // The block has to end in `Ok(())` once the function returns a `Result`.
+        let (None, Some(last_stmt)) = (block.expr, block.stmts.last()) else {
+            return;
+        };
+        let sm = self.tcx.sess.source_map();
+        let Some(indent) = sm.indentation_before(last_stmt.span) else {
+            return;
+        };
+        err.multipart_suggestion_verbose(
+            "consider returning a `Result`, so that errors can be passed on with `?`",
+            vec![
+                (ret_span, " -> Result<(), Box<dyn std::error::Error>>".to_string()),
+                (last_stmt.span.shrink_to_hi(), format!("\n{}Ok(())", indent)),
+            ],
+            Applicability::MaybeIncorrect,
+        );
+    }
+
     fn suggest_floating_point_literal(
         &self,
         obligation: &PredicateObligation<'tcx>,
@@ -3090,6 +3161,46 @@ fn suggest_trait_object_return_type_alternatives(
     }
 }
 
+/// Finds the span of the innermost `expr?` whose desugaring contains `span`.
+struct QuestionMarkFinder {
+    span: Span,
+    question_mark: Option<Span>,
+}
+
+impl<'hir> Visitor<'hir> for QuestionMarkFinder {
+    fn visit_expr(&mut self, expr: &'hir hir::Expr<'hir>) {
+        // `expr?` is lowered to `match Try::branch(expr) { .. }`.
+        if let hir::ExprKind::Match(_, _, hir::MatchSource::TryDesugar) = expr.kind
+            && expr.span.contains(self.span)
+        {
+            self.question_mark = Some(expr.span);
+        }
+        hir::intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Whether `def_id` is the `main` function of the crate, like the one wrapped around a doctest,
+/// or a `#[test]` function. Their signatures are usually not given much thought, so errors from
+/// them returning `()` shouldn't be buried under their fallout.
+fn is_entry_or_test_fn(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
+    if tcx.entry_fn(()).map_or(false, |(entry_def_id, _)| entry_def_id == def_id.to_def_id()) {
+        return true;
+    }
+    // `#[test]` leaves the function alone and adds a `#[rustc_test_marker]` constant of the same
+    // name next to it.
+    let hir = tcx.hir();
+    let Some(name) = hir.opt_name(hir.local_def_id_to_hir_id(def_id)) else {
+        return false;
+    };
+    let module = tcx.parent_module(hir.local_def_id_to_hir_id(def_id));
+    hir.module_items(module).any(|item_id| {
+        let item = hir.item(item_id);
+        item.ident.name == name
+            && matches!(item.kind, hir::ItemKind::Const(..))
+            && tcx.has_attr(item.def_id.to_def_id(), sym::rustc_test_marker)
+    })
+}
+
 /// Collect the spans that we see the generic param `param_did`
 struct ReplaceImplTraitVisitor<'a> {
     ty_spans: &'a mut Vec<Span>,
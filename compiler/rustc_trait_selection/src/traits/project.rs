@@ -1019,6 +1019,7 @@ fn opt_normalize_projection_type<'a, 'b, 'tcx>(
             if use_cache {
                 infcx.inner.borrow_mut().projection_cache().recur(cache_key);
             }
+            selcx.record_normalization_cycle(projection_ty);
             return Err(InProgress);
         }
         Err(ProjectionCacheEntry::Recur) => {
@@ -1051,7 +1052,7 @@ fn opt_normalize_projection_type<'a, 'b, 'tcx>(
 
     let obligation = Obligation::with_depth(cause.clone(), depth, param_env, projection_ty);
 
-    match project(selcx, &obligation) {
+    selcx.with_normalization_of(projection_ty, |selcx| match project(selcx, &obligation) {
         Ok(Projected::Progress(Progress {
             term: projected_term,
             obligations: mut projected_obligations,
@@ -1121,7 +1122,7 @@ fn opt_normalize_projection_type<'a, 'b, 'tcx>(
             obligations.extend(result.obligations);
             Ok(Some(result.value.into()))
         }
-    }
+    })
 }
 
 /// If we are projecting `<T as Trait>::Item`, but `T: Trait` does not
@@ -134,6 +134,10 @@ pub struct SelectionContext<'cx, 'tcx> {
     /// policy. In essence, canonicalized queries need their errors propagated
     /// rather than immediately reported because we do not have accurate spans.
     query_mode: TraitQueryMode,
+
+    /// The projections which are being normalized, innermost last, to tell which
+    /// projections take part in a normalization cycle.
+    normalization_stack: Vec<ty::ProjectionTy<'tcx>>,
 }
 
 // A stack that walks back up the stack frame.
@@ -222,6 +226,7 @@ pub fn new(infcx: &'cx InferCtxt<'cx, 'tcx>) -> SelectionContext<'cx, 'tcx> {
             intercrate: false,
             intercrate_ambiguity_causes: None,
             query_mode: TraitQueryMode::Standard,
+            normalization_stack: vec![],
         }
     }
 
@@ -232,6 +237,7 @@ pub fn intercrate(infcx: &'cx InferCtxt<'cx, 'tcx>) -> SelectionContext<'cx, 'tc
             intercrate: true,
             intercrate_ambiguity_causes: None,
             query_mode: TraitQueryMode::Standard,
+            normalization_stack: vec![],
         }
     }
 
@@ -246,6 +252,36 @@ pub fn with_query_mode(
             intercrate: false,
             intercrate_ambiguity_causes: None,
             query_mode,
+            normalization_stack: vec![],
+        }
+    }
+
+    /// Runs `f`, which normalizes `projection_ty`, with `projection_ty` on the normalization
+    /// stack.
+    pub(super) fn with_normalization_of<R>(
+        &mut self,
+        projection_ty: ty::ProjectionTy<'tcx>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.normalization_stack.push(projection_ty);
+        let result = f(self);
+        self.normalization_stack.pop();
+        result
+    }
+
+    /// Records the projections from `projection_ty` to the innermost one being normalized
+    /// as a cycle, for when normalizing them requires normalizing `projection_ty` again.
+    pub(super) fn record_normalization_cycle(&self, projection_ty: ty::ProjectionTy<'tcx>) {
+        let Some(start) = self.normalization_stack.iter().rposition(|p| *p == projection_ty) else {
+            return;
+        };
+        let cycle = &self.normalization_stack[start..];
+        let mut cycles = self.infcx.normalization_cycles.borrow_mut();
+        let is_known = cycles
+            .iter()
+            .any(|known| known.len() == cycle.len() && cycle.iter().all(|p| known.contains(p)));
+        if !is_known {
+            cycles.push(cycle.to_vec());
         }
     }
 
@@ -477,7 +477,9 @@ fn typeck_with_fallback<'tcx>(
 
         fcx.infcx.skip_region_resolution();
 
-        fcx.resolve_type_vars_in_body(body)
+        let typeck_results = fcx.resolve_type_vars_in_body(body);
+        fcx.infcx.emit_inference_error_summaries();
+        typeck_results
     });
 
     // Consistency check our TypeckResults instance can hold all ItemLocalIds
@@ -57,6 +57,7 @@
 /// ```
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg_attr(not(test), rustc_diagnostic_item = "IterMap")]
 #[derive(Clone)]
 pub struct Map<I, F> {
     // Used for `SplitWhitespace` and `SplitAsciiWhitespace` `as_str` methods
@@ -0,0 +1,13 @@
+#![crate_type = "lib"]
+
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[cfg(feature = "display")]
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
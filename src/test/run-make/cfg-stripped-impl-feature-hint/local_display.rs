@@ -0,0 +1,10 @@
+extern crate geometry;
+
+// Not the `std::fmt::Display` that `geometry` implements with its `display` feature.
+trait Display {}
+
+fn show(_: impl Display) {}
+
+fn main() {
+    show(geometry::Point { x: 1.0, y: 2.0 });
+}
@@ -0,0 +1,6 @@
+extern crate geometry;
+
+fn main() {
+    let point = geometry::Point { x: 1.0, y: 2.0 };
+    println!("{}", point);
+}
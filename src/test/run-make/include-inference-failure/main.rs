@@ -0,0 +1,5 @@
+include!(concat!(env!("OUT_DIR"), "/gen.rs"));
+
+fn main() {
+    generated();
+}
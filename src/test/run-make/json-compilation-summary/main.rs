@@ -0,0 +1,17 @@
+fn empty_vec() {
+    let _v = Vec::new();
+}
+
+fn nothing() {
+    let _n = None;
+}
+
+fn number() -> u32 {
+    "one"
+}
+
+fn main() {
+    empty_vec();
+    nothing();
+    number();
+}
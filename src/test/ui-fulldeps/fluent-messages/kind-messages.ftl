@@ -0,0 +1,14 @@
+kind_messages_some_slug = hi
+    .suggestion = add a type
+
+# kind: suggestion
+kind_messages_some_suggestion = consider adding a type
+
+# This is a label.
+# kind: label
+kind_messages_some_label = this has no type
+
+# kind: note
+kind_messages_some_note = types are needed here
+
+kind_messages_undeclared_label = this has no type either
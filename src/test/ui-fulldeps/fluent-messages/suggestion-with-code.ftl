@@ -0,0 +1,4 @@
+infer_some_slug = hi
+    .suggestion = use `IntoIterator::into_iter(..)` instead
+    .short_suggestion = use `into_iter` instead
+    .argument_suggestion = use `{$long_function_name}` instead
@@ -73,6 +73,17 @@ mod label_with_hyphens {
     }
 }
 
+mod suggestion_with_code {
+    use super::fluent_messages;
+
+    // Only checked for the resources which opt into it.
+    fluent_messages! {
+        #[code_free_suggestions]
+        infer => "./suggestion-with-code.ftl",
+//~^ ERROR suggestion `infer_some_slug.suggestion` contains the code
+    }
+}
+
 mod valid {
     use super::fluent_messages;
 
@@ -82,3 +93,46 @@ mod valid {
 
     use self::fluent_generated::{DEFAULT_LOCALE_RESOURCES, valid::valid};
 }
+
+/// Copies of the message types constructed by `fluent_messages` for the messages which declare
+/// a kind of subdiagnostic, as it expects them to exist in `crate`.
+macro_rules! kind_message_types {
+    ($($name:ident),*) => {
+        $(
+            pub struct $name(DiagnosticMessage);
+
+            impl $name {
+                pub const fn new(message: DiagnosticMessage) -> Self {
+                    $name(message)
+                }
+            }
+        )*
+    };
+}
+
+kind_message_types!(SuggestionMessage, LabelMessage, NoteMessage);
+
+mod kind_messages {
+    use super::{fluent_messages, DiagnosticMessage, LabelMessage, NoteMessage, SuggestionMessage};
+
+    // Only the messages whose comment declares a kind of subdiagnostic get that kind's type, other
+    // messages are left alone, whatever their names.
+    fluent_messages! {
+        kind_messages => "./kind-messages.ftl",
+    }
+
+    const _: DiagnosticMessage = fluent_generated::kind_messages::some_slug;
+    const _: SuggestionMessage = fluent_generated::kind_messages::some_suggestion;
+    const _: LabelMessage = fluent_generated::kind_messages::some_label;
+    const _: NoteMessage = fluent_generated::kind_messages::some_note;
+    const _: DiagnosticMessage = fluent_generated::kind_messages::undeclared_label;
+}
+
+mod unknown_kind {
+    use super::fluent_messages;
+
+    fluent_messages! {
+        unknown_kind => "./unknown-kind.ftl",
+//~^ ERROR message `unknown_kind_some_warning` declares the unknown kind `warning`
+    }
+}
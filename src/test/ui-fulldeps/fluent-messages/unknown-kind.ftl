@@ -0,0 +1,2 @@
+# kind: warning
+unknown_kind_some_warning = types are needed here
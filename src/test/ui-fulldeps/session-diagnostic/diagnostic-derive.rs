@@ -566,3 +566,66 @@ struct ErrorWithMultiSpan {
 struct ErrorWithWarn {
     val: String,
 }
+
+#[derive(SessionDiagnostic)]
+#[error(typeck::ambiguous_lifetime_bound, code = "E0123")]
+struct LabelOverlappingPrimary {
+    #[primary_span]
+    #[label]
+    span: Span,
+    #[label(typeck::label, overlaps_primary)]
+    other: Span,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(typeck::ambiguous_lifetime_bound, code = "E0123")]
+struct DuplicateLabels {
+    #[primary_span]
+    #[label]
+    #[label(typeck::label)]
+    //~^ ERROR `#[label]` is applied to this field more than once
+    span: Span,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(typeck::ambiguous_lifetime_bound, code = "E0123")]
+struct SuggestionAfterSpan {
+    #[primary_span]
+    #[label]
+    #[suggestion(code = ": {ty}", position = "after", applicability = "maybe-incorrect")]
+    binding: Span,
+    ty: String,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(typeck::ambiguous_lifetime_bound, code = "E0123")]
+struct SuggestionInvalidPosition {
+    #[primary_span]
+    #[suggestion(code = "...", position = "inside")]
+    //~^ ERROR invalid position, expected `before` or `after`
+    span: Span,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(typeck::ambiguous_lifetime_bound, code = "E0123")]
+struct SuggestionDuplicatePosition {
+    #[primary_span]
+    #[suggestion(code = "...", position = "before", position = "after")]
+    //~^ ERROR specified multiple times
+    span: Span,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(typeck::ambiguous_lifetime_bound, code = "E0123", no_dedup)]
+struct NoDedup {
+    #[primary_span]
+    span: Span,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(typeck::ambiguous_lifetime_bound, no_dedup, no_dedup)]
+//~^ ERROR specified multiple times
+struct DuplicateNoDedup {
+    #[primary_span]
+    span: Span,
+}
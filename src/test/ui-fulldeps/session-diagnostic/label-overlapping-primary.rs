@@ -0,0 +1,126 @@
+// run-pass
+// Tests how a `#[label(.., overlaps_primary)]` on a field holding the primary span is rendered:
+// it is merged into a note if the primary span is labeled already, and is the label of the
+// primary span otherwise, no matter the order of the fields.
+
+// ignore-beta
+// ignore-stable
+
+#![feature(rustc_private)]
+
+extern crate rustc_data_structures;
+extern crate rustc_errors;
+extern crate rustc_macros;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_data_structures::sync::Lrc;
+use rustc_errors::emitter::EmitterWriter;
+use rustc_errors::Handler;
+use rustc_macros::SessionDiagnostic;
+use rustc_session::parse::ParseSess;
+use rustc_session::SessionDiagnostic;
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::{BytePos, Span};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+#[derive(SessionDiagnostic)]
+#[error(typeck::field_multiply_specified_in_initializer, code = "E0062")]
+struct LabeledPrimary {
+    #[primary_span]
+    #[label]
+    span: Span,
+    #[label(typeck::previous_use_label, overlaps_primary)]
+    prev_span: Span,
+    ident: &'static str,
+}
+
+#[derive(SessionDiagnostic)]
+#[error(typeck::field_multiply_specified_in_initializer, code = "E0062")]
+struct UnlabeledPrimary {
+    #[label(typeck::previous_use_label, overlaps_primary)]
+    prev_span: Span,
+    #[primary_span]
+    span: Span,
+    ident: &'static str,
+}
+
+struct Shared(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Shared {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.lock().unwrap().flush()
+    }
+}
+
+/// Renders the error emitted by `emit` for `let x = 0;`, given the span of `x`.
+fn render(emit: impl FnOnce(&ParseSess, Span)) -> String {
+    let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    sm.new_source_file(PathBuf::from("test.rs").into(), "let x = 0;\n".to_owned());
+    let fallback_bundle =
+        rustc_errors::fallback_fluent_bundle(rustc_errors::DEFAULT_LOCALE_RESOURCES, false);
+
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let emitter = EmitterWriter::new(
+        Box::new(Shared(output.clone())),
+        Some(sm.clone()),
+        None,
+        fallback_bundle,
+        false,
+        false,
+        false,
+        None,
+        false,
+    );
+    let handler = Handler::with_emitter(true, None, Box::new(emitter));
+    let sess = ParseSess::with_span_handler(handler, sm);
+
+    emit(&sess, Span::with_root_ctxt(BytePos(4), BytePos(5)));
+
+    let bytes = output.lock().unwrap();
+    String::from_utf8(bytes.clone()).unwrap()
+}
+
+fn main() {
+    rustc_span::create_default_session_globals_then(|| check());
+}
+
+fn check() {
+    let labeled = render(|sess, span| {
+        LabeledPrimary { span, prev_span: span, ident: "x" }.into_diagnostic(sess).emit();
+    });
+    assert_eq!(
+        labeled,
+        "\
+error[E0062]: field `x` specified more than once
+ --> test.rs:1:5
+  |
+1 | let x = 0;
+  |     ^ used more than once
+  |
+  = note: first use of `x`
+
+"
+    );
+
+    let unlabeled = render(|sess, span| {
+        UnlabeledPrimary { prev_span: span, span, ident: "x" }.into_diagnostic(sess).emit();
+    });
+    assert_eq!(
+        unlabeled,
+        "\
+error[E0062]: field `x` specified more than once
+ --> test.rs:1:5
+  |
+1 | let x = 0;
+  |     ^ first use of `x`
+
+"
+    );
+}
@@ -520,3 +520,56 @@ struct AZ {
     #[primary_span]
     span: Span,
 }
+
+#[derive(SessionSubdiagnostic)]
+#[suggestion(parser::add_paren, code = r#"{var}.unwrap_or("")"#)]
+struct BA {
+    #[primary_span]
+    span: Span,
+    #[applicability]
+    applicability: Applicability,
+    var: String,
+}
+
+#[derive(SessionSubdiagnostic)]
+#[suggestion(
+    parser::add_paren,
+    code("let value = {var};", "value.unwrap_or_default()"),
+    applicability = "has-placeholders"
+)]
+struct BB {
+    #[primary_span]
+    span: Span,
+    var: String,
+}
+
+#[derive(SessionSubdiagnostic)]
+#[suggestion(
+    parser::add_paren,
+    code = ": {ty}",
+    position = "after",
+    applicability = "maybe-incorrect"
+)]
+struct BC {
+    #[primary_span]
+    span: Span,
+    ty: String,
+}
+
+#[derive(SessionSubdiagnostic)]
+#[suggestion(parser::add_paren, code = "...", position = "inside")]
+//~^ ERROR invalid position, expected `before` or `after`
+struct BD {
+    #[primary_span]
+    span: Span,
+    #[applicability]
+    applicability: Applicability,
+}
+
+#[derive(SessionSubdiagnostic)]
+#[label(parser::add_paren, position = "before")]
+//~^ ERROR `position` is not a valid nested attribute of a `label` attribute
+struct BE {
+    #[primary_span]
+    span: Span,
+}
@@ -0,0 +1,133 @@
+// run-pass
+// Tests that the `#[subdiagnostic]` fields of a `#[derive(SessionDiagnostic)]` are added to the
+// diagnostic after its other fields, with single-span suggestions before multipart ones regardless
+// of the order of the fields, and that its labels end up sorted by their spans.
+
+// ignore-beta
+// ignore-stable
+
+#![feature(rustc_private)]
+
+extern crate rustc_errors;
+extern crate rustc_macros;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_errors::{
+    fluent, AddSubdiagnostic, Applicability, Diagnostic, MultiSpan, SubdiagnosticPriority,
+};
+use rustc_macros::{SessionDiagnostic, SessionSubdiagnostic};
+use rustc_session::parse::ParseSess;
+use rustc_session::SessionDiagnostic;
+use rustc_span::source_map::FilePathMapping;
+use rustc_span::{BytePos, Span};
+
+#[derive(SessionSubdiagnostic)]
+#[label(parser::add_paren)]
+struct Label {
+    #[primary_span]
+    span: Span,
+}
+
+#[derive(SessionSubdiagnostic)]
+#[note(parser::forgot_paren)]
+struct Note {
+    #[primary_span]
+    span: Span,
+}
+
+#[derive(SessionSubdiagnostic)]
+#[help(parser::expect_path)]
+struct Help {
+    #[primary_span]
+    span: Span,
+}
+
+#[derive(SessionSubdiagnostic)]
+#[suggestion(parser::add_paren, code = "()", applicability = "machine-applicable")]
+struct Suggestion {
+    #[primary_span]
+    span: Span,
+}
+
+struct Multipart {
+    spans: [Span; 2],
+}
+
+impl AddSubdiagnostic for Multipart {
+    const PRIORITY: SubdiagnosticPriority = SubdiagnosticPriority::MultipartSuggestion;
+
+    fn add_to_diagnostic(self, diag: &mut Diagnostic) {
+        diag.multipart_suggestion(
+            fluent::parser::add_paren,
+            self.spans.into_iter().map(|span| (span, "()".to_string())).collect(),
+            Applicability::MachineApplicable,
+        );
+    }
+}
+
+#[derive(SessionDiagnostic)]
+#[error(parser::expect_path)]
+struct Scrambled {
+    #[subdiagnostic]
+    first_label: Label,
+    #[subdiagnostic]
+    multipart: Multipart,
+    #[subdiagnostic]
+    suggestion: Suggestion,
+    #[subdiagnostic]
+    note: Option<Note>,
+    #[subdiagnostic]
+    labels: Vec<Label>,
+    #[subdiagnostic]
+    help: Help,
+    // Moved like the subdiagnostics, so setting it after them would drop their labels.
+    #[primary_span]
+    span: MultiSpan,
+}
+
+fn span(lo: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(lo + 1))
+}
+
+fn main() {
+    rustc_span::create_default_session_globals_then(|| check());
+}
+
+fn check() {
+    let sess = ParseSess::new(FilePathMapping::empty());
+    let diag = Scrambled {
+        first_label: Label { span: span(5) },
+        multipart: Multipart { spans: [span(6), span(7)] },
+        suggestion: Suggestion { span: span(4) },
+        note: Some(Note { span: span(2) }),
+        labels: vec![Label { span: span(1) }, Label { span: span(0) }],
+        help: Help { span: span(3) },
+        span: MultiSpan::from_span(span(0)),
+    }
+    .into_diagnostic(&sess);
+
+    let labels: Vec<_> = diag
+        .span
+        .span_labels()
+        .into_iter()
+        .filter(|label| label.label.is_some())
+        .map(|label| label.span)
+        .collect();
+    assert_eq!(labels, [span(0), span(1), span(5)]);
+
+    let children: Vec<_> =
+        diag.children.iter().map(|child| child.span.primary_span().unwrap()).collect();
+    assert_eq!(children, [span(2), span(3)]);
+
+    let suggestions: Vec<Vec<_>> = diag
+        .suggestions
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|suggestion| suggestion.substitutions[0].parts.iter().map(|part| part.span).collect())
+        .collect();
+    assert_eq!(suggestions, [vec![span(4)], vec![span(6), span(7)]]);
+
+    diag.cancel();
+}
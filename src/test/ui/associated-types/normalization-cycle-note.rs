@@ -0,0 +1,19 @@
+// Check that ambiguities caused by a normalization cycle point out the cycle.
+
+#![feature(associated_type_defaults)]
+
+trait First {
+    type Out = <Self as Second>::Out;
+}
+
+trait Second {
+    type Out = <Self as First>::Out;
+}
+
+impl First for () {}
+impl Second for () {}
+
+fn main() {
+    let _x: <() as First>::Out = loop {};
+    //~^ ERROR type annotations needed
+}
@@ -0,0 +1,35 @@
+// Closures passed to `thread::spawn` have to be `'static`, which is explained both when the
+// closure borrows a local itself and when a `move` closure captures a reference to one. A
+// `&'static` reference captured along with it doesn't borrow from the function, and data that
+// can't be cloned can only be shared.
+
+use std::thread;
+
+fn borrows_local() {
+    let v = vec![1, 2, 3];
+    thread::spawn(|| v.len());
+    //~^ ERROR closure may outlive the current function, but it borrows `v`
+}
+
+fn moves_reference() {
+    let v = vec![1, 2, 3];
+    let r = &v; //~ ERROR `v` does not live long enough
+    thread::spawn(move || r.len());
+}
+
+fn moves_reference_and_static() {
+    let v = vec![1, 2, 3];
+    let s: &'static str = "static";
+    let r = &v; //~ ERROR `v` does not live long enough
+    thread::spawn(move || r.len() + s.len());
+}
+
+struct NotClone(Vec<i32>);
+
+fn moves_reference_to_uncloneable() {
+    let v = NotClone(vec![1, 2, 3]);
+    let r = &v; //~ ERROR `v` does not live long enough
+    thread::spawn(move || r.0.len());
+}
+
+fn main() {}
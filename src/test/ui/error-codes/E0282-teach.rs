@@ -0,0 +1,6 @@
+// compile-flags: -Z teach
+
+fn main() {
+    let _foo = Vec::new();
+    //~^ ERROR type annotations needed
+}
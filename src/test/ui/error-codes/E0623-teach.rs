@@ -0,0 +1,14 @@
+// compile-flags: -Z teach
+use std::marker::PhantomData;
+
+trait Convert<'a, 'b> {}
+impl<'long: 'short, 'short> Convert<'long, 'short> for () {}
+
+struct Foo<'a, 'b>(PhantomData<(&'a (), &'b ())>)
+where
+    (): Convert<'a, 'b>;
+
+fn convert<'a, 'b>(x: &'a u8, y: &'b u8, foo: Foo<'a, 'b>) {}
+//~^ ERROR lifetime mismatch
+
+fn main() {}
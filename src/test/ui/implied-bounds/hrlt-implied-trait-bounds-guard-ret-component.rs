@@ -0,0 +1,27 @@
+// The lifetime mismatch only labels the part of the return type with the mismatched
+// lifetime, rather than the whole return type.
+use std::marker::PhantomData;
+struct Foo<'a, 'b, T>(PhantomData<(&'a (), &'b (), T)>)
+where
+    T: Convert<'a, 'b>;
+
+trait Convert<'a, 'b>: Sized {
+    fn cast(&'a self) -> &'b Self;
+}
+impl<'long: 'short, 'short, T> Convert<'long, 'short> for T {
+    fn cast(&'long self) -> &'short T {
+        self
+    }
+}
+
+fn in_tuple<'in_, 'out, T>(x: Foo<'in_, 'out, T>, sadness: &'in_ T) -> (&'out T, usize) {
+    //~^ ERROR lifetime mismatch
+    (sadness.cast(), 0)
+}
+
+fn in_result<'in_, 'out, T>(x: Foo<'in_, 'out, T>, sadness: &'in_ T) -> Result<&'out T, ()> {
+    //~^ ERROR lifetime mismatch
+    Ok(sadness.cast())
+}
+
+fn main() {}
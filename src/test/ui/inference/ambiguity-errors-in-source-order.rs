@@ -0,0 +1,24 @@
+// The "type annotations needed" errors are reported in source order. Once a body has such an
+// error, its later ambiguities are considered fallout from it, so each of them is in a body
+// of its own.
+
+fn first<T: Into<String>>(x: i32) {}
+fn second<T: Into<String>>(x: i32) {}
+fn third<T: Into<String>>(x: i32) {}
+
+fn call_first() {
+    first(1);
+    //~^ ERROR type annotations needed
+}
+
+fn call_second() {
+    second(2);
+    //~^ ERROR type annotations needed
+}
+
+fn call_third() {
+    third(3);
+    //~^ ERROR type annotations needed
+}
+
+fn main() {}
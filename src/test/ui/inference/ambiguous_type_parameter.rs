@@ -1,3 +1,5 @@
+// compile-flags: -Zunstable-options
+
 use std::collections::HashMap;
 
 trait Store<K, V> {
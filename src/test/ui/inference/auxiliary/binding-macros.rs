@@ -0,0 +1,6 @@
+#[macro_export]
+macro_rules! bind {
+    ($name:ident = $init:expr) => {
+        let $name = $init;
+    };
+}
@@ -0,0 +1,29 @@
+// When the items being collected are `Result`s or `Option`s, point out the two ways of
+// collecting them.
+
+use std::num::ParseIntError;
+
+fn parse(s: &&str) -> Result<u32, ParseIntError> {
+    s.parse()
+}
+
+fn first_char(s: &&str) -> Option<char> {
+    s.chars().next()
+}
+
+fn parse_all(input: &[&str]) -> usize {
+    input.iter().map(parse).collect().len()
+    //~^ ERROR type annotations needed
+}
+
+fn first_chars(input: &[&str]) -> usize {
+    input.iter().map(first_char).collect().len()
+    //~^ ERROR type annotations needed
+}
+
+fn parse_all_in_closure(input: &[&str]) -> usize {
+    input.iter().map(|s| s.parse::<u32>()).collect().len()
+    //~^ ERROR type annotations needed
+}
+
+fn main() {}
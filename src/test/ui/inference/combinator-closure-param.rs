@@ -0,0 +1,25 @@
+// The parameter of a closure passed to an `Option` combinator is the only place its type can
+// be annotated. As the trait has a single impl, its type is suggested for the parameter.
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl Describe for u8 {
+    fn describe(&self) -> String {
+        format!("byte {}", self)
+    }
+}
+
+fn describe<T: Describe + ?Sized>(value: &T) -> String {
+    value.describe()
+}
+
+fn pick<T: ?Sized>() -> Option<&'static T> {
+    None
+}
+
+fn main() {
+    let _ = pick().map(|x| describe(x));
+    //~^ ERROR type annotations needed
+}
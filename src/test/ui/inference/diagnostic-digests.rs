@@ -0,0 +1,5 @@
+// check-diagnostic-digests
+
+fn main() {
+    let x = "hello".chars().rev().collect(); //~ ERROR E0282
+}
@@ -0,0 +1,6 @@
+use std::collections::HashMap;
+
+fn main() {
+    let _map = HashMap::new();
+    //~^ ERROR type annotations needed
+}
@@ -0,0 +1,33 @@
+// The note about empty collections is only given if the binding is only the receiver of
+// inherent methods of the collection which don't add anything to it.
+
+trait Fill {
+    fn fill(&mut self);
+}
+
+impl<T> Fill for Vec<T> {
+    fn fill(&mut self) {}
+}
+
+fn inherent() {
+    let v = Vec::new();
+    //~^ ERROR type annotations needed
+    v.len();
+}
+
+fn trait_method() {
+    let mut v = Vec::new();
+    //~^ ERROR type annotations needed
+    v.fill();
+}
+
+fn fill<T>(_: &mut T) {}
+
+fn passed_to_generic_fn() {
+    let mut v = Vec::new();
+    //~^ ERROR type annotations needed
+    v.len();
+    fill(&mut v);
+}
+
+fn main() {}
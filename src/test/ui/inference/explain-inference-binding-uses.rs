@@ -0,0 +1,8 @@
+// compile-flags: -Zexplain-inference
+
+fn main() {
+    let x = Vec::new();
+    //~^ ERROR type annotations needed
+    let _n = x.len();
+    drop(x);
+}
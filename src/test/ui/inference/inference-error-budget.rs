@@ -0,0 +1,21 @@
+// Check that once a function has run out of its budget for type annotation errors, the rest
+// of them are summarized. Only errors whose obligation has several candidate impls are still
+// reported once a function has an error, so the impls are local.
+
+// compile-flags: -Zinference-error-budget=2
+
+trait Backend {}
+
+impl Backend for u8 {}
+impl Backend for u16 {}
+
+fn make<T: Backend>() {}
+
+fn main() {
+    make();
+    //~^ ERROR type annotations needed
+    make();
+    //~^ ERROR type annotations needed
+    make();
+    make();
+}
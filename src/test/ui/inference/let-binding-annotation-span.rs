@@ -0,0 +1,30 @@
+// Check that the type annotation is suggested right after the name of the binding.
+
+#![feature(box_patterns)]
+
+fn mutable() {
+    let mut v = &[]; //~ ERROR type annotations needed
+    let _ = v.iter();
+}
+
+fn by_ref() {
+    let ref v = &[]; //~ ERROR type annotations needed
+    let _ = v.iter();
+}
+
+fn by_ref_mut() {
+    let ref mut v = &[]; //~ ERROR type annotations needed
+    let _ = v.iter();
+}
+
+fn boxed() {
+    let box v = Box::new(&[]); //~ ERROR type annotations needed
+    let _ = v.iter();
+}
+
+fn with_comment() {
+    let v /* a comment */ = &[]; //~ ERROR type annotations needed
+    let _ = v.iter();
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+// aux-build:binding-macros.rs
+// Tests that a binding needing a type annotation which was introduced by a macro points at the
+// macro instead of suggesting an annotation inside of it.
+
+extern crate binding_macros;
+
+macro_rules! bind {
+    ($name:ident = $init:expr) => {
+        let $name = $init;
+    };
+}
+
+macro_rules! bind_hidden {
+    ($init:expr) => {
+        let hidden = $init;
+    };
+}
+
+fn main() {
+    bind!(x = None);
+    //~^ ERROR type annotations needed
+
+    bind_hidden!(None);
+    //~^ ERROR type annotations needed
+
+    binding_macros::bind!(y = None);
+    //~^ ERROR type annotations needed
+}
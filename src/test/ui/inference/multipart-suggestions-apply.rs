@@ -0,0 +1,33 @@
+// run-rustfix
+// rustfix-maybe-incorrect
+// Check that the multipart suggestions specifying the types which can't be inferred apply
+// cleanly. They have placeholders, so the fixed code doesn't compile.
+
+#![allow(dead_code)]
+
+struct Thing;
+
+trait Method<T> {
+    fn method(&self) -> T;
+}
+
+impl<T> Method<T> for Thing {
+    fn method(&self) -> T {
+        loop {}
+    }
+}
+
+fn unbound_drop(_: impl Sized) {}
+
+fn closure_return() {
+    unbound_drop(|| []);
+    //~^ ERROR type annotations needed for `[_; 0]`
+}
+
+fn fully_qualified_method_call() {
+    let thing = Thing;
+    thing.method();
+    //~^ ERROR type annotations needed
+}
+
+fn main() {}
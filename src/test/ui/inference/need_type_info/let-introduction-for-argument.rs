@@ -0,0 +1,34 @@
+// Arguments passed to `impl Trait` parameters can't be annotated using a turbofish, so we
+// suggest moving them into a binding with a type instead.
+use std::fmt::Debug;
+
+fn takes_debug(_: impl Debug) {}
+
+fn in_statement() {
+    takes_debug(Default::default());
+    //~^ ERROR type annotations needed
+}
+
+fn in_nested_block() {
+    {
+        let _unit = takes_debug(Default::default());
+        //~^ ERROR type annotations needed
+    }
+}
+
+fn in_match_arm(x: Option<u8>) {
+    match x {
+        Some(_) => {
+            takes_debug(Default::default());
+            //~^ ERROR type annotations needed
+        }
+        None => {}
+    }
+}
+
+fn in_tail_expression() {
+    takes_debug(Default::default())
+    //~^ ERROR type annotations needed
+}
+
+fn main() {}
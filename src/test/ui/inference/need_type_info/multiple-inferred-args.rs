@@ -0,0 +1,8 @@
+// Check that all the parameters which are only given as `_` are mentioned in a single error.
+
+fn foo<T, U>() {}
+
+fn main() {
+    foo::<_, _>();
+    //~^ ERROR type annotations needed
+}
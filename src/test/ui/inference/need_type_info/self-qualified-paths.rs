@@ -0,0 +1,35 @@
+// Check the suggestions for paths going through `Self`. The parameters of the self type are known
+// inside of its impl, so the one needing an annotation is declared on the associated function, and
+// `Self { .. }` needs none. Naming the self type instead leaves its parameters to be inferred.
+
+struct Wrapper<T> {
+    len: usize,
+    value: Option<T>,
+}
+
+impl<T> Wrapper<T> {
+    fn with_capacity<C>() -> Self {
+        Wrapper { len: 0, value: None }
+    }
+
+    fn path() -> Self {
+        Self::with_capacity()
+        //~^ ERROR type annotations needed
+    }
+
+    fn qualified_path() -> Self {
+        <Self>::with_capacity()
+        //~^ ERROR type annotations needed
+    }
+
+    fn struct_expr() -> Self {
+        Self { len: 0, value: None }
+    }
+
+    fn type_name_path() -> usize {
+        Wrapper::with_capacity::<u8>().len
+        //~^ ERROR type annotations needed
+    }
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+// edition:2021
+// Types which can't be inferred in nested `async` blocks are part of the types of all of the
+// blocks, so they are reported at the header of the innermost one.
+
+fn main() {
+    async { async { |b: Vec<_>| {} } };
+    //~^ ERROR type annotations needed
+}
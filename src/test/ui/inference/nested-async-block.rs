@@ -0,0 +1,12 @@
+// edition:2021
+// Types which can't be inferred inside of nested `async` blocks are reported there, rather
+// than at the outermost block.
+
+fn main() {
+    let _ = async {
+        let _ = async {
+            let x = Vec::new();
+            //~^ ERROR type annotations needed
+        };
+    };
+}
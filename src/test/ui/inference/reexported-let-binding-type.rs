@@ -0,0 +1,19 @@
+// A type which is only visible through a re-export can still be named, so annotating a binding
+// with it is suggested.
+
+mod outer {
+    mod detail {
+        pub struct Secret<T>(pub Option<T>);
+    }
+
+    pub use detail::Secret;
+
+    pub fn make<T>() -> Secret<T> {
+        detail::Secret(None)
+    }
+}
+
+fn main() {
+    let secret = outer::make();
+    //~^ ERROR type annotations needed
+}
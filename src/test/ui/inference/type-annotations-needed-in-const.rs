@@ -0,0 +1,25 @@
+// Inference failures in const contexts say so, and don't suggest how to call functions which
+// can't be called there anyway.
+
+const fn size<T>() -> usize {
+    0
+}
+
+fn make<T>() -> Vec<T> {
+    Vec::new()
+}
+
+struct S;
+
+impl S {
+    const SIZE: usize = size();
+    //~^ ERROR type annotations needed in this constant expression
+}
+
+static LEN: usize = make().len();
+//~^ ERROR type annotations needed in this constant expression
+
+fn main() {
+    let _ = [0u8; size()];
+    //~^ ERROR type annotations needed in this constant expression
+}
@@ -0,0 +1,17 @@
+// Annotating a binding with a type which can't be named from where the binding is isn't
+// suggested.
+
+mod outer {
+    mod detail {
+        pub struct Secret<T>(pub Option<T>);
+    }
+
+    pub fn make<T>() -> detail::Secret<T> {
+        detail::Secret(None)
+    }
+}
+
+fn main() {
+    let secret = outer::make();
+    //~^ ERROR type annotations needed
+}
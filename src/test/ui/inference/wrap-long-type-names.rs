@@ -0,0 +1,28 @@
+// revisions: default narrow
+//[narrow] compile-flags: --diagnostic-width=60
+
+// Messages are soft-wrapped at the requested diagnostic width, shortening type names which
+// don't fit on a line of their own.
+
+struct Layer<T>(T);
+
+fn nested<T>() -> Layer<Layer<Layer<Layer<Layer<Layer<Vec<T>>>>>>> {
+    todo!()
+}
+
+fn parse(s: &&str) -> Result<u32, std::num::ParseIntError> {
+    s.parse()
+}
+
+fn binding() {
+    let value = nested();
+    //~^ ERROR type annotations needed
+}
+
+fn collected() {
+    let input = ["1", "2"];
+    let _ = input.iter().map(parse).collect().len();
+    //~^ ERROR type annotations needed
+}
+
+fn main() {}
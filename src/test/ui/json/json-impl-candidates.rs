@@ -0,0 +1,23 @@
+// compile-flags: --json=diagnostic-short --error-format=json
+
+// Check that the impls an ambiguity could have been resolved with are listed in the `metadata`
+// of the error, blanket impls included.
+
+struct Wrapper<T>(T);
+
+trait Convert<T> {
+    fn convert(self) -> T;
+}
+
+impl<T> Convert<Wrapper<T>> for T {
+    fn convert(self) -> Wrapper<T> { Wrapper(self) }
+}
+
+impl Convert<String> for u8 {
+    fn convert(self) -> String { String::new() }
+}
+
+fn main() {
+    0u8.convert();
+    //~^ ERROR type annotations needed
+}
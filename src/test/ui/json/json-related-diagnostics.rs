@@ -0,0 +1,25 @@
+// compile-flags: --json=diagnostic-short --error-format=json
+
+// Check that an inference error which may be fallout from an earlier error in the same body
+// points to that error, both in a note and in `related_diagnostics`.
+
+struct Thing;
+
+trait Method<T> {
+    fn method(self) -> T;
+}
+
+impl Method<i32> for Thing {
+    fn method(self) -> i32 { 0 }
+}
+
+impl Method<u32> for Thing {
+    fn method(self) -> u32 { 0 }
+}
+
+fn main() {
+    let _: u8 = "";
+    //~^ ERROR mismatched types
+    Thing.method();
+    //~^ ERROR type annotations needed
+}
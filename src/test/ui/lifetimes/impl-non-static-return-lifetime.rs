@@ -0,0 +1,19 @@
+// The `impl` returns data borrowed from `self`, so it can't return the `'static` reference its
+// `trait` requires, and changing its return type to one isn't suggested.
+
+trait Named {
+    fn name(&self) -> &'static str;
+}
+
+struct S {
+    name: String,
+}
+
+impl Named for S {
+    fn name(&self) -> &str {
+    //~^ ERROR `impl` item signature doesn't match `trait` item signature
+        &self.name
+    }
+}
+
+fn main() {}
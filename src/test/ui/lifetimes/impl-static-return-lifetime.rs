@@ -0,0 +1,24 @@
+// run-rustfix
+// The `impl` only returns `'static` data, so the `'static` lifetime its `trait` requires in the
+// return type can be written out.
+
+trait Named {
+    fn name(&self) -> &'static str;
+    fn bytes(&self) -> &'static [u8];
+}
+
+struct S;
+
+impl Named for S {
+    fn name(&self) -> &str {
+    //~^ ERROR `impl` item signature doesn't match `trait` item signature
+        "S"
+    }
+
+    fn bytes(&self) -> &[u8] {
+    //~^ ERROR `impl` item signature doesn't match `trait` item signature
+        Box::leak(Box::new(*b"S"))
+    }
+}
+
+fn main() {}
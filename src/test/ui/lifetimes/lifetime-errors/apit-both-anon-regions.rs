@@ -0,0 +1,8 @@
+#![feature(anonymous_lifetime_in_impl_trait)]
+
+fn push(v: &mut Vec<&u8>, mut it: impl Iterator<Item = &u8>) {
+    v.push(it.next().unwrap());
+    //~^ ERROR lifetime may not live long enough
+}
+
+fn main() {}
@@ -0,0 +1,3 @@
+pub trait Foreign {
+    fn push_into(v: &mut Vec<&u8>, x: &u8);
+}
@@ -0,0 +1,28 @@
+// Check where the new lifetime parameter goes in the suggestion when there are no written generic
+// parameters to put it in front of.
+
+fn with_where_clause(x: &mut Vec<&u8>, y: &u8)
+where
+    u8: Copy,
+{
+    x.push(y);
+    //~^ ERROR lifetime may not live long enough
+}
+
+fn with_const_param<const N: usize>(x: &mut Vec<&u8>, y: &u8) {
+    x.push(y);
+    //~^ ERROR lifetime may not live long enough
+}
+
+macro_rules! generic_fn {
+    ($name:ident, $param:ident) => {
+        fn $name<$param>(x: &mut Vec<&$param>, y: &$param) {
+            x.push(y);
+            //~^ ERROR lifetime may not live long enough
+        }
+    };
+}
+
+generic_fn!(with_macro_param, T);
+
+fn main() {}
@@ -0,0 +1,24 @@
+// Check that the lifetime suggestion for a provided method is made in terms of the trait, and
+// points out the local implementations of the trait and which of them override the method.
+
+trait WithoutLifetime {
+    fn push(x: &mut Vec<&u8>, y: &u8) {
+        x.push(y);
+        //~^ ERROR lifetime may not live long enough
+    }
+}
+
+trait WithLifetime<'a> {
+    fn push(x: &mut Vec<&u8>, y: &u8) {
+        x.push(y);
+        //~^ ERROR lifetime may not live long enough
+    }
+}
+
+impl WithLifetime<'_> for () {
+    fn push(_x: &mut Vec<&u8>, _y: &u8) {}
+}
+
+impl WithLifetime<'_> for u8 {}
+
+fn main() {}
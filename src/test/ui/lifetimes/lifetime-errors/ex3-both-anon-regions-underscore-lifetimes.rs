@@ -0,0 +1,23 @@
+// Lifetimes written as `'_` are replaced by the suggested name, and lifetimes elided in paths
+// are added to their generic arguments.
+
+use std::cell::Ref;
+
+struct Wrapper<'a>(&'a u8);
+
+fn push_ref(x: &mut Vec<Ref<'_, u8>>, y: Ref<'_, u8>) {
+    x.push(y);
+    //~^ ERROR lifetime may not live long enough
+}
+
+fn push_mixed(x: &mut Vec<&'_ u8>, y: &u8) {
+    x.push(y);
+    //~^ ERROR lifetime may not live long enough
+}
+
+fn push_wrapper(x: &mut Vec<Wrapper<'_>>, y: Wrapper) {
+    x.push(y);
+    //~^ ERROR lifetime may not live long enough
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+// A lifetime mismatch between lifetimes of the function points at their declarations when
+// these aren't on the same line as the types which are labeled for them.
+use std::marker::PhantomData;
+
+trait Convert<'a, 'b> {}
+impl<'long: 'short, 'short> Convert<'long, 'short> for () {}
+
+struct Foo<'a, 'b>(PhantomData<(&'a (), &'b ())>)
+where
+    (): Convert<'a, 'b>;
+
+fn convert<
+    'in_,
+    'out,
+>(x: Foo<'in_, 'out>) -> &'out u8 {
+    //~^ ERROR lifetime mismatch
+    loop {}
+}
+
+fn main() {}
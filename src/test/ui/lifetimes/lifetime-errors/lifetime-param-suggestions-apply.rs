@@ -0,0 +1,46 @@
+// run-rustfix
+// Check that the multipart suggestions naming two elided lifetimes with one parameter apply
+// cleanly, and that the fixed code compiles. Naming the lifetime of a `&impl Trait` argument
+// inserts it right where the `impl Trait` is replaced, and the two parts are merged.
+
+#![feature(anonymous_lifetime_in_impl_trait)]
+#![allow(dead_code)]
+
+struct Ref<'r>(&'r u8);
+
+struct S;
+
+fn introduce(x: &mut Vec<&u8>, y: &u8) {
+    x.push(y); //~ ERROR lifetime may not live long enough
+}
+
+fn reuse<'b>(_z: &'b u8, x: &mut Vec<&u8>, y: &u8) {
+    x.push(y); //~ ERROR lifetime may not live long enough
+}
+
+fn paths(x: &mut Vec<Ref>, y: Ref<'_>) {
+    x.push(y); //~ ERROR lifetime may not live long enough
+}
+
+impl S {
+    fn method<T>(&self, x: &mut Vec<&u8>, y: &u8, _t: T) {
+        x.push(y); //~ ERROR lifetime may not live long enough
+    }
+}
+
+trait Provided {
+    fn provided(x: &mut Vec<&u8>, y: &u8) {
+        x.push(y); //~ ERROR lifetime may not live long enough
+    }
+}
+
+trait Consumer<T> {
+    fn consume(&self, item: T);
+    fn own(&self) -> &u8;
+}
+
+fn feed(consumer: &impl Consumer<&u8>) {
+    consumer.consume(consumer.own()); //~ ERROR lifetime may not live long enough
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// run-rustfix
+// rustfix-maybe-incorrect
+// Check that the suggestion to tie the lifetimes together in the trait applies cleanly. The
+// implementation has to be changed after it as well, so the fixed code doesn't compile.
+
+#![allow(dead_code)]
+
+trait Local {
+    fn push_into(v: &mut Vec<&u8>, x: &u8);
+}
+
+impl Local for () {
+    fn push_into(v: &mut Vec<&u8>, x: &u8) {
+        v.push(x); //~ ERROR lifetime may not live long enough
+    }
+}
+
+fn main() {}
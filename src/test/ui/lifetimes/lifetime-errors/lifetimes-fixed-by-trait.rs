@@ -0,0 +1,39 @@
+// aux-build:lifetimes-fixed-by-trait.rs
+// Tests that naming the elided lifetimes of a method implementing a trait method isn't suggested
+// when the trait's signature leaves them unrelated, as the signature wouldn't match the trait,
+// and that it is pointed out instead.
+
+extern crate lifetimes_fixed_by_trait;
+
+use lifetimes_fixed_by_trait::Foreign;
+
+trait Local {
+    fn push_into(v: &mut Vec<&u8>, x: &u8);
+}
+
+impl Local for () {
+    fn push_into(v: &mut Vec<&u8>, x: &u8) {
+        v.push(x);
+        //~^ ERROR lifetime may not live long enough
+    }
+}
+
+impl Foreign for () {
+    fn push_into(v: &mut Vec<&u8>, x: &u8) {
+        v.push(x);
+        //~^ ERROR lifetime may not live long enough
+    }
+}
+
+trait Related {
+    fn push_into<'a>(v: &mut Vec<&'a u8>, x: &'a u8);
+}
+
+impl Related for () {
+    fn push_into(v: &mut Vec<&u8>, x: &u8) {
+        v.push(x);
+        //~^ ERROR lifetime may not live long enough
+    }
+}
+
+fn main() {}
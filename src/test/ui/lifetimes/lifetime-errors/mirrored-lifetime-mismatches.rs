@@ -0,0 +1,15 @@
+// Two lifetime mismatches which require opposite relations between the same two lifetimes
+// are reported as one error.
+use std::marker::PhantomData;
+
+trait Convert<'a, 'b> {}
+impl<'long: 'short, 'short> Convert<'long, 'short> for () {}
+
+struct Foo<'a, 'b>(PhantomData<(&'a (), &'b ())>)
+where
+    (): Convert<'a, 'b>;
+
+fn mirrored<'a, 'b>(a: &'a u8, b: &'b u8, x: Foo<'a, 'b>, y: Foo<'b, 'a>) {}
+//~^ ERROR lifetime mismatch
+
+fn main() {}
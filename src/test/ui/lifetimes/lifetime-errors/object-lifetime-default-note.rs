@@ -0,0 +1,27 @@
+// Check that a lifetime mismatch involving a `&dyn Trait` whose object lifetime isn't written
+// out points out when that lifetime isn't the one of the reference, because of a lifetime bound
+// on the trait.
+
+use std::marker::PhantomData;
+
+trait Tr {}
+trait Static: 'static {}
+
+trait Convert<'a, 'b> {}
+impl<'long: 'short, 'short> Convert<'long, 'short> for () {}
+
+struct Foo<'a, 'b>(PhantomData<(&'a (), &'b ())>)
+where
+    (): Convert<'a, 'b>;
+
+fn defaults_to_reference<'a, 'b>(y: &'a dyn Tr, x: Foo<'a, 'b>) -> &'b dyn Tr {
+    //~^ ERROR lifetime mismatch
+    loop {}
+}
+
+fn defaults_to_static<'a, 'b>(y: &'a dyn Static, x: Foo<'a, 'b>) -> &'b dyn Static {
+    //~^ ERROR lifetime mismatch
+    loop {}
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// The lifetime parameter suggestion names the elided lifetime of the reference behind the
+// `Pin` of a `self: Pin<&mut Self>` receiver.
+use std::pin::Pin;
+
+struct S;
+
+impl S {
+    fn get(self: Pin<&mut Self>, x: &u8) -> &u8 {
+        x
+        //~^ ERROR lifetime may not live long enough
+    }
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+// A lifetime mismatch involving a reference behind a wrapper like `Pin` or `Box` labels the
+// reference itself rather than the whole wrapper type.
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+trait Convert<'a, 'b> {}
+impl<'long: 'short, 'short> Convert<'long, 'short> for () {}
+
+struct Foo<'a, 'b>(PhantomData<(&'a (), &'b ())>)
+where
+    (): Convert<'a, 'b>;
+
+struct S;
+
+impl S {
+    fn poll<'a, 'b>(self: Pin<&'a mut Self>, x: Foo<'a, 'b>) -> Box<&'b u8> {
+        //~^ ERROR lifetime mismatch
+        loop {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+// Suggest copying or cloning the items of an iterator over borrowed data when collecting
+// the references is what makes the borrow escape.
+
+fn store<T: 'static>(_: Vec<T>) {}
+
+#[derive(Clone)]
+struct Name(String);
+
+struct Handle;
+
+fn copy_items(v: &Vec<u32>) {
+    store(v.iter().collect());
+    //~^ ERROR borrowed data escapes outside of function
+}
+
+fn clone_items(v: &Vec<Name>) {
+    store(v.iter().filter(|name| !name.0.is_empty()).collect());
+    //~^ ERROR borrowed data escapes outside of function
+}
+
+fn uncloneable_items(v: &Vec<Handle>) {
+    store(v.iter().collect());
+    //~^ ERROR borrowed data escapes outside of function
+}
+
+fn borrowed_items(v: &Vec<&str>) {
+    store(v.iter().collect());
+    //~^ ERROR borrowed data escapes outside of function
+}
+
+fn main() {}
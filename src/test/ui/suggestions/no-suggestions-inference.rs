@@ -0,0 +1,6 @@
+// compile-flags: -Zno-suggestions
+// The error, its primary span and its labels are unchanged, only the suggestion is dropped.
+
+fn main() {
+    let x = "hello".chars().rev().collect(); //~ ERROR E0282
+}
@@ -0,0 +1,9 @@
+// compile-flags: -Zno-suggestions
+// The error, its primary span and its labels are unchanged, only the suggestion is dropped.
+
+fn foo(x: &mut Vec<&u8>, y: &u8) {
+    x.push(y);
+    //~^ ERROR lifetime may not live long enough
+}
+
+fn main() {}
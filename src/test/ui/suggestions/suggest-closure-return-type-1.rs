@@ -1,3 +1,5 @@
+// compile-flags: -Zunstable-options
+
 fn unbound_drop(_: impl Sized) {}
 
 fn main() {
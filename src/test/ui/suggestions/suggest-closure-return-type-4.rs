@@ -0,0 +1,20 @@
+// Suggesting a return type for a closure with annotated parameters only replaces the type part of
+// an explicit return type, keeping the `->` and spacing that were written.
+
+fn unbound_drop(_: impl Sized) {}
+
+fn main() {
+    unbound_drop(|x: &str| -> _ { [] });
+    //~^ ERROR type annotations needed for `[_; 0]`
+    //~| HELP try giving this closure an explicit return type
+    unbound_drop(|x: &str| -> Vec<_> { vec![[]] });
+    //~^ ERROR type annotations needed for `Vec<[_; 0]>`
+    //~| HELP try giving this closure an explicit return type
+    unbound_drop(|x: &str|
+        ->   _ { [] });
+    //~^^ ERROR type annotations needed for `[_; 0]`
+    //~| HELP try giving this closure an explicit return type
+    unbound_drop(|x: &str| { [] });
+    //~^ ERROR type annotations needed for `[_; 0]`
+    //~| HELP try giving this closure an explicit return type
+}
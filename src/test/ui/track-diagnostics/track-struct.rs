@@ -0,0 +1,16 @@
+// compile-flags: -Z track-diagnostics
+
+// Errors created from a diagnostic struct also note the struct and its arguments.
+
+// Normalize the lines and columns, and the prefix the compiler's source may be remapped to, but
+// keep the files, which are what is tested here.
+// normalize-stderr-test "at (?:.*/)?(compiler/.*\.rs):\d+:\d+" -> "at $1:LL:CC"
+
+struct Foo {
+    x: i32,
+}
+
+fn main() {
+    let _ = Foo { x: 0, x: 0 };
+    //~^ ERROR field `x` specified more than once
+}
@@ -0,0 +1,12 @@
+// compile-flags: -Z track-diagnostics
+
+// Manual subdiagnostics which opt in also note where they were added.
+
+// Normalize the lines and columns, and the prefix the compiler's source may be remapped to, but
+// keep the files, which are what is tested here.
+// normalize-stderr-test "at (?:.*/)?(compiler/.*\.rs):\d+:\d+" -> "at $1:LL:CC"
+
+fn main() {
+    let x: Option<_> = None; //~ ERROR type annotations needed
+    x.unwrap().method_that_could_exist_on_some_type();
+}
@@ -0,0 +1,8 @@
+// compile-flags: -Z track-diagnostics
+
+// Normalize the locations, the compiler's source layout is not what is tested here.
+// normalize-stderr-test "(created|emitted) at .*:\d+:\d+" -> "$1 at COMPILER_SOURCE:LL:CC"
+
+fn main() {
+    let _ = missing;
+}
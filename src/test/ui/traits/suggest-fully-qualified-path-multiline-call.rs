@@ -0,0 +1,21 @@
+// The rest of a method call without arguments is replaced up to its closing parenthesis,
+// even when the method name is on another line than the receiver.
+
+struct Thing;
+
+trait Method<T> {
+    fn method(&mut self) -> T;
+}
+
+impl<T> Method<T> for Thing {
+    fn method(&mut self) -> T {
+        loop {}
+    }
+}
+
+fn main() {
+    let mut thing = Thing;
+    thing
+        .method ();
+    //~^ ERROR type annotations needed
+}
@@ -0,0 +1,29 @@
+// compile-flags: --diagnostic-width=60
+
+fn main() {
+    let thing = make_thing();
+    thing.method();
+    //~^ ERROR type annotations needed
+}
+
+use very_long_module_name::another_long_module_name::Method as _;
+
+fn make_thing() -> very_long_module_name::another_long_module_name::Thing {
+    very_long_module_name::another_long_module_name::Thing
+}
+
+mod very_long_module_name {
+    pub mod another_long_module_name {
+        pub struct Thing;
+
+        pub trait Method<T> {
+            fn method(&self) -> T;
+        }
+
+        impl<T> Method<T> for Thing {
+            fn method(&self) -> T {
+                loop {}
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// compile-flags: --diagnostic-width=60
+
+// `Thing` is already taken in this module and the module `Method` is declared in is private,
+// so neither of them is imported.
+
+struct Thing;
+
+fn main() {
+    let _ = Thing;
+    let thing = make_thing();
+    thing.method();
+    //~^ ERROR type annotations needed
+}
+
+use very_long_module_name::another_long_module_name::Method as _;
+
+fn make_thing() -> very_long_module_name::another_long_module_name::Thing {
+    very_long_module_name::another_long_module_name::Thing
+}
+
+mod very_long_module_name {
+    pub mod another_long_module_name {
+        pub use self::private_module::Method;
+
+        pub struct Thing;
+
+        mod private_module {
+            pub trait Method<T> {
+                fn method(&self) -> T;
+            }
+
+            impl<T> Method<T> for super::Thing {
+                fn method(&self) -> T {
+                    loop {}
+                }
+            }
+        }
+    }
+}
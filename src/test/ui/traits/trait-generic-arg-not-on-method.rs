@@ -0,0 +1,23 @@
+// The method has generic parameters of its own, but the one needing an annotation is declared on
+// the trait, so the suggestion points out that it can't be given in a turbofish on the method.
+
+struct Thing<X>(X);
+
+trait Method<T> {
+    fn method<U>(self, _: U) -> T;
+}
+
+impl<X> Method<i32> for Thing<X> {
+    fn method<U>(self, _: U) -> i32 { 0 }
+}
+
+impl<X> Method<u32> for Thing<X> {
+    fn method<U>(self, _: U) -> u32 { 0 }
+}
+
+fn main() {
+    let thing = Thing(true);
+    thing.method(42u8);
+    //~^ ERROR type annotations needed
+    //~| ERROR type annotations needed
+}
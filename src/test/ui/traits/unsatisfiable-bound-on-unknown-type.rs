@@ -0,0 +1,51 @@
+// When no `impl` could satisfy a bound on a type that is still unknown, that is what's reported,
+// rather than asking for a type annotation which couldn't help, or only a trait object could.
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+pub trait Backend<T>: sealed::Sealed {}
+
+pub struct Cpu;
+pub struct Gpu;
+
+impl sealed::Sealed for Cpu {}
+impl sealed::Sealed for Gpu {}
+impl Backend<f32> for Cpu {}
+impl Backend<f32> for Gpu {}
+impl Backend<f64> for Gpu {}
+
+// Not object safe, so `dyn Device<u8>` doesn't implement it either.
+pub trait Device<T>: Sized {}
+
+impl Device<f32> for Cpu {}
+impl Device<f32> for Gpu {}
+
+fn run<B: Backend<u8>>() {}
+
+fn run_f32<B: Backend<f32>>() {}
+
+fn open<D: Device<u8>>() {}
+
+fn unsatisfiable() {
+    run();
+    //~^ ERROR no `impl` can satisfy the trait bound `_: Backend<u8>`
+}
+
+fn unsatisfiable_not_object_safe() {
+    open();
+    //~^ ERROR no `impl` can satisfy the trait bound `_: Device<u8>`
+}
+
+fn ambiguous() {
+    run_f32();
+    //~^ ERROR type annotations needed
+}
+
+fn bounded<B: Backend<u8>>() {
+    run();
+    //~^ ERROR type annotations needed
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+// Check that a `?` in `main` which can't convert its error into `()` is the only error, even
+// though the type of its operand can't be inferred without it, and that returning a `Result` is
+// suggested. `number` is only moved afterwards, as ambiguities which aren't caused by the `?` or
+// its operand, like a `Display` bound from `println!`, are still reported.
+
+fn main() {
+    let number = "5".parse()?;
+    //~^ ERROR the `?` operator can only be used in a function that returns
+    drop(number);
+}
@@ -0,0 +1,10 @@
+// Like `question-mark-in-main-inference.rs`, but for `#[test]` functions.
+
+// compile-flags: --test
+
+#[test]
+fn parses() {
+    let number = "5".parse()?;
+    //~^ ERROR the `?` operator can only be used in a function that returns
+    drop(number);
+}
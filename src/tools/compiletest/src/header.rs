@@ -91,6 +91,8 @@ pub struct TestProps {
     pub check_stdout: bool,
     // Check stdout & stderr for output of run-pass test
     pub check_run_results: bool,
+    // For UI tests, check the Fluent identifier and argument names of each diagnostic
+    pub check_diagnostic_digests: bool,
     // For UI tests, allows compiler to generate arbitrary output to stdout
     pub dont_check_compiler_stdout: bool,
     // For UI tests, allows compiler to generate arbitrary output to stderr
@@ -154,6 +156,9 @@ pub struct TestProps {
     pub run_rustfix: bool,
     // If true, `rustfix` will only apply `MachineApplicable` suggestions.
     pub rustfix_only_machine_applicable: bool,
+    // If true, the code fixed by `rustfix` is allowed to fail to compile, because the applied
+    // suggestions are only `MaybeIncorrect` or have placeholders.
+    pub rustfix_maybe_incorrect: bool,
     pub assembly_output: Option<String>,
     // If true, the test is expected to ICE
     pub should_ice: bool,
@@ -173,6 +178,7 @@ mod directives {
     pub const FORCE_HOST: &'static str = "force-host";
     pub const CHECK_STDOUT: &'static str = "check-stdout";
     pub const CHECK_RUN_RESULTS: &'static str = "check-run-results";
+    pub const CHECK_DIAGNOSTIC_DIGESTS: &'static str = "check-diagnostic-digests";
     pub const DONT_CHECK_COMPILER_STDOUT: &'static str = "dont-check-compiler-stdout";
     pub const DONT_CHECK_COMPILER_STDERR: &'static str = "dont-check-compiler-stderr";
     pub const NO_PREFER_DYNAMIC: &'static str = "no-prefer-dynamic";
@@ -190,6 +196,7 @@ mod directives {
     pub const FAILURE_STATUS: &'static str = "failure-status";
     pub const RUN_RUSTFIX: &'static str = "run-rustfix";
     pub const RUSTFIX_ONLY_MACHINE_APPLICABLE: &'static str = "rustfix-only-machine-applicable";
+    pub const RUSTFIX_MAYBE_INCORRECT: &'static str = "rustfix-maybe-incorrect";
     pub const ASSEMBLY_OUTPUT: &'static str = "assembly-output";
     pub const STDERR_PER_BITWIDTH: &'static str = "stderr-per-bitwidth";
     pub const INCREMENTAL: &'static str = "incremental";
@@ -217,6 +224,7 @@ pub fn new() -> Self {
             force_host: false,
             check_stdout: false,
             check_run_results: false,
+            check_diagnostic_digests: false,
             dont_check_compiler_stdout: false,
             dont_check_compiler_stderr: false,
             no_prefer_dynamic: false,
@@ -236,6 +244,7 @@ pub fn new() -> Self {
             failure_status: -1,
             run_rustfix: false,
             rustfix_only_machine_applicable: false,
+            rustfix_maybe_incorrect: false,
             assembly_output: None,
             should_ice: false,
             stderr_per_bitwidth: false,
@@ -321,6 +330,11 @@ fn load_from(&mut self, testfile: &Path, cfg: Option<&str>, config: &Config) {
                 config.set_name_directive(ln, FORCE_HOST, &mut self.force_host);
                 config.set_name_directive(ln, CHECK_STDOUT, &mut self.check_stdout);
                 config.set_name_directive(ln, CHECK_RUN_RESULTS, &mut self.check_run_results);
+                config.set_name_directive(
+                    ln,
+                    CHECK_DIAGNOSTIC_DIGESTS,
+                    &mut self.check_diagnostic_digests,
+                );
                 config.set_name_directive(
                     ln,
                     DONT_CHECK_COMPILER_STDOUT,
@@ -398,6 +412,11 @@ fn load_from(&mut self, testfile: &Path, cfg: Option<&str>, config: &Config) {
                     RUSTFIX_ONLY_MACHINE_APPLICABLE,
                     &mut self.rustfix_only_machine_applicable,
                 );
+                config.set_name_directive(
+                    ln,
+                    RUSTFIX_MAYBE_INCORRECT,
+                    &mut self.rustfix_maybe_incorrect,
+                );
                 config.set_name_value_directive(
                     ln,
                     ASSEMBLY_OUTPUT,
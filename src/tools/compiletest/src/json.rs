@@ -44,6 +44,19 @@ struct DiagnosticSpan {
     expansion: Option<Box<DiagnosticSpanMacroExpansion>>,
 }
 
+#[derive(Deserialize)]
+struct DiagnosticDigest {
+    digest: String,
+}
+
+#[derive(Deserialize)]
+struct CompilationSummary {
+    #[allow(dead_code)]
+    errors: usize,
+    #[allow(dead_code)]
+    warnings: usize,
+}
+
 #[derive(Deserialize)]
 struct FutureIncompatReport {
     future_incompat_report: Vec<FutureBreakageItem>,
@@ -91,6 +104,16 @@ pub fn rustfix_diagnostics_only(output: &str) -> String {
         .collect()
 }
 
+/// Extracts the digests emitted with `-Zemit-diagnostic-digests`, one per line.
+pub fn extract_digests(output: &str) -> String {
+    output
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .filter_map(|line| serde_json::from_str::<DiagnosticDigest>(line).ok())
+        .map(|digest| format!("{}\n", digest.digest))
+        .collect()
+}
+
 pub fn extract_rendered(output: &str) -> String {
     output
         .lines()
@@ -118,12 +141,18 @@ pub fn extract_rendered(output: &str) -> String {
                                 .collect::<String>()
                         ))
                     }
+                } else if serde_json::from_str::<DiagnosticDigest>(line).is_ok() {
+                    // Digests are compared separately, see `extract_digests`.
+                    None
                 } else if serde_json::from_str::<ArtifactNotification>(line).is_ok() {
                     // Ignore the notification.
                     None
                 } else if serde_json::from_str::<UnusedExternNotification>(line).is_ok() {
                     // Ignore the notification.
                     None
+                } else if serde_json::from_str::<CompilationSummary>(line).is_ok() {
+                    // The summary repeats the counts of the rendered recap.
+                    None
                 } else {
                     print!(
                         "failed to decode compiler output as json: line: {}\noutput: {}",
@@ -155,8 +184,10 @@ fn parse_line(file_name: &str, line: &str, output: &str, proc_res: &ProcRes) ->
             }
             Err(error) => {
                 // Ignore the future compat report message - this is handled
-                // by `extract_rendered`
-                if serde_json::from_str::<FutureIncompatReport>(line).is_ok() {
+                // by `extract_rendered` - and the summary at the end of the output.
+                if serde_json::from_str::<FutureIncompatReport>(line).is_ok()
+                    || serde_json::from_str::<CompilationSummary>(line).is_ok()
+                {
                     vec![]
                 } else {
                     proc_res.fatal(
@@ -1,6 +1,8 @@
 // ignore-tidy-filelength
 
-use crate::common::{expected_output_path, UI_EXTENSIONS, UI_FIXED, UI_STDERR, UI_STDOUT};
+use crate::common::{
+    expected_output_path, UI_DIGESTS, UI_EXTENSIONS, UI_FIXED, UI_STDERR, UI_STDOUT,
+};
 use crate::common::{incremental_dir, output_base_dir, output_base_name, output_testname_unique};
 use crate::common::{Assembly, Incremental, JsDocTest, MirOpt, RunMake, RustdocJson, Ui};
 use crate::common::{Codegen, CodegenUnits, DebugInfo, Debugger, Rustdoc};
@@ -1950,6 +1952,11 @@ fn make_compile_args(
                 rustc.arg("-Ccodegen-units=1");
                 rustc.arg("-Zui-testing");
                 rustc.arg("-Zdeduplicate-diagnostics=no");
+                // Tests check each of the errors they expect, so summarizing doesn't help them.
+                rustc.arg("-Zinference-error-budget=0");
+                if self.props.check_diagnostic_digests {
+                    rustc.arg("-Zemit-diagnostic-digests");
+                }
                 // FIXME: use this for other modes too, for perf?
                 rustc.arg("-Cstrip=debuginfo");
             }
@@ -3180,6 +3187,11 @@ fn load_compare_outputs(
                     errors +=
                         self.compare_output(stderr_kind, &normalized_stderr, &expected_stderr);
                 }
+                if self.props.check_diagnostic_digests && !explicit_format {
+                    let expected_digests = self.load_expected_output(UI_DIGESTS);
+                    let digests = json::extract_digests(&proc_res.stderr);
+                    errors += self.compare_output(UI_DIGESTS, &digests, &expected_digests);
+                }
             }
             TestOutput::Run => {
                 errors += self.compare_output(stdout_kind, &normalized_stdout, &expected_stdout);
@@ -3348,9 +3360,14 @@ fn run_ui_test(&self) {
             }
         }
 
-        if self.props.run_rustfix && self.config.compare_mode.is_none() {
+        if self.props.run_rustfix
+            && !self.props.rustfix_maybe_incorrect
+            && self.config.compare_mode.is_none()
+        {
             // And finally, compile the fixed code and make sure it both
-            // succeeds and has no diagnostics.
+            // succeeds and has no diagnostics. Tests of suggestions which
+            // aren't expected to produce working code only compare the
+            // `.fixed` file.
             let mut rustc = self.make_compile_args(
                 &self.testpaths.file.with_extension(UI_FIXED),
                 TargetLocation::ThisFile(self.make_exe_name()),